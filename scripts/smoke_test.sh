@@ -11,8 +11,14 @@ mkdir -p "$WORK_DIR"
 echo "Generating synthetic dataset..."
 cargo run --bin synthetic_data_generator -- --output "$WORK_DIR/synthetic.csv" --rows 64 --seed 7
 
+echo "Profiling datasets..."
+cargo run --bin data_profile -- --input data/iris.csv --rules configs/iris_rules.json --output "$WORK_DIR/iris_profile.json"
+cargo run --bin data_profile -- --input "$WORK_DIR/synthetic.csv" --output "$WORK_DIR/profile.json"
+
 echo "Cleaning dataset..."
-cargo run --bin data_cleaning -- --input "$WORK_DIR/synthetic.csv" --output "$WORK_DIR/cleaned.csv"
+cargo run --bin data_cleaning -- --input "$WORK_DIR/synthetic.csv" --output "$WORK_DIR/cleaned.csv" --save-state "$WORK_DIR/outliers.json"
+cargo run --bin data_cleaning -- --input "$WORK_DIR/synthetic.csv" --output "$WORK_DIR/flagged.csv" --config configs/outliers.json --report "$WORK_DIR/outlier_report.json"
+cargo run --bin data_cleaning -- --input "$WORK_DIR/synthetic.csv" --output "$WORK_DIR/recleaned.csv" --load-state "$WORK_DIR/outliers.json"
 
 echo "Normalizing dataset..."
 cargo run --bin normalization -- --input "$WORK_DIR/cleaned.csv" --output "$WORK_DIR/normalized.csv"
@@ -20,12 +26,36 @@ cargo run --bin normalization -- --input "$WORK_DIR/cleaned.csv" --output "$WORK
 echo "Feature engineering dataset..."
 cargo run --bin feature_engineering -- --input "$WORK_DIR/normalized.csv" --output "$WORK_DIR/features.csv" --degree 2
 
+echo "Checking dataset manifests..."
+cargo run --bin dataset_version -- --action verify --input "$WORK_DIR/features.csv"
+cargo run --bin dataset_version -- --action diff --old "$WORK_DIR/cleaned.csv" --new "$WORK_DIR/normalized.csv" --output "$WORK_DIR/diff.json"
+
 echo "Training model..."
 cargo run --bin model_training -- --input "$WORK_DIR/features.csv" --output "$WORK_DIR/model.json"
 
+echo "Registering models..."
+cargo run --bin model_training -- --input "$WORK_DIR/features.csv" --output "$WORK_DIR/model_registered.json" --register smoke --registry "$WORK_DIR/registry"
+cargo run --bin model_registry -- --registry "$WORK_DIR/registry" --action register --name smoke --model "$WORK_DIR/model.json" --input "$WORK_DIR/features.csv"
+cargo run --bin model_registry -- --registry "$WORK_DIR/registry" --action stage --name smoke --version 2 --stage production
+cargo run --bin model_registry -- --registry "$WORK_DIR/registry" --action compare --name smoke --base 1 --candidate 2 > "$WORK_DIR/registry_compare.json"
+cargo run --bin model_registry -- --registry "$WORK_DIR/registry" --action get --name smoke --version production --output "$WORK_DIR/model_production.json"
+
+echo "Running config-driven pipeline..."
+cargo run --bin run_pipeline -- --config configs/iris_pipeline.json --output-dir "$WORK_DIR/config_run"
+
 echo "Evaluating model..."
 cargo run --bin model_evaluation -- --input "$WORK_DIR/features.csv" --model "$WORK_DIR/model.json" --output "$WORK_DIR/report.json"
 
+echo "Training and evaluating a binary artifact..."
+cargo run --bin model_training -- --input "$WORK_DIR/features.csv" --output "$WORK_DIR/model.bin" --format binary
+cargo run --bin model_evaluation -- --input "$WORK_DIR/features.csv" --model "$WORK_DIR/model.bin" --output "$WORK_DIR/report_binary.json"
+cmp -s <(grep -v created_at "$WORK_DIR/report.json") <(grep -v created_at "$WORK_DIR/report_binary.json")
+
+echo "Exporting model to ONNX and PMML..."
+cargo run --bin model_export -- --model "$WORK_DIR/model.json" --format onnx --output "$WORK_DIR/model.onnx"
+cargo run --bin model_export -- --model "$WORK_DIR/model.json" --format pmml --output "$WORK_DIR/model.pmml"
+cargo run --bin model_export -- --model "$WORK_DIR/model.json" --format c --output "$WORK_DIR/predictor.c"
+
 echo "Visualizing results..."
 cargo run --bin results_visualization -- --input "$WORK_DIR/report.json" > "$WORK_DIR/summary.txt"
 
@@ -36,11 +66,20 @@ echo "Simulating NFV integration..."
 cargo run --bin nfv_orchestrator_integration -- --model "$WORK_DIR/model.json" --output "$WORK_DIR/nfv_decision.json"
 
 echo "Running real-time decision making..."
-cargo run --bin real_time_decision_making -- --model "$WORK_DIR/model.json" --output "$WORK_DIR/realtime_decision.json" --rows 12
+cargo run --bin real_time_decision_making -- --model "$WORK_DIR/model.json" --input "$WORK_DIR/features.csv" --output "$WORK_DIR/realtime_decision.json" --drift-config configs/drift.json --drift-report "$WORK_DIR/drift_report.json"
 
+grep -q "\"metrics\"" "$WORK_DIR/config_run/report.json"
+grep -q "\"histogram\"" "$WORK_DIR/profile.json"
+grep -q "\"violations\": \[\]" "$WORK_DIR/iris_profile.json"
+grep -q "polynomial:degree=2" "$WORK_DIR/features.csv.manifest.json"
+grep -q "\"changed_rows\"" "$WORK_DIR/diff.json"
+grep -q "\"same_dataset\": true" "$WORK_DIR/registry_compare.json"
+grep -q "double predict(" "$WORK_DIR/predictor.c"
+grep -q "<RegressionModel" "$WORK_DIR/model.pmml"
 grep -q "Evaluation Summary" "$WORK_DIR/summary.txt"
 grep -q "\"action\"" "$WORK_DIR/sdn_decision.json"
 grep -q "\"action\"" "$WORK_DIR/nfv_decision.json"
 grep -q "\"action\"" "$WORK_DIR/realtime_decision.json"
+grep -q "\"jensen_shannon\"" "$WORK_DIR/drift_report.json"
 
 echo "Smoke test completed successfully."
@@ -1,17 +1,94 @@
-use rustml::data::cleaning::{clean_dataset, CleaningConfig};
+use rustml::data::cleaning::{
+    remove_rows_with_missing, OutlierAction, OutlierConfig, OutlierHandler, OutlierMethod,
+};
 use rustml::data::dataset::Dataset;
+use rustml::data::versioning::DatasetManifest;
+use rustml::data::Transformer;
 use rustml::utils::cli::ArgParser;
+use std::fmt::Display;
+
+const USAGE: &str = "usage: --input <path> --output <path> [--method percentile|iqr|mad|none] [--action clip|drop|flag] [--config <path>] [--save-state <path>] [--load-state <path>] [--report <path>]";
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("{err}");
+    std::process::exit(1);
+}
 
 fn main() {
     let parser = ArgParser::new();
-    let input = parser
-        .require("input")
-        .expect("usage: --input <path> --output <path>");
-    let output = parser
-        .require("output")
-        .expect("usage: --input <path> --output <path>");
+    let input = parser.require("input").expect(USAGE);
+    let output = parser.require("output").expect(USAGE);
+    let args = parser.parse();
     let dataset = Dataset::from_csv(&input).expect("failed to load input dataset");
-    let cleaned = clean_dataset(&dataset, CleaningConfig::default()).expect("cleaning failed");
-    cleaned.to_csv(&output).expect("failed to write cleaned dataset");
+    let complete = remove_rows_with_missing(&dataset).expect("cleaning failed");
+
+    let handler = match args.get("load-state") {
+        Some(path) => {
+            if let Some(option) = ["method", "action", "config"]
+                .iter()
+                .find(|option| args.contains_key(**option))
+            {
+                fail(format!(
+                    "--{option} cannot be combined with --load-state\n{USAGE}"
+                ));
+            }
+            OutlierHandler::load(path)
+                .unwrap_or_else(|err| fail(format!("failed to load outlier state {path}: {err}")))
+        }
+        None => {
+            let mut config = match args.get("config") {
+                Some(path) => std::fs::read_to_string(path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|content| {
+                        serde_json::from_str::<OutlierConfig>(&content)
+                            .map_err(|err| err.to_string())
+                    })
+                    .unwrap_or_else(|err| fail(format!("invalid outlier config {path}: {err}"))),
+                None => OutlierConfig::default(),
+            };
+            if let Some(name) = args.get("method") {
+                config.detection.method =
+                    OutlierMethod::from_name(name).unwrap_or_else(|| fail(USAGE));
+            }
+            if let Some(name) = args.get("action") {
+                config.action = OutlierAction::from_name(name).unwrap_or_else(|| fail(USAGE));
+            }
+            if let Err(message) = config.detection.validate() {
+                fail(format!("invalid outlier detection: {message}"));
+            }
+            let mut handler = OutlierHandler::new(config);
+            handler.fit(&complete).unwrap_or_else(|err| fail(err));
+            handler
+        }
+    };
+    let (cleaned, report) = handler.apply(&complete).unwrap_or_else(|err| fail(err));
+
+    cleaned
+        .to_csv(&output)
+        .expect("failed to write cleaned dataset");
     println!("Cleaned dataset written to {output}");
+    println!(
+        "Outliers ({}): {} of {} rows affected",
+        report.action.name(),
+        report.affected_rows,
+        report.rows
+    );
+    for column in report.columns.iter().filter(|c| c.below + c.above > 0) {
+        println!(
+            "  {}: {} below {:.4}, {} above {:.4}",
+            column.column, column.below, column.lower, column.above, column.upper
+        );
+    }
+    DatasetManifest::derived(&cleaned, &input, &dataset, "clean_dataset")
+        .save_beside(&output)
+        .expect("failed to write dataset manifest");
+    if let Some(path) = args.get("save-state") {
+        handler.save(path).expect("failed to write outlier state");
+        println!("Outlier state written to {path}");
+    }
+    if let Some(path) = args.get("report") {
+        let content = serde_json::to_string_pretty(&report).expect("serialize outlier report");
+        std::fs::write(path, content).expect("failed to write outlier report");
+        println!("Outlier report written to {path}");
+    }
 }
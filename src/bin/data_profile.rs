@@ -0,0 +1,89 @@
+use rustml::data::dataset::Dataset;
+use rustml::data::profiling::{DataProfile, ProfileConfig, RuleSet};
+use rustml::utils::cli::ArgParser;
+use std::fmt::Display;
+
+const USAGE: &str =
+    "usage: --input <data.csv> [--target <column>] [--rules <rules.json>] [--bins <n>] [--output <profile.json>]";
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("{err}");
+    std::process::exit(1);
+}
+
+fn format_value(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |value| format!("{value:.4}"))
+}
+
+fn main() {
+    let parser = ArgParser::new();
+    let args = parser.parse();
+    let input = parser.require("input").unwrap_or_else(|_| fail(USAGE));
+    let dataset = match args.get("target") {
+        Some(target) => Dataset::from_csv_with_target(&input, target),
+        None => Dataset::from_csv(&input),
+    }
+    .unwrap_or_else(|err| fail(err));
+    let config = ProfileConfig {
+        bins: parser
+            .optional("bins", "10")
+            .parse::<usize>()
+            .unwrap_or_else(|_| fail("--bins must be a positive integer")),
+        ..ProfileConfig::default()
+    };
+
+    let profile = DataProfile::new(&dataset, &config);
+    println!(
+        "{input}: {} rows, target '{}'",
+        profile.rows, profile.target
+    );
+    println!(
+        "{:<24} {:<10} {:>7} {:>8} {:>10} {:>10} {:>10} {:>10} {:>8}",
+        "column", "type", "missing", "distinct", "min", "max", "mean", "std", "corr"
+    );
+    for column in &profile.columns {
+        println!(
+            "{:<24} {:<10} {:>7} {:>8} {:>10} {:>10} {:>10} {:>10} {:>8}",
+            column.name,
+            format!("{:?}", column.column_type).to_lowercase(),
+            column.missing,
+            column.distinct,
+            format_value(column.min),
+            format_value(column.max),
+            format_value(column.mean),
+            format_value(column.std_dev),
+            column
+                .target_correlation
+                .map_or("-".to_string(), |corr| format!("{corr:.3}"))
+        );
+    }
+
+    let validation = args.get("rules").map(|path| {
+        let rules = RuleSet::load(path).unwrap_or_else(|err| fail(err));
+        rules.validate(&dataset)
+    });
+    if let Some(report) = &validation {
+        println!(
+            "Validation: {} rules, {} violations",
+            report.rules_checked,
+            report.violations.len()
+        );
+        for violation in &report.violations {
+            println!("  {}: {}", violation.column, violation.message);
+        }
+    }
+
+    if let Some(output) = args.get("output") {
+        let report = serde_json::json!({
+            "input": input,
+            "profile": profile,
+            "validation": validation,
+        });
+        let content = serde_json::to_string_pretty(&report).expect("serialize profile");
+        std::fs::write(output, content).unwrap_or_else(|err| fail(err));
+        println!("Profile written to {output}");
+    }
+    if validation.is_some_and(|report| !report.passed()) {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,75 @@
+use rustml::data::dataset::Dataset;
+use rustml::data::versioning::{diff_csv, manifest_path, DatasetManifest, Provenance};
+use rustml::utils::cli::ArgParser;
+use std::fmt::Display;
+
+const USAGE: &str = "usage: --action manifest|verify|diff
+  manifest --input <data.csv> [--output <manifest.json>]
+  verify   --input <data.csv> [--manifest <manifest.json>]
+  diff     --old <a.csv> --new <b.csv> [--key <column>] [--output <diff.json>]";
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("{err}");
+    std::process::exit(1);
+}
+
+fn main() {
+    let parser = ArgParser::new();
+    let args = parser.parse();
+    let action = parser.require("action").unwrap_or_else(|_| fail(USAGE));
+    let require = |key: &str| parser.require(key).unwrap_or_else(|err| fail(err));
+
+    match action.as_str() {
+        "manifest" => {
+            let input = require("input");
+            let dataset = Dataset::from_csv(&input).unwrap_or_else(|err| fail(err));
+            let output = parser.optional("output", &manifest_path(&input));
+            let provenance = Provenance {
+                source: Some(input.clone()),
+                ..Provenance::default()
+            };
+            let manifest = DatasetManifest::new(&dataset, provenance);
+            manifest.save(&output).unwrap_or_else(|err| fail(err));
+            println!(
+                "Manifest for {input} ({} rows, fingerprint {}) written to {output}",
+                manifest.rows, manifest.fingerprint
+            );
+        }
+        "verify" => {
+            let input = require("input");
+            let path = parser.optional("manifest", &manifest_path(&input));
+            let manifest = DatasetManifest::load(&path).unwrap_or_else(|err| fail(err));
+            let dataset = Dataset::from_csv(&input).unwrap_or_else(|err| fail(err));
+            if !manifest.matches(&dataset) {
+                fail(format!(
+                    "{input} does not match {path}: fingerprint {} != {}",
+                    dataset.fingerprint(),
+                    manifest.fingerprint
+                ));
+            }
+            println!("{input} matches {path}");
+        }
+        "diff" => {
+            let old = require("old");
+            let new = require("new");
+            let diff = diff_csv(&old, &new, args.get("key").map(String::as_str))
+                .unwrap_or_else(|err| fail(err));
+            println!(
+                "rows: {} -> {} (+{} -{} ~{}), columns: +{:?} -{:?}",
+                diff.rows_before,
+                diff.rows_after,
+                diff.added_rows.len(),
+                diff.removed_rows.len(),
+                diff.changed_rows.len(),
+                diff.added_columns,
+                diff.removed_columns
+            );
+            if let Some(output) = args.get("output") {
+                let content = serde_json::to_string_pretty(&diff).expect("serialize diff");
+                std::fs::write(output, content).unwrap_or_else(|err| fail(err));
+                println!("Diff written to {output}");
+            }
+        }
+        _ => fail(USAGE),
+    }
+}
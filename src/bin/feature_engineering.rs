@@ -1,5 +1,6 @@
 use rustml::data::dataset::Dataset;
 use rustml::data::feature_engineering::{add_polynomial_features, FeatureEngineeringConfig};
+use rustml::data::versioning::DatasetManifest;
 use rustml::utils::cli::ArgParser;
 
 fn main() {
@@ -14,11 +15,23 @@ fn main() {
         .optional("degree", "2")
         .parse::<usize>()
         .unwrap_or(2);
+    let config = FeatureEngineeringConfig {
+        polynomial_degree: degree,
+        interaction_only: parser.optional("interaction-only", "false") == "true",
+        include_bias: parser.optional("bias", "false") == "true",
+        max_features: parser
+            .parse()
+            .get("max-features")
+            .and_then(|value| value.parse::<usize>().ok()),
+    };
     let dataset = Dataset::from_csv(&input).expect("failed to load input dataset");
-    let engineered = add_polynomial_features(&dataset, FeatureEngineeringConfig { polynomial_degree: degree })
-        .expect("feature engineering failed");
+    let step = format!("polynomial:degree={degree}");
+    let engineered = add_polynomial_features(&dataset, config).expect("feature engineering failed");
     engineered
         .to_csv(&output)
         .expect("failed to write feature engineered dataset");
     println!("Feature engineered dataset written to {output}");
+    DatasetManifest::derived(&engineered, &input, &dataset, &step)
+        .save_beside(&output)
+        .expect("failed to write dataset manifest");
 }
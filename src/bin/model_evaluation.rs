@@ -1,6 +1,5 @@
 use rustml::data::dataset::Dataset;
 use rustml::io::load_model;
-use rustml::ml::Model;
 use rustml::utils::cli::ArgParser;
 use rustml::utils::evaluation::{mean_absolute_error, mean_squared_error, root_mean_squared_error};
 use rustml::utils::report::EvaluationReport;
@@ -19,7 +18,10 @@ fn main() {
 
     let dataset = Dataset::from_csv(&input).expect("failed to load dataset");
     let artifact = load_model(&model_path).expect("failed to load model artifact");
-    let predictions = artifact.model.predict(&dataset);
+    let predictions = artifact.predict(&dataset).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
     let report = EvaluationReport {
         mse: mean_squared_error(&predictions, &dataset.target),
         rmse: root_mean_squared_error(&predictions, &dataset.target),
@@ -0,0 +1,34 @@
+use rustml::io::codegen::{save_source, Language};
+use rustml::io::export::LinearExport;
+use rustml::io::load_model;
+use rustml::io::onnx::save_onnx;
+use rustml::io::pmml::save_pmml;
+use rustml::utils::cli::ArgParser;
+
+const USAGE: &str = "usage: --model <model.json> --format onnx|pmml|rust|c --output <path>";
+
+fn main() {
+    let parser = ArgParser::new();
+    let model_path = parser.require("model").expect(USAGE);
+    let format = parser.require("format").expect(USAGE);
+    let output = parser.require("output").expect(USAGE);
+
+    let artifact = load_model(&model_path).expect("failed to load model artifact");
+    let export = LinearExport::from_artifact(&artifact).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+    match format.as_str() {
+        "onnx" => save_onnx(&output, &export).expect("failed to write ONNX model"),
+        "pmml" => save_pmml(&output, &export).expect("failed to write PMML document"),
+        other => {
+            let language = Language::from_name(other).expect(USAGE);
+            save_source(&output, &export, language).expect("failed to write predictor source")
+        }
+    }
+    println!(
+        "Exported {} ({} features) as {format} to {output}",
+        artifact.model.kind(),
+        export.num_features()
+    );
+}
@@ -0,0 +1,110 @@
+use rustml::config::AppConfig;
+use rustml::data::dataset::Dataset;
+use rustml::io::{load_model, save_model};
+use rustml::registry::{ModelRef, ModelRegistry, RunRecord, Stage};
+use rustml::utils::cli::ArgParser;
+use std::fmt::Display;
+
+const USAGE: &str = "usage: --action register|list|show|compare|stage|get [--registry <dir>]
+  register --name <name> --model <model.json> --input <training.csv>
+  list     [--name <name>]
+  show     --name <name> [--version <n|latest|staging|production>]
+  compare  --name <name> --base <n> --candidate <n>
+  stage    --name <name> --version <n> --stage none|staging|production|archived
+  get      --name <name> [--version <n|latest|staging|production>] --output <model.json>";
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("{err}");
+    std::process::exit(1);
+}
+
+fn version_number(parser: &ArgParser, key: &str) -> u32 {
+    match ModelRef::parse(&parser.require(key).unwrap_or_else(|err| fail(err))) {
+        Some(ModelRef::Version(version)) => version,
+        _ => fail(format!("--{key} must be a version number")),
+    }
+}
+
+fn print_run(run: &RunRecord) {
+    println!(
+        "{} v{} [{}] {} mse={:.4} rmse={:.4} mae={:.4} data={} ({} rows)",
+        run.name,
+        run.version,
+        run.stage.name(),
+        run.model_kind,
+        run.metrics.mse,
+        run.metrics.rmse,
+        run.metrics.mae,
+        run.dataset.fingerprint,
+        run.dataset.rows
+    );
+}
+
+fn main() {
+    let parser = ArgParser::new();
+    let action = parser.require("action").unwrap_or_else(|_| fail(USAGE));
+    let registry = match parser.parse().get("registry") {
+        Some(root) => ModelRegistry::new(root),
+        None => ModelRegistry::from_config(&AppConfig::default()),
+    };
+    let name = || parser.require("name").unwrap_or_else(|err| fail(err));
+    let reference = || {
+        let version = parser.optional("version", "latest");
+        ModelRef::parse(&version).unwrap_or_else(|| fail(format!("invalid --version '{version}'")))
+    };
+
+    match action.as_str() {
+        "register" => {
+            let model = parser.require("model").unwrap_or_else(|err| fail(err));
+            let input = parser.require("input").unwrap_or_else(|err| fail(err));
+            let artifact = load_model(&model).unwrap_or_else(|err| fail(err));
+            let dataset = Dataset::from_csv(&input).unwrap_or_else(|err| fail(err));
+            let run = registry
+                .register(&name(), &artifact, &dataset, Some(&input))
+                .unwrap_or_else(|err| fail(err));
+            println!("Registered {} version {}", run.name, run.version);
+        }
+        "list" => {
+            let runs = match parser.parse().get("name") {
+                Some(name) => registry.runs(name),
+                None => registry.list(),
+            };
+            for run in runs.unwrap_or_else(|err| fail(err)) {
+                print_run(&run);
+            }
+        }
+        "show" => {
+            let run = registry
+                .resolve(&name(), reference())
+                .unwrap_or_else(|err| fail(err));
+            println!("{}", serde_json::to_string_pretty(&run).unwrap());
+        }
+        "compare" => {
+            let base = version_number(&parser, "base");
+            let candidate = version_number(&parser, "candidate");
+            let comparison = registry
+                .compare(&name(), base, candidate)
+                .unwrap_or_else(|err| fail(err));
+            println!("{}", serde_json::to_string_pretty(&comparison).unwrap());
+        }
+        "stage" => {
+            let version = version_number(&parser, "version");
+            let stage = parser.require("stage").unwrap_or_else(|err| fail(err));
+            let stage = Stage::from_name(&stage)
+                .unwrap_or_else(|| fail(format!("unknown stage '{stage}'")));
+            let run = registry
+                .set_stage(&name(), version, stage)
+                .unwrap_or_else(|err| fail(err));
+            println!("{} v{} is now {}", run.name, run.version, stage.name());
+        }
+        "get" => {
+            let output = parser.require("output").unwrap_or_else(|err| fail(err));
+            let (run, artifact) = registry
+                .load(&name(), reference())
+                .unwrap_or_else(|err| fail(err));
+            save_model(&output, &artifact).unwrap_or_else(|err| fail(err));
+            println!("Wrote {} v{} to {output}", run.name, run.version);
+        }
+        _ => fail(USAGE),
+    }
+}
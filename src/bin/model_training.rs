@@ -1,23 +1,52 @@
+use rustml::config::AppConfig;
 use rustml::data::dataset::Dataset;
-use rustml::io::{save_model, ModelArtifact};
-use rustml::pipeline::train_linear_pipeline;
+use rustml::io::{save_model_as, ArtifactFormat, ModelArtifact};
+use rustml::ml::parallel::Parallelism;
+use rustml::pipeline::train_linear_pipeline_with_parallelism;
+use rustml::registry::ModelRegistry;
 use rustml::utils::cli::ArgParser;
 
+const USAGE: &str = "usage: --input <path> --output <model.json> [--threads <n>] [--format json|binary] [--register <name> [--registry <dir>]]";
+
 fn main() {
     let parser = ArgParser::new();
-    let input = parser
-        .require("input")
-        .expect("usage: --input <path> --output <model.json>");
-    let output = parser
-        .require("output")
-        .expect("usage: --input <path> --output <model.json>");
+    let input = parser.require("input").expect(USAGE);
+    let output = parser.require("output").expect(USAGE);
+    let threads = parser
+        .optional("threads", "1")
+        .parse::<usize>()
+        .unwrap_or_else(|_| {
+            eprintln!("--threads must be a non-negative integer\n{USAGE}");
+            std::process::exit(1);
+        });
+    let format = ArtifactFormat::from_name(&parser.optional("format", "json"))
+        .expect("--format must be json or binary");
 
     let dataset = Dataset::from_csv(&input).expect("failed to load dataset");
-    let result = train_linear_pipeline(&dataset).expect("training pipeline failed");
-    let artifact = ModelArtifact {
-        model: result.model,
-        summary: result.summary,
-    };
-    save_model(&output, &artifact).expect("failed to write model artifact");
+    let result =
+        train_linear_pipeline_with_parallelism(&dataset, Parallelism::with_threads(threads))
+            .expect("training pipeline failed");
+    let artifact = ModelArtifact::from_pipeline(&result.pipeline, &dataset, result.summary)
+        .expect("failed to package model artifact");
+    save_model_as(&output, &artifact, format).expect("failed to write model artifact");
     println!("Model artifact written to {output}");
+
+    if let Some(name) = parser.parse().get("register") {
+        let registry = match parser.parse().get("registry") {
+            Some(root) => ModelRegistry::new(root),
+            None => ModelRegistry::from_config(&AppConfig::default()),
+        };
+        let run = registry
+            .register(name, &artifact, &dataset, Some(&input))
+            .unwrap_or_else(|err| {
+                eprintln!("{err}");
+                std::process::exit(1);
+            });
+        println!(
+            "Registered {} version {} in {}",
+            run.name,
+            run.version,
+            registry.root().display()
+        );
+    }
 }
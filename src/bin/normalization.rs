@@ -1,19 +1,42 @@
 use rustml::data::dataset::Dataset;
+use rustml::data::versioning::DatasetManifest;
+use rustml::data::Transformer;
 use rustml::utils::cli::ArgParser;
-use rustml::utils::normalization::normalize_z_score;
+use rustml::utils::normalization::Scaler;
+
+const USAGE: &str = "usage: --input <path> --output <path> [--method zscore|minmax|robust|maxabs] [--save-state <path>] [--load-state <path>]";
 
 fn main() {
     let parser = ArgParser::new();
-    let input = parser
-        .require("input")
-        .expect("usage: --input <path> --output <path>");
-    let output = parser
-        .require("output")
-        .expect("usage: --input <path> --output <path>");
+    let input = parser.require("input").expect(USAGE);
+    let output = parser.require("output").expect(USAGE);
+    let args = parser.parse();
     let dataset = Dataset::from_csv(&input).expect("failed to load input dataset");
-    let (normalized, _) = normalize_z_score(&dataset).expect("normalization failed");
+    let (scaler, normalized) = match args.get("load-state") {
+        Some(path) => {
+            let content = std::fs::read_to_string(path).expect("failed to read scaler state");
+            let scaler: Scaler = serde_json::from_str(&content).expect("invalid scaler state");
+            let normalized = scaler.transform(&dataset).expect("normalization failed");
+            (scaler, normalized)
+        }
+        None => {
+            let method = parser.optional("method", "zscore");
+            let mut scaler = Scaler::from_name(&method).expect(USAGE);
+            let normalized = scaler.fit_transform(&dataset).expect("normalization failed");
+            (scaler, normalized)
+        }
+    };
     normalized
         .to_csv(&output)
         .expect("failed to write normalized dataset");
     println!("Normalized dataset written to {output}");
+    let step = format!("scale:{}", scaler.kind());
+    DatasetManifest::derived(&normalized, &input, &dataset, &step)
+        .save_beside(&output)
+        .expect("failed to write dataset manifest");
+    if let Some(path) = args.get("save-state") {
+        let content = serde_json::to_string_pretty(&scaler).expect("serialize scaler state");
+        std::fs::write(path, content).expect("failed to write scaler state");
+        println!("Scaler state written to {path}");
+    }
 }
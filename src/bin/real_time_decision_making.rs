@@ -1,24 +1,68 @@
+use rustml::data::dataset::Dataset;
 use rustml::data::synthetic::{generate_network_dataset, SyntheticConfig};
 use rustml::deployment::sdn_decision;
+use rustml::drift::DriftConfig;
 use rustml::io::load_model;
-use rustml::ml::Model;
 use rustml::utils::cli::ArgParser;
 
+const USAGE: &str = "usage: --model <model.json> --output <decision.json> [--input <features.csv>] [--drift-config <drift.json>] [--drift-report <drift_report.json>]";
+
 fn main() {
     let parser = ArgParser::new();
-    let model_path = parser
-        .require("model")
-        .expect("usage: --model <model.json> --output <decision.json>");
-    let output = parser
-        .require("output")
-        .expect("usage: --model <model.json> --output <decision.json>");
+    let args = parser.parse();
+    let model_path = parser.require("model").expect(USAGE);
+    let output = parser.require("output").expect(USAGE);
     let rows = parser
         .optional("rows", "8")
         .parse::<usize>()
         .unwrap_or(8);
     let artifact = load_model(&model_path).expect("failed to load model");
-    let dataset = generate_network_dataset(SyntheticConfig { rows, seed: 7 });
-    let predictions = artifact.model.predict(&dataset);
+    let dataset = match args.get("input") {
+        Some(input) => Dataset::from_csv(input).expect("failed to load input dataset"),
+        None => generate_network_dataset(SyntheticConfig {
+            rows,
+            seed: 7,
+            ..SyntheticConfig::default()
+        }),
+    };
+    let predictions = artifact.predict(&dataset).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+
+    match &artifact.reference {
+        Some(reference) => {
+            let config = match args.get("drift-config") {
+                Some(path) => DriftConfig::load(path).expect("failed to load drift config"),
+                None => DriftConfig::default(),
+            };
+            let report = reference
+                .compare(&dataset, Some(&predictions), &config)
+                .unwrap_or_else(|err| {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                });
+            if !report.evaluated {
+                println!(
+                    "Drift scored on {} rows, below min_rows={}; alerts suppressed",
+                    report.rows, config.min_rows
+                );
+            }
+            for alert in &report.alerts {
+                eprintln!(
+                    "Drift alert: {} {:?} = {:.4} exceeds {:.4}",
+                    alert.feature, alert.metric, alert.value, alert.threshold
+                );
+            }
+            if let Some(path) = args.get("drift-report") {
+                let content = serde_json::to_string_pretty(&report).expect("serialize drift report");
+                std::fs::write(path, content).expect("write drift report");
+                println!("Drift report written to {path}");
+            }
+        }
+        None => println!("Model has no reference profile; skipping drift checks"),
+    }
+
     let avg_score = predictions.iter().sum::<f64>() / predictions.len().max(1) as f64;
     let decision = sdn_decision(avg_score);
     let content = serde_json::to_string_pretty(&decision).expect("serialize decision");
@@ -0,0 +1,45 @@
+use rustml::config::AppConfig;
+use rustml::utils::cli::ArgParser;
+
+const USAGE: &str = "usage: --config <pipeline.json> [--input <path>] [--output-dir <dir>]";
+
+fn main() {
+    let parser = ArgParser::new();
+    let path = parser.require("config").expect(USAGE);
+    let args = parser.parse();
+    let mut config = match AppConfig::from_file(&path) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    };
+    if let Some(spec) = config.pipeline.as_mut() {
+        if let Some(input) = args.get("input") {
+            spec.input = input.clone();
+        }
+        if let Some(directory) = args.get("output-dir") {
+            spec.outputs.directory = Some(directory.clone());
+        }
+    }
+    let (_, report) = match config.run_pipeline() {
+        Ok(result) => result,
+        Err(err) => {
+            eprintln!("pipeline failed: {err}");
+            std::process::exit(1);
+        }
+    };
+    println!(
+        "Trained on {} rows ({} input features, {} steps), evaluated on {} rows",
+        report.train_rows,
+        report.features.len(),
+        report.steps.len(),
+        report.test_rows
+    );
+    for (metric, value) in &report.metrics {
+        println!("  {metric}: {value:.6}");
+    }
+    for output in &report.outputs {
+        println!("Wrote {output}");
+    }
+}
@@ -1,8 +1,19 @@
-use rustml::data::synthetic::{generate_network_dataset, SyntheticConfig};
+use rustml::data::synthetic::{
+    generate_link_time_series, generate_network_traffic, generate_traffic_classes,
+    ClassificationConfig, SyntheticConfig, TimeSeriesConfig,
+};
+use rustml::data::versioning::{DatasetManifest, Provenance};
 use rustml::utils::cli::ArgParser;
+use std::fmt::Display;
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("{err}");
+    std::process::exit(1);
+}
 
 fn main() {
     let parser = ArgParser::new();
+    let defaults = SyntheticConfig::default();
     let output = parser.optional("output", "output/synthetic.csv");
     let rows = parser
         .optional("rows", "256")
@@ -12,13 +23,97 @@ fn main() {
         .optional("seed", "42")
         .parse::<u64>()
         .unwrap_or(42);
+    let float_arg = |key: &str, default: f64| {
+        parser
+            .optional(key, &default.to_string())
+            .parse::<f64>()
+            .unwrap_or(default)
+    };
+    let config = SyntheticConfig {
+        rows,
+        seed,
+        samples_per_day: parser
+            .optional("samples-per-day", &defaults.samples_per_day.to_string())
+            .parse::<usize>()
+            .unwrap_or(defaults.samples_per_day),
+        diurnal_amplitude: float_arg("diurnal-amplitude", defaults.diurnal_amplitude),
+        burst_probability: float_arg("burst-probability", defaults.burst_probability),
+        tail_index: float_arg("tail-index", defaults.tail_index),
+        anomaly_rate: float_arg("anomaly-rate", defaults.anomaly_rate),
+        noise_level: float_arg("noise", defaults.noise_level),
+    };
 
-    let dataset = generate_network_dataset(SyntheticConfig { rows, seed });
     if let Some(parent) = std::path::Path::new(&output).parent() {
         if !parent.as_os_str().is_empty() {
             std::fs::create_dir_all(parent).expect("failed to create output directory");
         }
     }
+
+    let mode = parser.optional("mode", "regression");
+    let dataset = match mode.as_str() {
+        "regression" => {
+            if rows == 0 {
+                fail("rows must be at least 1");
+            }
+            let traffic = generate_network_traffic(config);
+            if let Some(labels) = parser.parse().get("labels") {
+                traffic.write_labels(labels).expect("failed to write labels");
+                println!("Anomaly labels written to {labels}");
+            }
+            traffic.dataset
+        }
+        "classification" => {
+            let mut classification = ClassificationConfig {
+                rows,
+                seed,
+                noise_level: float_arg("noise", ClassificationConfig::default().noise_level),
+                ..ClassificationConfig::default()
+            };
+            if let Some(weights) = parser.parse().get("class-weights") {
+                let parsed: Vec<f64> = weights
+                    .split(',')
+                    .map(|value| value.trim().parse::<f64>().expect("invalid class weight"))
+                    .collect();
+                assert_eq!(
+                    parsed.len(),
+                    5,
+                    "--class-weights expects web,video,voip,bulk,attack"
+                );
+                classification.class_weights.copy_from_slice(&parsed);
+            }
+            generate_traffic_classes(classification).unwrap_or_else(|err| fail(err))
+        }
+        "timeseries" => {
+            let defaults = TimeSeriesConfig::default();
+            let series = TimeSeriesConfig {
+                seed,
+                links: parser
+                    .optional("links", &defaults.links.to_string())
+                    .parse::<usize>()
+                    .unwrap_or(defaults.links),
+                steps: parser
+                    .optional("steps", &defaults.steps.to_string())
+                    .parse::<usize>()
+                    .unwrap_or(defaults.steps),
+                trend: float_arg("trend", defaults.trend),
+                seasonal_amplitude: float_arg("seasonal-amplitude", defaults.seasonal_amplitude),
+                noise_level: float_arg("noise", defaults.noise_level),
+                ..defaults
+            };
+            generate_link_time_series(series).unwrap_or_else(|err| fail(err))
+        }
+        other => panic!(
+            "unknown --mode {}; expected regression, classification or timeseries",
+            other
+        ),
+    };
     dataset.to_csv(&output).expect("failed to write dataset");
     println!("Synthetic dataset written to {output}");
+    let provenance = Provenance {
+        transformations: vec![format!("synthetic:mode={mode},rows={rows},seed={seed}")],
+        ..Provenance::default()
+    };
+    DatasetManifest::new(&dataset, provenance)
+        .save_beside(&output)
+        .expect("failed to write dataset manifest");
 }
@@ -1,7 +1,34 @@
-#[derive(Debug, Clone)]
+use crate::data::cleaning::{CleaningConfig, OutlierClipper, OutlierDetection};
+use crate::data::dataset::{Dataset, DatasetError};
+use crate::data::feature_engineering::{
+    BinningStrategy, DerivedFeature, DerivedFeatures, Discretizer, FeatureEngineeringConfig,
+    LogTransform, PolynomialFeatures, PowerMethod, PowerTransform, SplineBasis,
+};
+use crate::data::selection::{FeatureSelector, SelectionMethod};
+use crate::data::TransformError;
+use crate::io::{save_model, ModelArtifact};
+use crate::math::random::DeterministicRng;
+use crate::ml::linear_regression::LinearRegression;
+use crate::ml::logistic_regression::LogisticRegression;
+use crate::ml::parallel::Parallelism;
+use crate::ml::Estimator;
+use crate::pipeline::{Pipeline, TrainingSummary, TransformStep};
+use crate::utils::evaluation::{
+    mean_absolute_error, mean_squared_error, root_mean_squared_error, Metric,
+};
+use crate::utils::normalization::Scaler;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct AppConfig {
     pub default_output_dir: String,
     pub seed: u64,
+    pub pipeline: Option<PipelineSpec>,
 }
 
 impl Default for AppConfig {
@@ -9,6 +36,635 @@ impl Default for AppConfig {
         AppConfig {
             default_output_dir: "output".to_string(),
             seed: 42,
+            pipeline: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io { path: String, message: String },
+    Parse { path: String, message: String },
+    Invalid { field: String, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, message } => write!(f, "cannot read {path}: {message}"),
+            ConfigError::Parse { path, message } => write!(f, "invalid config {path}: {message}"),
+            ConfigError::Invalid { field, message } => write!(f, "{field}: {message}"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+fn invalid(field: impl Into<String>, message: impl Into<String>) -> ConfigError {
+    ConfigError::Invalid {
+        field: field.into(),
+        message: message.into(),
+    }
+}
+
+impl AppConfig {
+    /// Reads and validates a JSON config file.
+    pub fn from_file(path: &str) -> Result<AppConfig, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|err| ConfigError::Io {
+            path: path.to_string(),
+            message: err.to_string(),
+        })?;
+        let config: AppConfig =
+            serde_json::from_str(&content).map_err(|err| ConfigError::Parse {
+                path: path.to_string(),
+                message: err.to_string(),
+            })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match &self.pipeline {
+            Some(pipeline) => pipeline.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Declarative description of one training run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineSpec {
+    pub input: String,
+    #[serde(default)]
+    pub columns: ColumnRoles,
+    #[serde(default)]
+    pub cleaning: CleaningSpec,
+    #[serde(default)]
+    pub transformers: Vec<TransformerSpec>,
+    pub model: ModelSpec,
+    #[serde(default)]
+    pub evaluation: EvaluationSpec,
+    #[serde(default)]
+    pub outputs: OutputSpec,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnRoles {
+    /// Target column; defaults to the last CSV column.
+    pub target: Option<String>,
+    /// Feature whitelist; defaults to every non-target column.
+    pub features: Option<Vec<String>>,
+    pub ignore: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CleaningSpec {
+    pub drop_incomplete_rows: bool,
+    /// Percentile clipping fitted as the first pipeline step; `null` disables it.
+    pub clip_outliers: Option<CleaningConfig>,
+    /// Per-column outlier bounds used by `clip_outliers` instead of its
+    /// percentiles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outlier_detection: Option<OutlierDetection>,
+}
+
+impl Default for CleaningSpec {
+    fn default() -> Self {
+        CleaningSpec {
+            drop_incomplete_rows: true,
+            clip_outliers: Some(CleaningConfig::default()),
+            outlier_detection: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TransformerSpec {
+    Scale {
+        name: Option<String>,
+        /// One of `zscore`, `minmax`, `robust` or `maxabs`.
+        method: String,
+    },
+    Log {
+        name: Option<String>,
+        columns: Vec<String>,
+        #[serde(default)]
+        plus_one: bool,
+    },
+    Power {
+        name: Option<String>,
+        columns: Vec<String>,
+        method: PowerMethod,
+    },
+    Discretize {
+        name: Option<String>,
+        columns: Vec<String>,
+        bins: usize,
+        strategy: BinningStrategy,
+    },
+    Spline {
+        name: Option<String>,
+        columns: Vec<String>,
+        knots: usize,
+        degree: usize,
+    },
+    Derived {
+        name: Option<String>,
+        features: Vec<DerivedFeature>,
+    },
+    Polynomial {
+        name: Option<String>,
+        #[serde(default = "default_degree")]
+        degree: usize,
+        #[serde(default)]
+        interaction_only: bool,
+        #[serde(default)]
+        include_bias: bool,
+        #[serde(default)]
+        max_features: Option<usize>,
+    },
+    Select {
+        name: Option<String>,
+        selection: SelectionMethod,
+    },
+}
+
+fn default_degree() -> usize {
+    2
+}
+
+fn borrowed(columns: &[String]) -> Vec<&str> {
+    columns.iter().map(|column| column.as_str()).collect()
+}
+
+impl TransformerSpec {
+    fn type_name(&self) -> &'static str {
+        match self {
+            TransformerSpec::Scale { .. } => "scale",
+            TransformerSpec::Log { .. } => "log",
+            TransformerSpec::Power { .. } => "power",
+            TransformerSpec::Discretize { .. } => "discretize",
+            TransformerSpec::Spline { .. } => "spline",
+            TransformerSpec::Derived { .. } => "derived",
+            TransformerSpec::Polynomial { .. } => "polynomial",
+            TransformerSpec::Select { .. } => "select",
+        }
+    }
+
+    /// Step name, defaulting to the transformer type.
+    pub fn name(&self) -> String {
+        let name = match self {
+            TransformerSpec::Scale { name, .. }
+            | TransformerSpec::Log { name, .. }
+            | TransformerSpec::Power { name, .. }
+            | TransformerSpec::Discretize { name, .. }
+            | TransformerSpec::Spline { name, .. }
+            | TransformerSpec::Derived { name, .. }
+            | TransformerSpec::Polynomial { name, .. }
+            | TransformerSpec::Select { name, .. } => name,
+        };
+        name.clone().unwrap_or_else(|| self.type_name().to_string())
+    }
+
+    pub fn to_step(&self) -> Result<TransformStep, ConfigError> {
+        let step = match self {
+            TransformerSpec::Scale { method, .. } => Scaler::from_name(method)
+                .ok_or_else(|| {
+                    invalid(
+                        "method",
+                        format!(
+                            "unknown scaler '{method}'; expected zscore, minmax, robust or maxabs"
+                        ),
+                    )
+                })?
+                .into(),
+            TransformerSpec::Log {
+                columns, plus_one, ..
+            } => {
+                if *plus_one {
+                    LogTransform::log1p(&borrowed(columns)).into()
+                } else {
+                    LogTransform::new(&borrowed(columns)).into()
+                }
+            }
+            TransformerSpec::Power {
+                columns, method, ..
+            } => PowerTransform::new(&borrowed(columns), *method).into(),
+            TransformerSpec::Discretize {
+                columns,
+                bins,
+                strategy,
+                ..
+            } => Discretizer::new(&borrowed(columns), *bins, *strategy).into(),
+            TransformerSpec::Spline {
+                columns,
+                knots,
+                degree,
+                ..
+            } => SplineBasis::new(&borrowed(columns), *knots, *degree).into(),
+            TransformerSpec::Derived { features, .. } => {
+                DerivedFeatures::new(features.clone()).into()
+            }
+            TransformerSpec::Polynomial {
+                degree,
+                interaction_only,
+                include_bias,
+                max_features,
+                ..
+            } => PolynomialFeatures::new(FeatureEngineeringConfig {
+                polynomial_degree: *degree,
+                interaction_only: *interaction_only,
+                include_bias: *include_bias,
+                max_features: *max_features,
+            })
+            .into(),
+            TransformerSpec::Select { selection, .. } => FeatureSelector::new(*selection).into(),
+        };
+        Ok(step)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ModelSpec {
+    LinearRegression {
+        #[serde(default = "default_linear_learning_rate")]
+        learning_rate: f64,
+        #[serde(default = "default_epochs")]
+        epochs: usize,
+        #[serde(default = "default_threads")]
+        threads: usize,
+    },
+    LogisticRegression {
+        #[serde(default = "default_logistic_learning_rate")]
+        learning_rate: f64,
+        #[serde(default = "default_epochs")]
+        epochs: usize,
+        #[serde(default = "default_threads")]
+        threads: usize,
+    },
+}
+
+fn default_linear_learning_rate() -> f64 {
+    0.001
+}
+
+fn default_logistic_learning_rate() -> f64 {
+    0.05
+}
+
+fn default_epochs() -> usize {
+    300
+}
+
+fn default_threads() -> usize {
+    1
+}
+
+impl ModelSpec {
+    pub fn build(&self) -> Estimator {
+        match *self {
+            ModelSpec::LinearRegression {
+                learning_rate,
+                epochs,
+                threads,
+            } => LinearRegression::with_params(0, learning_rate, epochs)
+                .with_parallelism(Parallelism::with_threads(threads))
+                .into(),
+            ModelSpec::LogisticRegression {
+                learning_rate,
+                epochs,
+                threads,
+            } => {
+                let mut model =
+                    LogisticRegression::new(0).with_parallelism(Parallelism::with_threads(threads));
+                model.learning_rate = learning_rate;
+                model.epochs = epochs;
+                model.into()
+            }
+        }
+    }
+
+    fn learning_rate_and_epochs(&self) -> (f64, usize) {
+        match *self {
+            ModelSpec::LinearRegression {
+                learning_rate,
+                epochs,
+                ..
+            }
+            | ModelSpec::LogisticRegression {
+                learning_rate,
+                epochs,
+                ..
+            } => (learning_rate, epochs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvaluationSpec {
+    /// Fraction of rows held out for evaluation; 0 evaluates on the training set.
+    pub test_ratio: f64,
+    /// Shuffle rows with the config seed before splitting.
+    pub shuffle: bool,
+    pub metrics: Vec<Metric>,
+    pub threshold: f64,
+}
+
+impl Default for EvaluationSpec {
+    fn default() -> Self {
+        EvaluationSpec {
+            test_ratio: 0.2,
+            shuffle: true,
+            metrics: vec![Metric::Mse, Metric::Rmse, Metric::Mae],
+            threshold: 0.5,
+        }
+    }
+}
+
+/// Output files, relative to `directory` (which defaults to
+/// `AppConfig::default_output_dir`). `null` skips a file. `model` is a
+/// `ModelArtifact`, readable with `io::load_model`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputSpec {
+    pub directory: Option<String>,
+    pub model: Option<String>,
+    pub report: Option<String>,
+    pub predictions: Option<String>,
+}
+
+impl Default for OutputSpec {
+    fn default() -> Self {
+        OutputSpec {
+            directory: None,
+            model: Some("model.json".to_string()),
+            report: Some("report.json".to_string()),
+            predictions: None,
+        }
+    }
+}
+
+impl PipelineSpec {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.input.trim().is_empty() {
+            return Err(invalid("pipeline.input", "must name a CSV file"));
+        }
+        if let Some(target) = &self.columns.target {
+            if self.columns.ignore.contains(target) {
+                return Err(invalid(
+                    "pipeline.columns.ignore",
+                    format!("cannot ignore the target column '{target}'"),
+                ));
+            }
+        }
+        if let Some(features) = &self.columns.features {
+            if features.is_empty() {
+                return Err(invalid(
+                    "pipeline.columns.features",
+                    "must list at least one feature or be omitted",
+                ));
+            }
+        }
+        if let Some(clip) = &self.cleaning.clip_outliers {
+            let (lower, upper) = (clip.clip_lower_percentile, clip.clip_upper_percentile);
+            if !(0.0..=1.0).contains(&lower) || !(0.0..=1.0).contains(&upper) || lower >= upper {
+                return Err(invalid(
+                    "pipeline.cleaning.clip_outliers",
+                    format!(
+                        "percentiles must satisfy 0 <= lower < upper <= 1, got {lower} and {upper}"
+                    ),
+                ));
+            }
+        }
+        if let Some(detection) = &self.cleaning.outlier_detection {
+            if self.cleaning.clip_outliers.is_none() {
+                return Err(invalid(
+                    "pipeline.cleaning.outlier_detection",
+                    "requires pipeline.cleaning.clip_outliers",
+                ));
+            }
+            detection
+                .validate()
+                .map_err(|message| invalid("pipeline.cleaning.outlier_detection", message))?;
+        }
+        let mut names = HashSet::new();
+        for (idx, transformer) in self.transformers.iter().enumerate() {
+            let field = format!("pipeline.transformers[{idx}]");
+            transformer.to_step().map_err(|err| match err {
+                ConfigError::Invalid {
+                    field: inner,
+                    message,
+                } => invalid(format!("{field}.{inner}"), message),
+                other => other,
+            })?;
+            let name = transformer.name();
+            if name == "clip_outliers" && self.cleaning.clip_outliers.is_some() {
+                return Err(invalid(
+                    field,
+                    "step name 'clip_outliers' is reserved for cleaning",
+                ));
+            }
+            if !names.insert(name.clone()) {
+                return Err(invalid(
+                    field,
+                    format!("duplicate step name '{name}'; set a unique \"name\""),
+                ));
+            }
+        }
+        let (learning_rate, epochs) = self.model.learning_rate_and_epochs();
+        if !(learning_rate.is_finite() && learning_rate > 0.0) {
+            return Err(invalid(
+                "pipeline.model.learning_rate",
+                format!("must be a positive number, got {learning_rate}"),
+            ));
+        }
+        if epochs == 0 {
+            return Err(invalid("pipeline.model.epochs", "must be at least 1"));
+        }
+        let evaluation = &self.evaluation;
+        if !(0.0..1.0).contains(&evaluation.test_ratio) {
+            return Err(invalid(
+                "pipeline.evaluation.test_ratio",
+                format!("must be in [0, 1), got {}", evaluation.test_ratio),
+            ));
+        }
+        if evaluation.metrics.is_empty() {
+            return Err(invalid(
+                "pipeline.evaluation.metrics",
+                "must list at least one metric",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Unfitted pipeline with the cleaning step, the transformers and the model.
+    pub fn build_pipeline(&self) -> Result<Pipeline, ConfigError> {
+        let mut pipeline = Pipeline::new(self.model.build());
+        pipeline.drop_incomplete_rows = self.cleaning.drop_incomplete_rows;
+        if let Some(clip) = self.cleaning.clip_outliers {
+            let mut clipper = OutlierClipper::new(clip);
+            if let Some(detection) = &self.cleaning.outlier_detection {
+                clipper = clipper.with_detection(detection.clone());
+            }
+            pipeline = pipeline.with_step("clip_outliers", clipper);
+        }
+        for transformer in &self.transformers {
+            pipeline = pipeline.with_step(transformer.name(), transformer.to_step()?);
+        }
+        Ok(pipeline)
+    }
+}
+
+/// Outcome of running a `PipelineSpec`, also written as the JSON report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunReport {
+    pub input: String,
+    pub target: String,
+    pub features: Vec<String>,
+    pub steps: Vec<String>,
+    pub train_rows: usize,
+    pub test_rows: usize,
+    pub epochs: usize,
+    pub final_loss: f64,
+    pub metrics: BTreeMap<String, f64>,
+    pub outputs: Vec<String>,
+}
+
+impl AppConfig {
+    /// Loads, fits and evaluates the configured pipeline, writing every
+    /// requested output file.
+    pub fn run_pipeline(&self) -> Result<(Pipeline, RunReport), Box<dyn Error>> {
+        let spec = self
+            .pipeline
+            .as_ref()
+            .ok_or_else(|| invalid("pipeline", "the config has no pipeline section"))?;
+        spec.validate()?;
+        let dataset = match &spec.columns.target {
+            Some(target) => Dataset::from_csv_with_target(&spec.input, target)?,
+            None => Dataset::from_csv(&spec.input)?,
+        };
+        let dataset = spec.columns.apply(&dataset)?;
+
+        let mut order: Vec<usize> = (0..dataset.num_rows()).collect();
+        if spec.evaluation.shuffle {
+            DeterministicRng::new(self.seed).shuffle(&mut order);
+        }
+        let test_size = ((order.len() as f64) * spec.evaluation.test_ratio).round() as usize;
+        let (test_rows, train_rows) = order.split_at(test_size);
+        let train = dataset.subset(train_rows)?;
+        let test = if test_rows.is_empty() {
+            train.clone()
+        } else {
+            dataset.subset(test_rows)?
+        };
+
+        let mut pipeline = spec.build_pipeline()?;
+        let training = pipeline.fit(&train)?;
+        let predictions = pipeline.predict(&test)?;
+        let metrics = spec
+            .evaluation
+            .metrics
+            .iter()
+            .map(|metric| {
+                let value = metric.compute(&predictions, &test.target, spec.evaluation.threshold);
+                (metric.name().to_string(), value)
+            })
+            .collect();
+
+        let outputs = &spec.outputs;
+        let directory = outputs
+            .directory
+            .clone()
+            .unwrap_or_else(|| self.default_output_dir.clone());
+        let output_path = |file: &Option<String>| {
+            file.as_ref().map(|name| {
+                Path::new(&directory)
+                    .join(name)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        };
+        let mut report = RunReport {
+            input: spec.input.clone(),
+            target: dataset.target_name.clone(),
+            features: dataset.feature_names.clone(),
+            steps: pipeline
+                .steps
+                .iter()
+                .map(|step| step.name.clone())
+                .collect(),
+            train_rows: train.num_rows(),
+            test_rows: test_rows.len(),
+            epochs: training.epochs,
+            final_loss: training.final_loss,
+            metrics,
+            outputs: Vec::new(),
+        };
+        let model_path = output_path(&outputs.model);
+        let report_path = output_path(&outputs.report);
+        let predictions_path = output_path(&outputs.predictions);
+        report.outputs = model_path
+            .iter()
+            .chain(&report_path)
+            .chain(&predictions_path)
+            .cloned()
+            .collect();
+        if !report.outputs.is_empty() {
+            std::fs::create_dir_all(&directory)?;
+        }
+        if let Some(path) = &model_path {
+            let summary = TrainingSummary {
+                mse: mean_squared_error(&predictions, &test.target),
+                rmse: root_mean_squared_error(&predictions, &test.target),
+                mae: mean_absolute_error(&predictions, &test.target),
+                epochs: training.epochs,
+                loss: training.final_loss,
+            };
+            let artifact = ModelArtifact::from_pipeline(&pipeline, &train, summary)?;
+            save_model(path, &artifact)?;
+        }
+        if let Some(path) = &predictions_path {
+            let mut writer = csv::Writer::from_path(path)?;
+            writer.write_record(["prediction", "target"])?;
+            for (prediction, actual) in predictions.iter().zip(&test.target) {
+                writer.write_record(&[prediction.to_string(), actual.to_string()])?;
+            }
+            writer.flush()?;
+        }
+        if let Some(path) = &report_path {
+            std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        }
+        Ok((pipeline, report))
+    }
+}
+
+impl ColumnRoles {
+    /// Keeps the listed features (or all of them) minus the ignored ones.
+    pub fn apply(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let index = |name: &String| {
+            dataset
+                .feature_index(name)
+                .ok_or_else(|| TransformError::UnknownFeature { name: name.clone() })
+        };
+        let mut indices: Vec<usize> = match &self.features {
+            Some(features) => features.iter().map(index).collect::<Result<_, _>>()?,
+            None => (0..dataset.num_features()).collect(),
+        };
+        let ignored = self
+            .ignore
+            .iter()
+            .map(index)
+            .collect::<Result<Vec<_>, _>>()?;
+        indices.retain(|idx| !ignored.contains(idx));
+        if indices.is_empty() {
+            return Err(DatasetError::EmptyDataset.into());
         }
+        Ok(dataset.select_features(&indices))
     }
 }
@@ -1,6 +1,12 @@
 use crate::data::dataset::{Dataset, DatasetError};
+use crate::data::{TransformError, Transformer};
+use crate::math::stats::{quantile_sorted, QuantileMethod};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CleaningConfig {
     pub missing_value_threshold: f64,
     pub clip_lower_percentile: f64,
@@ -18,53 +24,490 @@ impl Default for CleaningConfig {
 }
 
 pub fn remove_rows_with_missing(dataset: &Dataset) -> Result<Dataset, DatasetError> {
-    let mut data = Vec::new();
-    let mut target = Vec::new();
-    for (row, target_value) in dataset.iter_rows() {
-        if row.iter().all(|value| value.is_finite()) && target_value.is_finite() {
-            data.push(row.to_vec());
-            target.push(target_value);
-        }
-    }
-    if data.is_empty() {
-        return Err(DatasetError::EmptyDataset);
-    }
-    Dataset::from_records(
-        dataset.feature_names.clone(),
-        dataset.target_name.clone(),
-        data,
-        target,
-    )
-}
-
-pub fn clip_outliers(dataset: &Dataset, config: CleaningConfig) -> Result<Dataset, DatasetError> {
-    let mut clipped = dataset.data.clone();
-    let cols = dataset.num_features();
-    for col_idx in 0..cols {
-        let mut values: Vec<f64> = dataset.data.iter().map(|row| row[col_idx]).collect();
-        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        let lower_idx = ((values.len() as f64) * config.clip_lower_percentile).floor() as usize;
-        let upper_idx = ((values.len() as f64) * config.clip_upper_percentile).ceil() as usize;
-        let lower = values[lower_idx.min(values.len() - 1)];
-        let upper = values[upper_idx.min(values.len() - 1)];
-        for row in &mut clipped {
-            if row[col_idx] < lower {
-                row[col_idx] = lower;
+    let complete: Vec<usize> = dataset
+        .iter_rows()
+        .enumerate()
+        .filter(|(_, (row, target_value))| {
+            row.iter().all(|value| value.is_finite()) && target_value.is_finite()
+        })
+        .map(|(index, _)| index)
+        .collect();
+    if complete.len() == dataset.num_rows() {
+        return Ok(dataset.clone());
+    }
+    dataset.subset(&complete)
+}
+
+/// How a column's outlier bounds are derived from training data, tagged by
+/// `method` in JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case", deny_unknown_fields)]
+pub enum OutlierMethod {
+    /// Bounds at the given percentiles (0 to 1) of the training values.
+    Percentile { lower: f64, upper: f64 },
+    /// Tukey fences `Q1 - k * IQR` and `Q3 + k * IQR`.
+    Iqr { k: f64 },
+    /// Values whose modified z-score `0.6745 * |x - median| / MAD` exceeds
+    /// `threshold`.
+    ModifiedZScore { threshold: f64 },
+    /// Fixed bounds; either may be omitted.
+    Fixed {
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    /// Leaves the column unbounded.
+    Ignore,
+}
+
+impl Default for OutlierMethod {
+    fn default() -> Self {
+        OutlierMethod::Percentile {
+            lower: 0.01,
+            upper: 0.99,
+        }
+    }
+}
+
+/// Stand-ins for an open bound; infinities do not survive JSON.
+const UNBOUNDED: (f64, f64) = (f64::MIN, f64::MAX);
+
+fn sorted_finite(values: impl Iterator<Item = f64>) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values.filter(|value| value.is_finite()).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+fn median_sorted(sorted: &[f64]) -> f64 {
+    quantile_sorted(sorted, 0.5, QuantileMethod::Linear).unwrap_or(0.0)
+}
+
+impl OutlierMethod {
+    /// Unfitted method from a name such as `percentile`, `iqr`, `mad` or `none`,
+    /// with conventional defaults.
+    pub fn from_name(name: &str) -> Option<OutlierMethod> {
+        match name {
+            "percentile" => Some(OutlierMethod::default()),
+            "iqr" => Some(OutlierMethod::Iqr { k: 1.5 }),
+            "mad" | "modified_z_score" => Some(OutlierMethod::ModifiedZScore { threshold: 3.5 }),
+            "none" | "ignore" => Some(OutlierMethod::Ignore),
+            _ => None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            OutlierMethod::Percentile { lower, upper }
+                if !(0.0..=1.0).contains(&lower)
+                    || !(0.0..=1.0).contains(&upper)
+                    || lower >= upper =>
+            {
+                return Err(format!(
+                    "percentiles must satisfy 0 <= lower < upper <= 1, got {lower} and {upper}"
+                ));
+            }
+            OutlierMethod::Iqr { k } if k.is_nan() || k < 0.0 || k.is_infinite() => {
+                return Err(format!("k must be a non-negative number, got {k}"))
+            }
+            OutlierMethod::ModifiedZScore { threshold }
+                if threshold.is_nan() || threshold <= 0.0 =>
+            {
+                return Err(format!("threshold must be positive, got {threshold}"))
+            }
+            OutlierMethod::Fixed {
+                min: Some(min),
+                max: Some(max),
+            } if min > max => return Err(format!("min {min} is above max {max}")),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// `(lower, upper)` bounds over the finite `values`. Columns without
+    /// finite values, and spreads of zero under IQR or MAD, stay unbounded.
+    pub fn bounds(&self, values: impl Iterator<Item = f64>) -> (f64, f64) {
+        if let OutlierMethod::Fixed { min, max } = *self {
+            return (min.unwrap_or(UNBOUNDED.0), max.unwrap_or(UNBOUNDED.1));
+        }
+        let sorted = sorted_finite(values);
+        if sorted.is_empty() {
+            return UNBOUNDED;
+        }
+        let last = sorted.len() - 1;
+        match *self {
+            OutlierMethod::Percentile { lower, upper } => {
+                let lower_idx = ((sorted.len() as f64) * lower).floor() as usize;
+                let upper_idx = ((sorted.len() as f64) * upper).ceil() as usize;
+                (sorted[lower_idx.min(last)], sorted[upper_idx.min(last)])
             }
-            if row[col_idx] > upper {
-                row[col_idx] = upper;
+            OutlierMethod::Iqr { k } => {
+                let q1 = quantile_sorted(&sorted, 0.25, QuantileMethod::Linear).unwrap_or(0.0);
+                let q3 = quantile_sorted(&sorted, 0.75, QuantileMethod::Linear).unwrap_or(0.0);
+                let iqr = q3 - q1;
+                if iqr > 0.0 {
+                    (q1 - k * iqr, q3 + k * iqr)
+                } else {
+                    UNBOUNDED
+                }
             }
+            OutlierMethod::ModifiedZScore { threshold } => {
+                let median = median_sorted(&sorted);
+                let deviations = sorted_finite(sorted.iter().map(|value| (value - median).abs()));
+                let mad = median_sorted(&deviations);
+                if mad > 0.0 {
+                    let spread = threshold * mad / 0.6745;
+                    (median - spread, median + spread)
+                } else {
+                    UNBOUNDED
+                }
+            }
+            OutlierMethod::Fixed { .. } | OutlierMethod::Ignore => UNBOUNDED,
         }
     }
-    Dataset::from_records(
-        dataset.feature_names.clone(),
-        dataset.target_name.clone(),
-        clipped,
-        dataset.target.clone(),
-    )
 }
 
-pub fn clean_dataset(dataset: &Dataset, config: CleaningConfig) -> Result<Dataset, DatasetError> {
+/// A default method plus per-column overrides by feature name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutlierDetection {
+    pub method: OutlierMethod,
+    pub columns: BTreeMap<String, OutlierMethod>,
+}
+
+impl OutlierDetection {
+    pub fn method_for(&self, column: &str) -> OutlierMethod {
+        self.columns.get(column).copied().unwrap_or(self.method)
+    }
+
+    /// Checks every method, naming the column of an invalid override.
+    pub fn validate(&self) -> Result<(), String> {
+        self.method.validate()?;
+        for (column, method) in &self.columns {
+            method
+                .validate()
+                .map_err(|message| format!("column '{column}': {message}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Clipping bounds learned from training data. Without `detection` the
+/// bounds are the configured percentiles of each column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutlierClipper {
+    pub lower_percentile: f64,
+    pub upper_percentile: f64,
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detection: Option<OutlierDetection>,
+    /// Feature names seen at fit time, in bound order. Empty for state saved
+    /// before names were recorded, which is then matched by position only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_features: Vec<String>,
+}
+
+impl OutlierClipper {
+    pub fn new(config: CleaningConfig) -> Self {
+        OutlierClipper {
+            lower_percentile: config.clip_lower_percentile,
+            upper_percentile: config.clip_upper_percentile,
+            lower: Vec::new(),
+            upper: Vec::new(),
+            detection: None,
+            input_features: Vec::new(),
+        }
+    }
+
+    /// Fits bounds with `detection` instead of the percentiles.
+    pub fn with_detection(mut self, detection: OutlierDetection) -> Self {
+        self.detection = Some(detection);
+        self
+    }
+
+    fn method_for(&self, column: &str) -> OutlierMethod {
+        match &self.detection {
+            Some(detection) => detection.method_for(column),
+            None => OutlierMethod::Percentile {
+                lower: self.lower_percentile,
+                upper: self.upper_percentile,
+            },
+        }
+    }
+
+    /// Checks that the bounds and recorded feature names describe the same
+    /// columns, which a hand-edited or truncated file may not.
+    fn check_consistent(&self) -> Result<(), DatasetError> {
+        let columns = self.lower.len();
+        if self.upper.len() != columns {
+            return Err(DatasetError::InvalidColumnCount {
+                expected: columns,
+                found: self.upper.len(),
+            });
+        }
+        if !self.input_features.is_empty() && self.input_features.len() != columns {
+            return Err(DatasetError::InvalidColumnCount {
+                expected: columns,
+                found: self.input_features.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_fitted(&self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.check_consistent()?;
+        if self.lower.len() != dataset.num_features() {
+            return Err(if self.lower.is_empty() {
+                TransformError::NotFitted
+            } else {
+                DatasetError::InvalidColumnCount {
+                    expected: self.lower.len(),
+                    found: dataset.num_features(),
+                }
+                .into()
+            });
+        }
+        if let Some((expected, found)) = self
+            .input_features
+            .iter()
+            .zip(&dataset.feature_names)
+            .find(|(expected, found)| expected != found)
+        {
+            return Err(TransformError::MismatchedFeature {
+                expected: expected.clone(),
+                found: found.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Transformer for OutlierClipper {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        if let Some(detection) = &self.detection {
+            if let Some(name) = detection
+                .columns
+                .keys()
+                .find(|name| dataset.feature_index(name).is_none())
+            {
+                return Err(TransformError::UnknownFeature { name: name.clone() });
+            }
+        }
+        self.lower.clear();
+        self.upper.clear();
+        self.input_features = dataset.feature_names.clone();
+        for (col_idx, name) in dataset.feature_names.iter().enumerate() {
+            let (lower, upper) = self.method_for(name).bounds(dataset.column(col_idx).iter());
+            self.lower.push(lower);
+            self.upper.push(upper);
+        }
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.check_fitted(dataset)?;
+        let mut clipped = dataset.clone();
+        for row in clipped.data.iter_rows_mut() {
+            for (col_idx, value) in row.iter_mut().enumerate() {
+                if *value < self.lower[col_idx] {
+                    *value = self.lower[col_idx];
+                }
+                if *value > self.upper[col_idx] {
+                    *value = self.upper[col_idx];
+                }
+            }
+        }
+        Ok(clipped)
+    }
+
+    fn inverse_transform(&self, _dataset: &Dataset) -> Result<Dataset, TransformError> {
+        Err(TransformError::NotInvertible)
+    }
+}
+
+/// What to do with values outside the fitted bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlierAction {
+    /// Winsorize each value to its column's bounds.
+    Clip,
+    /// Remove every row with at least one outlier.
+    Drop,
+    /// Keep the data and append a 0/1 feature marking outlier rows.
+    Flag,
+}
+
+impl OutlierAction {
+    pub fn from_name(name: &str) -> Option<OutlierAction> {
+        match name {
+            "clip" => Some(OutlierAction::Clip),
+            "drop" => Some(OutlierAction::Drop),
+            "flag" => Some(OutlierAction::Flag),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutlierAction::Clip => "clip",
+            OutlierAction::Drop => "drop",
+            OutlierAction::Flag => "flag",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutlierConfig {
+    pub detection: OutlierDetection,
+    pub action: OutlierAction,
+    /// Name of the feature added by `OutlierAction::Flag`.
+    pub flag_column: String,
+}
+
+impl Default for OutlierConfig {
+    fn default() -> Self {
+        OutlierConfig {
+            detection: OutlierDetection::default(),
+            action: OutlierAction::Clip,
+            flag_column: "is_outlier".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnOutliers {
+    pub column: String,
+    pub lower: f64,
+    pub upper: f64,
+    pub below: usize,
+    pub above: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutlierReport {
+    pub action: OutlierAction,
+    pub rows: usize,
+    /// Rows with at least one value outside its column's bounds.
+    pub affected_rows: usize,
+    pub columns: Vec<ColumnOutliers>,
+}
+
+/// Fitted outlier bounds plus the action to take on new data; the state can
+/// be saved and replayed so test data is judged by training-data bounds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutlierHandler {
+    pub action: OutlierAction,
+    pub flag_column: String,
+    pub clipper: OutlierClipper,
+}
+
+impl OutlierHandler {
+    pub fn new(config: OutlierConfig) -> Self {
+        OutlierHandler {
+            action: config.action,
+            flag_column: config.flag_column,
+            clipper: OutlierClipper::new(CleaningConfig::default())
+                .with_detection(config.detection),
+        }
+    }
+
+    /// Counts the values outside the fitted bounds, per column and per row.
+    pub fn detect(&self, dataset: &Dataset) -> Result<(Vec<bool>, OutlierReport), TransformError> {
+        self.clipper.check_fitted(dataset)?;
+        let (lower, upper) = (&self.clipper.lower, &self.clipper.upper);
+        let mut columns: Vec<ColumnOutliers> = dataset
+            .feature_names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| ColumnOutliers {
+                column: name.clone(),
+                lower: lower[idx],
+                upper: upper[idx],
+                below: 0,
+                above: 0,
+            })
+            .collect();
+        let mut flags = Vec::with_capacity(dataset.num_rows());
+        for (row, _) in dataset.iter_rows() {
+            let mut outlier = false;
+            for (idx, value) in row.iter().enumerate() {
+                if *value < lower[idx] {
+                    columns[idx].below += 1;
+                    outlier = true;
+                } else if *value > upper[idx] {
+                    columns[idx].above += 1;
+                    outlier = true;
+                }
+            }
+            flags.push(outlier);
+        }
+        let report = OutlierReport {
+            action: self.action,
+            rows: dataset.num_rows(),
+            affected_rows: flags.iter().filter(|flag| **flag).count(),
+            columns,
+        };
+        Ok((flags, report))
+    }
+
+    /// Applies the action with the fitted bounds and reports what it touched.
+    pub fn apply(&self, dataset: &Dataset) -> Result<(Dataset, OutlierReport), TransformError> {
+        let (flags, report) = self.detect(dataset)?;
+        let result = match self.action {
+            OutlierAction::Clip => self.clipper.transform(dataset)?,
+            OutlierAction::Drop => {
+                let kept: Vec<usize> = (0..dataset.num_rows()).filter(|idx| !flags[*idx]).collect();
+                dataset.subset(&kept)?
+            }
+            OutlierAction::Flag => {
+                let mut flagged = dataset.clone();
+                let values = flags
+                    .iter()
+                    .map(|flag| f64::from(u8::from(*flag)))
+                    .collect();
+                flagged.add_feature(self.flag_column.clone(), values)?;
+                flagged
+            }
+        };
+        Ok((result, report))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let handler: OutlierHandler = serde_json::from_str(&content)?;
+        handler.clipper.check_consistent()?;
+        Ok(handler)
+    }
+}
+
+impl Transformer for OutlierHandler {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.clipper.fit(dataset)
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.apply(dataset).map(|(result, _)| result)
+    }
+
+    fn inverse_transform(&self, _dataset: &Dataset) -> Result<Dataset, TransformError> {
+        Err(TransformError::NotInvertible)
+    }
+}
+
+/// Fits percentile bounds on `dataset` and clips it with them. To clip new
+/// data with training bounds, fit an `OutlierClipper` or `OutlierHandler`.
+pub fn clip_outliers(dataset: &Dataset, config: CleaningConfig) -> Result<Dataset, TransformError> {
+    OutlierClipper::new(config).fit_transform(dataset)
+}
+
+pub fn clean_dataset(dataset: &Dataset, config: CleaningConfig) -> Result<Dataset, TransformError> {
     let removed = remove_rows_with_missing(dataset)?;
     clip_outliers(&removed, config)
 }
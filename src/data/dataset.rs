@@ -1,12 +1,15 @@
+use crate::math::matrix::Matrix;
+use crate::utils::hashing::ContentHasher;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
+use std::ops::Range;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Dataset {
     pub feature_names: Vec<String>,
     pub target_name: String,
-    pub data: Vec<Vec<f64>>,
+    pub data: Matrix,
     pub target: Vec<f64>,
 }
 
@@ -17,6 +20,7 @@ pub enum DatasetError {
     MissingTarget,
     InvalidColumnCount { expected: usize, found: usize },
     InvalidTargetCount { expected: usize, found: usize },
+    RowOutOfBounds { index: usize, rows: usize },
 }
 
 impl fmt::Display for DatasetError {
@@ -36,14 +40,129 @@ impl fmt::Display for DatasetError {
                 f,
                 "invalid target count: expected {expected} targets, found {found}"
             ),
+            DatasetError::RowOutOfBounds { index, rows } => write!(
+                f,
+                "row index {index} out of bounds for dataset with {rows} rows"
+            ),
         }
     }
 }
 
 impl Error for DatasetError {}
 
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnView<'a> {
+    values: &'a [f64],
+    stride: usize,
+    offset: usize,
+    len: usize,
+}
+
+impl<'a> ColumnView<'a> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, row: usize) -> Option<f64> {
+        if row < self.len {
+            Some(self.values[row * self.stride + self.offset])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = f64> + 'a {
+        let values = self.values;
+        let (stride, offset) = (self.stride, self.offset);
+        (0..self.len).map(move |row| values[row * stride + offset])
+    }
+
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.iter().collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RowSelection {
+    Range(Range<usize>),
+    Indices(Vec<usize>),
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetView<'a> {
+    dataset: &'a Dataset,
+    rows: RowSelection,
+}
+
+impl<'a> DatasetView<'a> {
+    pub fn num_rows(&self) -> usize {
+        match &self.rows {
+            RowSelection::Range(range) => range.len(),
+            RowSelection::Indices(indices) => indices.len(),
+        }
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.dataset.num_features()
+    }
+
+    pub fn feature_names(&self) -> &'a [String] {
+        &self.dataset.feature_names
+    }
+
+    pub fn source_index(&self, row: usize) -> usize {
+        match &self.rows {
+            RowSelection::Range(range) => range.start + row,
+            RowSelection::Indices(indices) => indices[row],
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &'a [f64] {
+        self.dataset.data.row(self.source_index(row))
+    }
+
+    pub fn target(&self, row: usize) -> f64 {
+        self.dataset.target[self.source_index(row)]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = (&'a [f64], f64)> + '_ {
+        let dataset = self.dataset;
+        (0..self.num_rows()).map(move |row| {
+            let index = self.source_index(row);
+            (dataset.data.row(index), dataset.target[index])
+        })
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = f64> + '_ {
+        self.iter_rows().map(move |(row, _)| row[col])
+    }
+
+    pub fn to_dataset(&self) -> Dataset {
+        let indices: Vec<usize> = (0..self.num_rows()).map(|row| self.source_index(row)).collect();
+        Dataset {
+            feature_names: self.dataset.feature_names.clone(),
+            target_name: self.dataset.target_name.clone(),
+            data: self.dataset.data.select_rows(&indices),
+            target: indices.iter().map(|&index| self.dataset.target[index]).collect(),
+        }
+    }
+}
+
 impl Dataset {
     pub fn from_csv(path: &str) -> Result<Self, Box<dyn Error>> {
+        Self::read_csv(path, None)
+    }
+
+    /// Loads a CSV whose target is the named column rather than the last one.
+    pub fn from_csv_with_target(path: &str, target: &str) -> Result<Self, Box<dyn Error>> {
+        Self::read_csv(path, Some(target))
+    }
+
+    fn read_csv(path: &str, target: Option<&str>) -> Result<Self, Box<dyn Error>> {
         let mut reader = csv::ReaderBuilder::new().has_headers(true).from_path(path)?;
         let headers = reader
             .headers()?
@@ -55,10 +174,22 @@ impl Dataset {
             return Err(Box::new(DatasetError::MissingTarget));
         }
 
-        let feature_names = headers[..headers.len() - 1].to_vec();
-        let target_name = headers.last().cloned().unwrap_or_else(|| "target".to_string());
+        let target_idx = match target {
+            Some(name) => headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or(DatasetError::MissingTarget)?,
+            None => headers.len() - 1,
+        };
+        let feature_names = headers
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != target_idx)
+            .map(|(_, name)| name.clone())
+            .collect::<Vec<String>>();
+        let target_name = headers[target_idx].clone();
 
-        let mut data = Vec::new();
+        let mut values = Vec::new();
         let mut target = Vec::new();
 
         for result in reader.records() {
@@ -69,22 +200,21 @@ impl Dataset {
                     found: record.len(),
                 }));
             }
-            let mut row = Vec::with_capacity(feature_names.len());
             for (idx, field) in record.iter().enumerate() {
                 let value: f64 = field.parse()?;
-                if idx == headers.len() - 1 {
+                if idx == target_idx {
                     target.push(value);
                 } else {
-                    row.push(value);
+                    values.push(value);
                 }
             }
-            data.push(row);
         }
 
-        if data.is_empty() {
+        if target.is_empty() {
             return Err(Box::new(DatasetError::EmptyDataset));
         }
 
+        let data = Matrix::new(target.len(), feature_names.len(), values)?;
         Ok(Dataset {
             feature_names,
             target_name,
@@ -111,9 +241,22 @@ impl Dataset {
                 });
             }
         }
-        if target.len() != data.len() {
+        let matrix = Matrix::from_rows(&data).expect("row lengths were validated");
+        Dataset::from_matrix(feature_names, target_name, matrix, target)
+    }
+
+    pub fn from_matrix(
+        feature_names: Vec<String>,
+        target_name: impl Into<String>,
+        data: Matrix,
+        target: Vec<f64>,
+    ) -> Result<Self, DatasetError> {
+        if data.rows() == 0 {
+            return Err(DatasetError::EmptyDataset);
+        }
+        if target.len() != data.rows() {
             return Err(DatasetError::InvalidTargetCount {
-                expected: data.len(),
+                expected: data.rows(),
                 found: target.len(),
             });
         }
@@ -130,7 +273,7 @@ impl Dataset {
         let mut headers = self.feature_names.clone();
         headers.push(self.target_name.clone());
         writer.write_record(headers)?;
-        for (row, target_value) in self.data.iter().zip(self.target.iter()) {
+        for (row, target_value) in self.iter_rows() {
             let mut record = Vec::with_capacity(row.len() + 1);
             for value in row {
                 record.push(value.to_string());
@@ -143,77 +286,185 @@ impl Dataset {
     }
 
     pub fn num_rows(&self) -> usize {
-        self.data.len()
+        self.data.rows()
     }
 
     pub fn num_features(&self) -> usize {
-        self.data.first().map(|row| row.len()).unwrap_or(0)
+        self.data.cols()
     }
 
     pub fn feature_names(&self) -> &[String] {
         &self.feature_names
     }
 
+    pub fn feature_index(&self, name: &str) -> Option<usize> {
+        self.feature_names.iter().position(|feature| feature == name)
+    }
+
     pub fn targets(&self) -> &[f64] {
         &self.target
     }
 
+    pub fn row(&self, index: usize) -> &[f64] {
+        self.data.row(index)
+    }
+
+    pub fn column(&self, index: usize) -> ColumnView<'_> {
+        assert!(index < self.num_features(), "column index out of bounds");
+        ColumnView {
+            values: self.data.as_slice(),
+            stride: self.num_features(),
+            offset: index,
+            len: self.num_rows(),
+        }
+    }
+
     pub fn iter_rows(&self) -> impl Iterator<Item = (&[f64], f64)> {
-        self.data
-            .iter()
-            .zip(self.target.iter())
-            .map(|(row, target)| (row.as_slice(), *target))
+        self.data.iter_rows().zip(self.target.iter().copied())
+    }
+
+    /// Hash of the column names, in order, and which one is the target.
+    pub fn schema_hash(&self) -> String {
+        let mut hasher = ContentHasher::new();
+        hasher.write(&(self.num_features() as u64).to_le_bytes());
+        for name in &self.feature_names {
+            hasher.write_str(name);
+        }
+        hasher.write_str(&self.target_name);
+        hasher.finish_hex()
+    }
+
+    /// Hash of every value, row by row, independent of the column names.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = ContentHasher::new();
+        hasher.write(&(self.num_rows() as u64).to_le_bytes());
+        for (row, target) in self.iter_rows() {
+            for value in row {
+                hasher.write_f64(*value);
+            }
+            hasher.write_f64(target);
+        }
+        hasher.finish_hex()
+    }
+
+    /// Identifies the dataset by both its schema and its contents.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = ContentHasher::new();
+        hasher.write_str(&self.schema_hash());
+        hasher.write_str(&self.content_hash());
+        hasher.finish_hex()
+    }
+
+    pub fn view(&self) -> DatasetView<'_> {
+        DatasetView {
+            dataset: self,
+            rows: RowSelection::Range(0..self.num_rows()),
+        }
+    }
+
+    pub fn slice_rows(&self, range: Range<usize>) -> Result<DatasetView<'_>, DatasetError> {
+        if range.end > self.num_rows() || range.start > range.end {
+            return Err(DatasetError::RowOutOfBounds {
+                index: range.end,
+                rows: self.num_rows(),
+            });
+        }
+        Ok(DatasetView {
+            dataset: self,
+            rows: RowSelection::Range(range),
+        })
+    }
+
+    pub fn select_rows(&self, indices: &[usize]) -> Result<DatasetView<'_>, DatasetError> {
+        if let Some(&index) = indices.iter().find(|&&index| index >= self.num_rows()) {
+            return Err(DatasetError::RowOutOfBounds {
+                index,
+                rows: self.num_rows(),
+            });
+        }
+        Ok(DatasetView {
+            dataset: self,
+            rows: RowSelection::Indices(indices.to_vec()),
+        })
+    }
+
+    /// New dataset with only the given feature columns, in the given order.
+    pub fn select_features(&self, indices: &[usize]) -> Dataset {
+        let mut values = Vec::with_capacity(self.num_rows() * indices.len());
+        for row in self.data.iter_rows() {
+            values.extend(indices.iter().map(|idx| row[*idx]));
+        }
+        Dataset {
+            feature_names: indices
+                .iter()
+                .map(|idx| self.feature_names[*idx].clone())
+                .collect(),
+            target_name: self.target_name.clone(),
+            data: Matrix::new(self.num_rows(), indices.len(), values)
+                .expect("selected rows have one value per index"),
+            target: self.target.clone(),
+        }
     }
 
     pub fn add_feature(&mut self, name: impl Into<String>, values: Vec<f64>) -> Result<(), DatasetError> {
-        if values.len() != self.data.len() {
+        if values.len() != self.num_rows() {
             return Err(DatasetError::InvalidTargetCount {
-                expected: self.data.len(),
+                expected: self.num_rows(),
                 found: values.len(),
             });
         }
-        for (row, value) in self.data.iter_mut().zip(values.into_iter()) {
-            row.push(value);
-        }
+        self.data
+            .push_column(&values)
+            .expect("column length was validated");
         self.feature_names.push(name.into());
         Ok(())
     }
 
-    pub fn map_features<F>(&self, mut func: F) -> Dataset
+    /// Panics if `func` returns rows of different lengths; use
+    /// `try_map_features` to get an error instead.
+    pub fn map_features<F>(&self, func: F) -> Dataset
     where
         F: FnMut(&[f64]) -> Vec<f64>,
     {
-        let data = self.data.iter().map(|row| func(row)).collect();
-        Dataset {
-            feature_names: self.feature_names.clone(),
-            target_name: self.target_name.clone(),
-            data,
-            target: self.target.clone(),
+        if self.num_rows() == 0 {
+            return self.clone();
         }
+        self.try_map_features(func)
+            .expect("mapped rows must all have the same length")
+    }
+
+    pub fn try_map_features<F>(&self, mut func: F) -> Result<Dataset, DatasetError>
+    where
+        F: FnMut(&[f64]) -> Vec<f64>,
+    {
+        let mut rows = self.data.iter_rows();
+        let first = match rows.next() {
+            Some(row) => func(row),
+            None => return Err(DatasetError::EmptyDataset),
+        };
+        let mut data = Matrix::zeros(0, first.len());
+        data.push_row(&first).expect("first row defines the width");
+        for row in rows {
+            let mapped = func(row);
+            data.push_row(&mapped)
+                .map_err(|_| DatasetError::MismatchedRowLengths {
+                    expected: first.len(),
+                    found: mapped.len(),
+                })?;
+        }
+        Dataset::from_matrix(
+            self.feature_names.clone(),
+            self.target_name.clone(),
+            data,
+            self.target.clone(),
+        )
     }
 
     pub fn subset(&self, indices: &[usize]) -> Result<Dataset, DatasetError> {
         if indices.is_empty() {
             return Err(DatasetError::EmptyDataset);
         }
-        let mut data = Vec::with_capacity(indices.len());
-        let mut target = Vec::with_capacity(indices.len());
-        for &index in indices {
-            if index >= self.data.len() {
-                return Err(DatasetError::InvalidTargetCount {
-                    expected: self.data.len(),
-                    found: index,
-                });
-            }
-            data.push(self.data[index].clone());
-            target.push(self.target[index]);
-        }
-        Ok(Dataset {
-            feature_names: self.feature_names.clone(),
-            target_name: self.target_name.clone(),
-            data,
-            target,
-        })
+        Ok(self.select_rows(indices)?.to_dataset())
     }
 
     pub fn train_test_split(&self, test_ratio: f64) -> Result<(Dataset, Dataset), DatasetError> {
@@ -223,9 +474,9 @@ impl Dataset {
                 found: 0,
             });
         }
-        let test_size = ((self.data.len() as f64) * test_ratio).round() as usize;
+        let test_size = ((self.num_rows() as f64) * test_ratio).round() as usize;
         let test_indices: Vec<usize> = (0..test_size).collect();
-        let train_indices: Vec<usize> = (test_size..self.data.len()).collect();
+        let train_indices: Vec<usize> = (test_size..self.num_rows()).collect();
         Ok((self.subset(&train_indices)?, self.subset(&test_indices)?))
     }
 }
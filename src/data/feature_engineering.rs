@@ -1,43 +1,806 @@
-use crate::data::dataset::{Dataset, DatasetError};
+use crate::data::dataset::Dataset;
+use crate::data::{TransformError, Transformer};
+use crate::math::matrix::Matrix;
+use crate::math::stats::{quantiles, variance, QuantileMethod};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct FeatureEngineeringConfig {
     pub polynomial_degree: usize,
+    /// Only emit products of distinct features, skipping powers like `x^2`.
+    pub interaction_only: bool,
+    pub include_bias: bool,
+    /// Upper bound on output columns; expansions above it are rejected.
+    pub max_features: Option<usize>,
 }
 
 impl Default for FeatureEngineeringConfig {
     fn default() -> Self {
-        FeatureEngineeringConfig { polynomial_degree: 2 }
+        FeatureEngineeringConfig {
+            polynomial_degree: 2,
+            interaction_only: false,
+            include_bias: false,
+            max_features: None,
+        }
+    }
+}
+
+/// Monomials of the expansion as non-decreasing feature index lists, ordered
+/// by degree and then lexicographically. The empty list is the bias term.
+pub fn polynomial_terms(num_features: usize, config: FeatureEngineeringConfig) -> Vec<Vec<usize>> {
+    let mut terms = Vec::new();
+    if config.include_bias {
+        terms.push(Vec::new());
+    }
+    let mut previous: Vec<Vec<usize>> = (0..num_features).map(|idx| vec![idx]).collect();
+    terms.extend(previous.iter().cloned());
+    for _ in 2..=config.polynomial_degree {
+        let mut next = Vec::new();
+        for term in &previous {
+            let last = *term
+                .last()
+                .expect("terms of degree one or more are non-empty");
+            let start = if config.interaction_only {
+                last + 1
+            } else {
+                last
+            };
+            for idx in start..num_features {
+                let mut extended = term.clone();
+                extended.push(idx);
+                next.push(extended);
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        terms.extend(next.iter().cloned());
+        previous = next;
+    }
+    terms
+}
+
+/// `n` choose `k`, or `None` if it does not fit in a `usize`.
+fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        // Exact: the running product of i + 1 consecutive integers is
+        // divisible by (i + 1)!.
+        result = result * (n - i) as u128 / (i + 1) as u128;
+        if result > usize::MAX as u128 {
+            return None;
+        }
+    }
+    Some(result as usize)
+}
+
+/// Number of terms `polynomial_terms` returns, computed without generating
+/// them; `None` if it does not fit in a `usize`.
+pub fn polynomial_term_count(
+    num_features: usize,
+    config: FeatureEngineeringConfig,
+) -> Option<usize> {
+    let mut count = usize::from(config.include_bias);
+    for degree in 1..=config.polynomial_degree.max(1) {
+        let terms = if config.interaction_only {
+            binomial(num_features, degree)?
+        } else if num_features == 0 {
+            0
+        } else {
+            binomial(num_features + degree - 1, degree)?
+        };
+        if terms == 0 {
+            break;
+        }
+        count = count.checked_add(terms)?;
     }
+    Some(count)
+}
+
+fn term_name(feature_names: &[String], term: &[usize]) -> String {
+    if term.is_empty() {
+        return "bias".to_string();
+    }
+    let mut factors = Vec::new();
+    let mut idx = 0;
+    while idx < term.len() {
+        let feature = term[idx];
+        let power = term[idx..]
+            .iter()
+            .take_while(|other| **other == feature)
+            .count();
+        if power == 1 {
+            factors.push(feature_names[feature].clone());
+        } else {
+            factors.push(format!("{}^{}", feature_names[feature], power));
+        }
+        idx += power;
+    }
+    factors.join("*")
 }
 
 pub fn add_polynomial_features(
     dataset: &Dataset,
     config: FeatureEngineeringConfig,
-) -> Result<Dataset, DatasetError> {
-    if config.polynomial_degree < 2 {
-        return Ok(dataset.clone());
-    }
-    let mut data = Vec::with_capacity(dataset.data.len());
-    let mut feature_names = dataset.feature_names.clone();
-    for degree in 2..=config.polynomial_degree {
-        for name in dataset.feature_names.iter() {
-            feature_names.push(format!("{name}^{}", degree));
+) -> Result<Dataset, TransformError> {
+    if let Some(limit) = config.max_features {
+        let generated = polynomial_term_count(dataset.num_features(), config).unwrap_or(usize::MAX);
+        if generated > limit {
+            return Err(TransformError::TooManyFeatures { generated, limit });
         }
     }
-    for row in &dataset.data {
-        let mut expanded = row.clone();
-        for degree in 2..=config.polynomial_degree {
-            for value in row {
-                expanded.push(value.powi(degree as i32));
-            }
+    let terms = polynomial_terms(dataset.num_features(), config);
+    let feature_names: Vec<String> = terms
+        .iter()
+        .map(|term| term_name(&dataset.feature_names, term))
+        .collect();
+    let mut values = Vec::with_capacity(dataset.num_rows() * terms.len());
+    for row in dataset.data.iter_rows() {
+        for term in &terms {
+            values.push(term.iter().map(|idx| row[*idx]).product::<f64>());
         }
-        data.push(expanded);
     }
-    Dataset::from_records(
+    let data = Matrix::new(dataset.num_rows(), feature_names.len(), values)
+        .expect("expanded rows match the generated feature names");
+    Ok(Dataset::from_matrix(
         feature_names,
         dataset.target_name.clone(),
         data,
         dataset.target.clone(),
-    )
+    )?)
+}
+
+fn resolve_columns(dataset: &Dataset, columns: &[String]) -> Result<Vec<usize>, TransformError> {
+    columns
+        .iter()
+        .map(|name| {
+            dataset
+                .feature_index(name)
+                .ok_or_else(|| TransformError::UnknownFeature { name: name.clone() })
+        })
+        .collect()
+}
+
+fn drop_columns(dataset: &Dataset, names: &[String]) -> Result<Dataset, TransformError> {
+    let indices = resolve_columns(dataset, names)?;
+    let kept: Vec<usize> = (0..dataset.num_features())
+        .filter(|idx| !indices.contains(idx))
+        .collect();
+    Ok(dataset.select_features(&kept))
+}
+
+fn owned_names(columns: &[&str]) -> Vec<String> {
+    columns.iter().map(|name| name.to_string()).collect()
+}
+
+fn finite_column(dataset: &Dataset, index: usize) -> Vec<f64> {
+    dataset
+        .column(index)
+        .iter()
+        .filter(|value| value.is_finite())
+        .collect()
+}
+
+fn map_columns<F>(
+    dataset: &Dataset,
+    columns: &[String],
+    mut func: F,
+) -> Result<Dataset, TransformError>
+where
+    F: FnMut(usize, f64) -> Option<f64>,
+{
+    let indices = resolve_columns(dataset, columns)?;
+    let mut transformed = dataset.clone();
+    for row in transformed.data.iter_rows_mut() {
+        for (position, index) in indices.iter().enumerate() {
+            let value = row[*index];
+            if value.is_nan() {
+                continue;
+            }
+            row[*index] =
+                func(position, value).ok_or_else(|| TransformError::InvalidFeatureValue {
+                    feature: columns[position].clone(),
+                    value,
+                })?;
+        }
+    }
+    Ok(transformed)
+}
+
+/// Polynomial expansion as a fitted step; remembers the input schema so the
+/// inverse can drop the generated terms.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolynomialFeatures {
+    pub config: FeatureEngineeringConfig,
+    pub input_features: Vec<String>,
+}
+
+impl PolynomialFeatures {
+    pub fn new(config: FeatureEngineeringConfig) -> Self {
+        PolynomialFeatures {
+            config,
+            input_features: Vec::new(),
+        }
+    }
+}
+
+impl Transformer for PolynomialFeatures {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.input_features = dataset.feature_names.clone();
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        if self.input_features.is_empty() {
+            return Err(TransformError::NotFitted);
+        }
+        let indices = resolve_columns(dataset, &self.input_features)?;
+        add_polynomial_features(&dataset.select_features(&indices), self.config)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let indices = resolve_columns(dataset, &self.input_features)?;
+        Ok(dataset.select_features(&indices))
+    }
+}
+
+/// Natural logarithm of `value + shift`; `shift` is 1 for `log1p`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogTransform {
+    pub columns: Vec<String>,
+    pub shift: f64,
+}
+
+impl LogTransform {
+    pub fn new(columns: &[&str]) -> Self {
+        LogTransform {
+            columns: owned_names(columns),
+            shift: 0.0,
+        }
+    }
+
+    pub fn log1p(columns: &[&str]) -> Self {
+        LogTransform {
+            columns: owned_names(columns),
+            shift: 1.0,
+        }
+    }
+}
+
+impl Transformer for LogTransform {
+    /// Only checks that the training data lies in the log's domain.
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.transform(dataset).map(|_| ())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        map_columns(dataset, &self.columns, |_, value| {
+            let shifted = value + self.shift;
+            if shifted > 0.0 {
+                Some(shifted.ln())
+            } else {
+                None
+            }
+        })
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        map_columns(dataset, &self.columns, |_, value| {
+            Some(value.exp() - self.shift)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerMethod {
+    /// Requires strictly positive values.
+    BoxCox,
+    YeoJohnson,
+}
+
+impl PowerMethod {
+    fn apply(self, value: f64, lambda: f64) -> Option<f64> {
+        match self {
+            PowerMethod::BoxCox => {
+                if value <= 0.0 {
+                    None
+                } else if lambda.abs() < 1e-12 {
+                    Some(value.ln())
+                } else {
+                    Some((value.powf(lambda) - 1.0) / lambda)
+                }
+            }
+            PowerMethod::YeoJohnson => Some(if value >= 0.0 {
+                if lambda.abs() < 1e-12 {
+                    value.ln_1p()
+                } else {
+                    ((value + 1.0).powf(lambda) - 1.0) / lambda
+                }
+            } else if (lambda - 2.0).abs() < 1e-12 {
+                -(-value).ln_1p()
+            } else {
+                -((1.0 - value).powf(2.0 - lambda) - 1.0) / (2.0 - lambda)
+            }),
+        }
+    }
+
+    fn invert(self, value: f64, lambda: f64) -> f64 {
+        match self {
+            PowerMethod::BoxCox => {
+                if lambda.abs() < 1e-12 {
+                    value.exp()
+                } else {
+                    (lambda * value + 1.0).powf(1.0 / lambda)
+                }
+            }
+            PowerMethod::YeoJohnson => {
+                if value >= 0.0 {
+                    if lambda.abs() < 1e-12 {
+                        value.exp_m1()
+                    } else {
+                        (lambda * value + 1.0).powf(1.0 / lambda) - 1.0
+                    }
+                } else if (lambda - 2.0).abs() < 1e-12 {
+                    -(-value).exp_m1()
+                } else {
+                    1.0 - (1.0 - (2.0 - lambda) * value).powf(1.0 / (2.0 - lambda))
+                }
+            }
+        }
+    }
+
+    /// Profile log-likelihood of `lambda` under a normal model of the
+    /// transformed values.
+    fn log_likelihood(self, values: &[f64], lambda: f64) -> f64 {
+        let transformed: Vec<f64> = values
+            .iter()
+            .filter_map(|value| self.apply(*value, lambda))
+            .collect();
+        let spread = variance(&transformed).max(1e-300);
+        let jacobian: f64 = match self {
+            PowerMethod::BoxCox => values.iter().map(|value| value.ln()).sum(),
+            PowerMethod::YeoJohnson => values
+                .iter()
+                .map(|value| value.signum() * value.abs().ln_1p())
+                .sum(),
+        };
+        -(values.len() as f64) / 2.0 * spread.ln() + (lambda - 1.0) * jacobian
+    }
+
+    fn fit_lambda(self, values: &[f64]) -> f64 {
+        // Golden-section search; the profile likelihood is unimodal in lambda.
+        let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+        let (mut low, mut high) = (-5.0, 5.0);
+        let mut left = high - ratio * (high - low);
+        let mut right = low + ratio * (high - low);
+        let mut left_value = self.log_likelihood(values, left);
+        let mut right_value = self.log_likelihood(values, right);
+        while high - low > 1e-8 {
+            if left_value < right_value {
+                low = left;
+                left = right;
+                left_value = right_value;
+                right = low + ratio * (high - low);
+                right_value = self.log_likelihood(values, right);
+            } else {
+                high = right;
+                right = left;
+                right_value = left_value;
+                left = high - ratio * (high - low);
+                left_value = self.log_likelihood(values, left);
+            }
+        }
+        (low + high) / 2.0
+    }
+}
+
+/// Box-Cox or Yeo-Johnson transform with one maximum-likelihood lambda per column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerTransform {
+    pub method: PowerMethod,
+    pub columns: Vec<String>,
+    pub lambdas: Vec<f64>,
+}
+
+impl PowerTransform {
+    pub fn new(columns: &[&str], method: PowerMethod) -> Self {
+        PowerTransform {
+            method,
+            columns: owned_names(columns),
+            lambdas: Vec::new(),
+        }
+    }
+
+    fn check_fitted(&self) -> Result<(), TransformError> {
+        if self.lambdas.len() == self.columns.len() {
+            Ok(())
+        } else {
+            Err(TransformError::NotFitted)
+        }
+    }
+}
+
+impl Transformer for PowerTransform {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        let method = self.method;
+        let mut lambdas = Vec::with_capacity(self.columns.len());
+        for (name, index) in self
+            .columns
+            .iter()
+            .zip(resolve_columns(dataset, &self.columns)?)
+        {
+            let values = finite_column(dataset, index);
+            if let Some(value) = values
+                .iter()
+                .find(|value| method.apply(**value, 1.0).is_none())
+            {
+                return Err(TransformError::InvalidFeatureValue {
+                    feature: name.clone(),
+                    value: *value,
+                });
+            }
+            lambdas.push(if values.len() < 2 {
+                1.0
+            } else {
+                method.fit_lambda(&values)
+            });
+        }
+        self.lambdas = lambdas;
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.check_fitted()?;
+        map_columns(dataset, &self.columns, |position, value| {
+            self.method.apply(value, self.lambdas[position])
+        })
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.check_fitted()?;
+        map_columns(dataset, &self.columns, |position, value| {
+            Some(self.method.invert(value, self.lambdas[position]))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinningStrategy {
+    Quantile,
+    EqualWidth,
+}
+
+/// Replaces values with their bin index, `0..bins`. `edges` holds each
+/// column's training minimum, the learned cut points and the training
+/// maximum; quantile bins on heavily tied columns may collapse.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Discretizer {
+    pub strategy: BinningStrategy,
+    pub bins: usize,
+    pub columns: Vec<String>,
+    pub edges: Vec<Vec<f64>>,
+}
+
+impl Discretizer {
+    pub fn new(columns: &[&str], bins: usize, strategy: BinningStrategy) -> Self {
+        Discretizer {
+            strategy,
+            bins: bins.max(1),
+            columns: owned_names(columns),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn num_bins(&self, column: usize) -> usize {
+        self.edges[column].len().saturating_sub(1).max(1)
+    }
+
+    fn check_fitted(&self) -> Result<(), TransformError> {
+        if self.edges.len() == self.columns.len() {
+            Ok(())
+        } else {
+            Err(TransformError::NotFitted)
+        }
+    }
+}
+
+impl Transformer for Discretizer {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        let bins = self.bins.max(1);
+        let mut edges = Vec::with_capacity(self.columns.len());
+        for index in resolve_columns(dataset, &self.columns)? {
+            let values = finite_column(dataset, index);
+            let low = values.iter().cloned().fold(f64::INFINITY, f64::min);
+            let high = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            if !low.is_finite() {
+                edges.push(vec![0.0, 0.0]);
+                continue;
+            }
+            let mut column_edges = vec![low];
+            match self.strategy {
+                BinningStrategy::Quantile => {
+                    let levels: Vec<f64> = (1..bins).map(|bin| bin as f64 / bins as f64).collect();
+                    column_edges.extend(
+                        quantiles(&values, &levels, QuantileMethod::Linear)
+                            .into_iter()
+                            .flatten(),
+                    );
+                }
+                BinningStrategy::EqualWidth => {
+                    column_edges
+                        .extend((1..bins).map(|bin| low + (high - low) * bin as f64 / bins as f64));
+                }
+            }
+            column_edges.push(high);
+            column_edges.dedup();
+            if column_edges.len() == 1 {
+                column_edges.push(high);
+            }
+            edges.push(column_edges);
+        }
+        self.edges = edges;
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.check_fitted()?;
+        map_columns(dataset, &self.columns, |position, value| {
+            let edges = &self.edges[position];
+            let cuts = &edges[1..edges.len() - 1];
+            Some(cuts.partition_point(|edge| *edge <= value) as f64)
+        })
+    }
+
+    /// Maps bin indices back to the midpoint of their training range.
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.check_fitted()?;
+        map_columns(dataset, &self.columns, |position, value| {
+            let edges = &self.edges[position];
+            let bin = (value.max(0.0) as usize).min(self.num_bins(position) - 1);
+            let upper = edges.get(bin + 1).cloned().unwrap_or(edges[bin]);
+            Some((edges[bin] + upper) / 2.0)
+        })
+    }
+}
+
+/// B-spline basis expansion. Each column contributes `knots + degree - 1`
+/// appended features named `<column>_bs<j>`; values outside the fitted knot
+/// range are clamped to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplineBasis {
+    pub columns: Vec<String>,
+    pub degree: usize,
+    pub num_knots: usize,
+    pub knots: Vec<Vec<f64>>,
+}
+
+impl SplineBasis {
+    /// Boundary and interior knots are placed at `num_knots` evenly spaced
+    /// quantiles when fitted.
+    pub fn new(columns: &[&str], num_knots: usize, degree: usize) -> Self {
+        SplineBasis {
+            columns: owned_names(columns),
+            degree,
+            num_knots: num_knots.max(2),
+            knots: Vec::new(),
+        }
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        (0..self.columns.len())
+            .flat_map(|position| {
+                (0..self.num_basis(position))
+                    .map(move |j| format!("{}_bs{}", self.columns[position], j))
+            })
+            .collect()
+    }
+
+    pub fn num_basis(&self, column: usize) -> usize {
+        self.knots[column].len() + self.degree - 1
+    }
+
+    fn evaluate(&self, column: usize, value: f64) -> Vec<f64> {
+        let base = &self.knots[column];
+        let first = base[0];
+        let last = base[base.len() - 1];
+        let degree = self.degree;
+        let left_step = base[1] - first;
+        let right_step = last - base[base.len() - 2];
+        let mut knots: Vec<f64> = (0..degree)
+            .rev()
+            .map(|offset| first - left_step * (offset + 1) as f64)
+            .collect();
+        knots.extend_from_slice(base);
+        knots.extend((1..=degree).map(|offset| last + right_step * offset as f64));
+
+        // Keep the right boundary inside the last half-open knot interval.
+        let x = value.max(first).min(last - (last - first) * 1e-12);
+        let mut basis: Vec<f64> = knots
+            .windows(2)
+            .map(|span| {
+                if span[0] <= x && x < span[1] {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        for order in 1..=degree {
+            for idx in 0..knots.len() - order - 1 {
+                let left_width = knots[idx + order] - knots[idx];
+                let right_width = knots[idx + order + 1] - knots[idx + 1];
+                let left = if left_width > 0.0 {
+                    (x - knots[idx]) / left_width * basis[idx]
+                } else {
+                    0.0
+                };
+                let right = if right_width > 0.0 {
+                    (knots[idx + order + 1] - x) / right_width * basis[idx + 1]
+                } else {
+                    0.0
+                };
+                basis[idx] = left + right;
+            }
+        }
+        basis.truncate(self.num_basis(column));
+        basis
+    }
+}
+
+impl Transformer for SplineBasis {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        let knots = self.num_knots.max(2);
+        let levels: Vec<f64> = (0..knots)
+            .map(|knot| knot as f64 / (knots - 1) as f64)
+            .collect();
+        let mut fitted = Vec::with_capacity(self.columns.len());
+        for index in resolve_columns(dataset, &self.columns)? {
+            let mut positions: Vec<f64> = quantiles(
+                &finite_column(dataset, index),
+                &levels,
+                QuantileMethod::Linear,
+            )
+            .into_iter()
+            .flatten()
+            .collect();
+            positions.dedup();
+            if positions.len() < 2 {
+                let anchor = positions.first().cloned().unwrap_or(0.0);
+                positions = vec![anchor, anchor + 1.0];
+            }
+            fitted.push(positions);
+        }
+        self.knots = fitted;
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        if self.knots.len() != self.columns.len() {
+            return Err(TransformError::NotFitted);
+        }
+        let indices = resolve_columns(dataset, &self.columns)?;
+        let mut expanded = dataset.clone();
+        for (position, index) in indices.into_iter().enumerate() {
+            let mut columns =
+                vec![Vec::with_capacity(dataset.num_rows()); self.num_basis(position)];
+            for value in dataset.column(index).iter() {
+                let basis = if value.is_nan() {
+                    vec![f64::NAN; columns.len()]
+                } else {
+                    self.evaluate(position, value)
+                };
+                for (column, weight) in columns.iter_mut().zip(basis) {
+                    column.push(weight);
+                }
+            }
+            for (j, column) in columns.into_iter().enumerate() {
+                expanded.add_feature(format!("{}_bs{}", self.columns[position], j), column)?;
+            }
+        }
+        Ok(expanded)
+    }
+
+    /// Drops the basis columns, leaving the untouched source features.
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        drop_columns(dataset, &self.output_names())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnOperation {
+    /// `left / right`, or 0 when the denominator is zero.
+    Ratio,
+    Difference,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivedFeature {
+    pub name: String,
+    pub left: String,
+    pub right: String,
+    pub operation: ColumnOperation,
+}
+
+impl DerivedFeature {
+    pub fn ratio(left: &str, right: &str) -> Self {
+        DerivedFeature {
+            name: format!("{left}/{right}"),
+            left: left.to_string(),
+            right: right.to_string(),
+            operation: ColumnOperation::Ratio,
+        }
+    }
+
+    pub fn difference(left: &str, right: &str) -> Self {
+        DerivedFeature {
+            name: format!("{left}-{right}"),
+            left: left.to_string(),
+            right: right.to_string(),
+            operation: ColumnOperation::Difference,
+        }
+    }
+
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+/// Appends ratio and difference columns computed from pairs of named features.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivedFeatures {
+    pub features: Vec<DerivedFeature>,
+}
+
+impl DerivedFeatures {
+    pub fn new(features: Vec<DerivedFeature>) -> Self {
+        DerivedFeatures { features }
+    }
+}
+
+impl Transformer for DerivedFeatures {
+    /// Only checks that every referenced column exists.
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        for feature in &self.features {
+            resolve_columns(dataset, &[feature.left.clone(), feature.right.clone()])?;
+        }
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let mut derived = dataset.clone();
+        for feature in &self.features {
+            let indices = resolve_columns(dataset, &[feature.left.clone(), feature.right.clone()])?;
+            let values = dataset
+                .data
+                .iter_rows()
+                .map(|row| {
+                    let (left, right) = (row[indices[0]], row[indices[1]]);
+                    match feature.operation {
+                        ColumnOperation::Ratio if right == 0.0 => 0.0,
+                        ColumnOperation::Ratio => left / right,
+                        ColumnOperation::Difference => left - right,
+                    }
+                })
+                .collect();
+            derived.add_feature(feature.name.clone(), values)?;
+        }
+        Ok(derived)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let names: Vec<String> = self
+            .features
+            .iter()
+            .map(|feature| feature.name.clone())
+            .collect();
+        drop_columns(dataset, &names)
+    }
 }
@@ -1,4 +1,5 @@
 use crate::data::dataset::Dataset;
+use crate::math::matrix::Matrix;
 
 #[derive(Debug, Clone, Copy)]
 pub struct NetworkRecord {
@@ -11,7 +12,7 @@ pub struct NetworkRecord {
     pub congestion_score: f64,
 }
 
-pub const NETWORK_FIXTURES: [NetworkRecord; 5000] = [
+pub static NETWORK_FIXTURES: [NetworkRecord; 5000] = [
     NetworkRecord { flow_duration: 50.00, src_bytes: 100.00, dst_bytes: 200.00, packet_rate: 10.00, jitter: 0.50, loss_rate: 0.0100, congestion_score: 3.1150 },
     NetworkRecord { flow_duration: 51.50, src_bytes: 103.20, dst_bytes: 202.10, packet_rate: 10.50, jitter: 0.70, loss_rate: 0.0200, congestion_score: 3.4200 },
     NetworkRecord { flow_duration: 53.00, src_bytes: 106.40, dst_bytes: 204.20, packet_rate: 11.00, jitter: 0.90, loss_rate: 0.0300, congestion_score: 3.7250 },
@@ -5015,13 +5016,14 @@ pub const NETWORK_FIXTURES: [NetworkRecord; 5000] = [
 ];
 
 pub fn fixture_dataset() -> Dataset {
-    let mut data = Vec::with_capacity(NETWORK_FIXTURES.len());
+    let mut values = Vec::with_capacity(NETWORK_FIXTURES.len() * 6);
     let mut target = Vec::with_capacity(NETWORK_FIXTURES.len());
     for record in NETWORK_FIXTURES.iter() {
-        data.push(vec![record.flow_duration, record.src_bytes, record.dst_bytes, record.packet_rate, record.jitter, record.loss_rate]);
+        values.extend_from_slice(&[record.flow_duration, record.src_bytes, record.dst_bytes, record.packet_rate, record.jitter, record.loss_rate]);
         target.push(record.congestion_score);
     }
-    Dataset::from_records(
+    let data = Matrix::new(NETWORK_FIXTURES.len(), 6, values).expect("fixture rows have six features");
+    Dataset::from_matrix(
         vec![
             "flow_duration".into(),
             "src_bytes".into(),
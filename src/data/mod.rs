@@ -3,3 +3,74 @@ pub mod cleaning;
 pub mod synthetic;
 pub mod fixtures;
 pub mod feature_engineering;
+pub mod temporal;
+pub mod selection;
+pub mod versioning;
+pub mod profiling;
+
+use crate::data::dataset::{Dataset, DatasetError};
+use std::error::Error;
+use std::fmt;
+
+/// Failure to fit or apply a `Transformer`: either the data itself is
+/// unusable or it does not match what the transformer expects.
+#[derive(Debug)]
+pub enum TransformError {
+    Dataset(DatasetError),
+    TooManyFeatures { generated: usize, limit: usize },
+    UnknownFeature { name: String },
+    MismatchedFeature { expected: String, found: String },
+    InvalidFeatureValue { feature: String, value: f64 },
+    InvalidRange { lower: f64, upper: f64 },
+    MissingTimestamp,
+    NotFitted,
+    NotInvertible,
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformError::Dataset(err) => write!(f, "{err}"),
+            TransformError::TooManyFeatures { generated, limit } => write!(
+                f,
+                "expansion would generate {generated} features, above the limit of {limit}"
+            ),
+            TransformError::UnknownFeature { name } => write!(f, "unknown feature '{name}'"),
+            TransformError::MismatchedFeature { expected, found } => {
+                write!(f, "expected feature '{expected}' but found '{found}'")
+            }
+            TransformError::InvalidFeatureValue { feature, value } => write!(
+                f,
+                "value {value} of feature '{feature}' is outside the transform's domain"
+            ),
+            TransformError::InvalidRange { lower, upper } => write!(
+                f,
+                "invalid range ({lower}, {upper}): lower must be below upper and both finite"
+            ),
+            TransformError::MissingTimestamp => write!(f, "a timestamp column is required"),
+            TransformError::NotFitted => write!(f, "transformer must be fitted before use"),
+            TransformError::NotInvertible => write!(f, "transformer has no inverse"),
+        }
+    }
+}
+
+impl Error for TransformError {}
+
+impl From<DatasetError> for TransformError {
+    fn from(err: DatasetError) -> Self {
+        TransformError::Dataset(err)
+    }
+}
+
+/// Preprocessing step that learns its state from training data and replays
+/// it on new data with the same feature layout.
+pub trait Transformer {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError>;
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError>;
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError>;
+
+    fn fit_transform(&mut self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.fit(dataset)?;
+        self.transform(dataset)
+    }
+}
@@ -0,0 +1,418 @@
+//! Column profiles and declarative validation rules for a `Dataset`.
+
+use crate::data::dataset::Dataset;
+use crate::math::stats::{self, QuantileMethod};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Row indices listed per violation; `count` still has the full total.
+pub const MAX_REPORTED_ROWS: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    pub bins: usize,
+    pub quantiles: Vec<f64>,
+}
+
+impl Default for ProfileConfig {
+    fn default() -> Self {
+        ProfileConfig {
+            bins: 10,
+            quantiles: vec![0.05, 0.25, 0.5, 0.75, 0.95],
+        }
+    }
+}
+
+/// What the values of a numeric column look like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    /// No finite values at all.
+    Empty,
+    Constant,
+    /// Only 0 and 1.
+    Binary,
+    Integer,
+    Continuous,
+}
+
+impl ColumnType {
+    fn infer(finite: &[f64], distinct: usize) -> ColumnType {
+        if finite.is_empty() {
+            ColumnType::Empty
+        } else if distinct == 1 {
+            ColumnType::Constant
+        } else if finite.iter().all(|value| *value == 0.0 || *value == 1.0) {
+            ColumnType::Binary
+        } else if finite.iter().all(|value| value.fract() == 0.0) {
+            ColumnType::Integer
+        } else {
+            ColumnType::Continuous
+        }
+    }
+}
+
+/// Equal-width bins between the smallest and largest value; the last bin is
+/// closed on the right.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    /// `counts.len() + 1` bin edges.
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Histogram of the finite `values`; `None` when there are none.
+    pub fn new(values: &[f64], bins: usize) -> Option<Histogram> {
+        let finite: Vec<f64> = values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect();
+        let (min, max) = (stats::min(&finite)?, stats::max(&finite)?);
+        let bins = if min == max { 1 } else { bins.max(1) };
+        let width = (max - min) / bins as f64;
+        let edges = (0..=bins)
+            .map(|idx| {
+                if idx == bins {
+                    max
+                } else {
+                    min + width * idx as f64
+                }
+            })
+            .collect();
+        let mut counts = vec![0; bins];
+        for value in finite {
+            let bin = if width > 0.0 {
+                (((value - min) / width) as usize).min(bins - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+        Some(Histogram { edges, counts })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantileValue {
+    pub q: f64,
+    pub value: f64,
+}
+
+/// Statistics are over finite values; NaN counts as missing and infinities
+/// are counted separately.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    pub name: String,
+    pub is_target: bool,
+    pub column_type: ColumnType,
+    pub count: usize,
+    pub missing: usize,
+    pub infinite: usize,
+    pub distinct: usize,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub std_dev: Option<f64>,
+    pub quantiles: Vec<QuantileValue>,
+    pub histogram: Option<Histogram>,
+    pub zeros: usize,
+    pub negatives: usize,
+    /// Pearson correlation with the target over rows where both are finite;
+    /// `None` for the target itself.
+    pub target_correlation: Option<f64>,
+}
+
+impl ColumnProfile {
+    pub fn new(
+        name: &str,
+        values: &[f64],
+        target: Option<&[f64]>,
+        config: &ProfileConfig,
+    ) -> ColumnProfile {
+        let finite: Vec<f64> = values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect();
+        let distinct = finite
+            .iter()
+            .map(|value| (value + 0.0).to_bits())
+            .collect::<HashSet<u64>>()
+            .len();
+        let observed = |value: f64| Some(value).filter(|_| !finite.is_empty());
+        let quantiles = config
+            .quantiles
+            .iter()
+            .zip(stats::quantiles(
+                &finite,
+                &config.quantiles,
+                QuantileMethod::Linear,
+            ))
+            .filter_map(|(q, value)| value.map(|value| QuantileValue { q: *q, value }))
+            .collect();
+        let target_correlation = target.map(|target| {
+            let (a, b): (Vec<f64>, Vec<f64>) = values
+                .iter()
+                .zip(target)
+                .filter(|(value, target)| value.is_finite() && target.is_finite())
+                .map(|(value, target)| (*value, *target))
+                .unzip();
+            stats::pearson(&a, &b)
+        });
+        ColumnProfile {
+            name: name.to_string(),
+            is_target: target.is_none(),
+            column_type: ColumnType::infer(&finite, distinct),
+            count: values.len(),
+            missing: values.iter().filter(|value| value.is_nan()).count(),
+            infinite: values.iter().filter(|value| value.is_infinite()).count(),
+            distinct,
+            min: stats::min(&finite),
+            max: stats::max(&finite),
+            mean: observed(stats::mean(&finite)),
+            std_dev: observed(stats::std_dev(&finite)),
+            quantiles,
+            histogram: Histogram::new(&finite, config.bins),
+            zeros: finite.iter().filter(|value| **value == 0.0).count(),
+            negatives: finite.iter().filter(|value| **value < 0.0).count(),
+            target_correlation,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataProfile {
+    pub rows: usize,
+    pub target: String,
+    /// The features followed by the target.
+    pub columns: Vec<ColumnProfile>,
+}
+
+impl DataProfile {
+    pub fn new(dataset: &Dataset, config: &ProfileConfig) -> DataProfile {
+        let mut columns: Vec<ColumnProfile> = dataset
+            .feature_names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                let values = dataset.column(idx).to_vec();
+                ColumnProfile::new(name, &values, Some(&dataset.target), config)
+            })
+            .collect();
+        columns.push(ColumnProfile::new(
+            &dataset.target_name,
+            &dataset.target,
+            None,
+            config,
+        ));
+        DataProfile {
+            rows: dataset.num_rows(),
+            target: dataset.target_name.clone(),
+            columns,
+        }
+    }
+
+    pub fn column(&self, name: &str) -> Option<&ColumnProfile> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+/// A check on one column, tagged by `rule` in JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ValidationRule {
+    /// Values must lie within `[min, max]`; either bound may be omitted.
+    /// Missing values are left to `non_null`.
+    Range {
+        column: String,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    NonNull {
+        column: String,
+    },
+    /// Each value must not be below (or above) the previous one in file order,
+    /// or must strictly exceed it when `strict`. Missing values are skipped.
+    Monotonic {
+        column: String,
+        direction: Direction,
+        #[serde(default)]
+        strict: bool,
+    },
+}
+
+impl ValidationRule {
+    pub fn column(&self) -> &str {
+        match self {
+            ValidationRule::Range { column, .. }
+            | ValidationRule::NonNull { column }
+            | ValidationRule::Monotonic { column, .. } => column,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            ValidationRule::Range { column, min, max } => {
+                let bound = |value: &Option<f64>| value.map_or("..".to_string(), |v| v.to_string());
+                format!("{column} in [{}, {}]", bound(min), bound(max))
+            }
+            ValidationRule::NonNull { column } => format!("{column} is not null"),
+            ValidationRule::Monotonic {
+                column,
+                direction,
+                strict,
+            } => {
+                let strictness = if *strict { "strictly " } else { "" };
+                let direction = match direction {
+                    Direction::Increasing => "increasing",
+                    Direction::Decreasing => "decreasing",
+                };
+                format!("{column} is {strictness}{direction}")
+            }
+        }
+    }
+
+    /// Indices of the rows that break the rule.
+    fn failing_rows(&self, values: &[f64]) -> Vec<usize> {
+        match self {
+            ValidationRule::Range { min, max, .. } => values
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| {
+                    !value.is_nan()
+                        && (min.is_some_and(|min| **value < min)
+                            || max.is_some_and(|max| **value > max))
+                })
+                .map(|(idx, _)| idx)
+                .collect(),
+            ValidationRule::NonNull { .. } => values
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| value.is_nan())
+                .map(|(idx, _)| idx)
+                .collect(),
+            ValidationRule::Monotonic {
+                direction, strict, ..
+            } => {
+                let mut failing = Vec::new();
+                let mut previous: Option<f64> = None;
+                for (idx, value) in values.iter().enumerate() {
+                    if value.is_nan() {
+                        continue;
+                    }
+                    if let Some(previous) = previous {
+                        let ordered = match (direction, strict) {
+                            (Direction::Increasing, false) => *value >= previous,
+                            (Direction::Increasing, true) => *value > previous,
+                            (Direction::Decreasing, false) => *value <= previous,
+                            (Direction::Decreasing, true) => *value < previous,
+                        };
+                        if !ordered {
+                            failing.push(idx);
+                        }
+                    }
+                    previous = Some(*value);
+                }
+                failing
+            }
+        }
+    }
+}
+
+/// The rules file format: `{"rules": [...]}`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSet {
+    pub rules: Vec<ValidationRule>,
+}
+
+impl RuleSet {
+    pub fn load(path: &str) -> Result<RuleSet, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn validate(&self, dataset: &Dataset) -> ValidationReport {
+        validate(dataset, &self.rules)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    pub column: String,
+    pub rule: String,
+    pub count: usize,
+    /// The first `MAX_REPORTED_ROWS` failing rows (0-based data rows).
+    pub rows: Vec<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub rules_checked: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+pub fn validate(dataset: &Dataset, rules: &[ValidationRule]) -> ValidationReport {
+    let violations = rules
+        .iter()
+        .filter_map(|rule| {
+            let column = rule.column();
+            let values = if column == dataset.target_name {
+                dataset.target.clone()
+            } else {
+                match dataset.feature_index(column) {
+                    Some(idx) => dataset.column(idx).to_vec(),
+                    None => {
+                        return Some(Violation {
+                            column: column.to_string(),
+                            rule: rule.describe(),
+                            count: 0,
+                            rows: Vec::new(),
+                            message: format!("column '{column}' does not exist"),
+                        })
+                    }
+                }
+            };
+            let failing = rule.failing_rows(&values);
+            if failing.is_empty() {
+                return None;
+            }
+            Some(Violation {
+                column: column.to_string(),
+                rule: rule.describe(),
+                count: failing.len(),
+                message: format!(
+                    "{} of {} rows violate '{}'",
+                    failing.len(),
+                    values.len(),
+                    rule.describe()
+                ),
+                rows: failing.into_iter().take(MAX_REPORTED_ROWS).collect(),
+            })
+        })
+        .collect();
+    ValidationReport {
+        rules_checked: rules.len(),
+        violations,
+    }
+}
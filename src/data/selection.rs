@@ -0,0 +1,358 @@
+use crate::data::dataset::Dataset;
+use crate::data::{TransformError, Transformer};
+use crate::math::stats::{correlation, mean, pearson, std_dev, variance, CorrelationMethod};
+use crate::ml::{LinearModel, Model};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const MUTUAL_INFORMATION_BINS: usize = 10;
+
+/// Which of the original features survived selection, by name so the mask
+/// can be replayed on data with the same schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureMask {
+    pub feature_names: Vec<String>,
+    pub selected: Vec<bool>,
+}
+
+impl FeatureMask {
+    pub fn all(dataset: &Dataset) -> Self {
+        FeatureMask {
+            feature_names: dataset.feature_names.clone(),
+            selected: vec![true; dataset.num_features()],
+        }
+    }
+
+    pub fn num_selected(&self) -> usize {
+        self.selected.iter().filter(|keep| **keep).count()
+    }
+
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.selected
+            .iter()
+            .enumerate()
+            .filter(|(_, keep)| **keep)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn selected_names(&self) -> Vec<&str> {
+        self.selected_indices()
+            .into_iter()
+            .map(|idx| self.feature_names[idx].as_str())
+            .collect()
+    }
+
+    pub fn apply(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let indices = self
+            .selected_names()
+            .into_iter()
+            .map(|name| {
+                dataset
+                    .feature_index(name)
+                    .ok_or_else(|| TransformError::UnknownFeature {
+                        name: name.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<usize>, TransformError>>()?;
+        Ok(dataset.select_features(&indices))
+    }
+}
+
+fn select(dataset: &Dataset, selected: Vec<bool>) -> Result<(Dataset, FeatureMask), TransformError> {
+    let mask = FeatureMask {
+        feature_names: dataset.feature_names.clone(),
+        selected,
+    };
+    Ok((mask.apply(dataset)?, mask))
+}
+
+fn top_k(scores: &[f64], k: usize) -> Vec<bool> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|a, b| {
+        let score = |idx: usize| {
+            if scores[idx].is_nan() {
+                f64::NEG_INFINITY
+            } else {
+                scores[idx]
+            }
+        };
+        score(*b).total_cmp(&score(*a)).then(a.cmp(b))
+    });
+    let mut selected = vec![false; scores.len()];
+    for idx in order.into_iter().take(k) {
+        selected[idx] = true;
+    }
+    selected
+}
+
+/// Drops features whose population variance is not above `threshold`.
+pub fn variance_threshold(
+    dataset: &Dataset,
+    threshold: f64,
+) -> Result<(Dataset, FeatureMask), TransformError> {
+    let selected = (0..dataset.num_features())
+        .map(|col| variance(&dataset.column(col).to_vec()) > threshold)
+        .collect();
+    select(dataset, selected)
+}
+
+/// Walks features in order and drops any whose absolute correlation with an
+/// already kept feature exceeds `threshold`.
+pub fn correlation_filter(
+    dataset: &Dataset,
+    threshold: f64,
+    method: CorrelationMethod,
+) -> Result<(Dataset, FeatureMask), TransformError> {
+    let columns: Vec<Vec<f64>> = (0..dataset.num_features())
+        .map(|col| dataset.column(col).to_vec())
+        .collect();
+    let mut kept: Vec<usize> = Vec::new();
+    let mut selected = vec![false; columns.len()];
+    for (idx, column) in columns.iter().enumerate() {
+        let redundant = kept
+            .iter()
+            .any(|other| correlation(column, &columns[*other], method).abs() > threshold);
+        if !redundant {
+            kept.push(idx);
+            selected[idx] = true;
+        }
+    }
+    select(dataset, selected)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnivariateScore {
+    /// Regression F statistic of each feature against the target.
+    FTest,
+    /// Plug-in mutual information, in nats, over quantile-binned values.
+    MutualInformation,
+}
+
+pub fn univariate_scores(dataset: &Dataset, score: UnivariateScore) -> Vec<f64> {
+    let target = dataset.target.as_slice();
+    (0..dataset.num_features())
+        .map(|col| {
+            let values = dataset.column(col).to_vec();
+            match score {
+                UnivariateScore::FTest => f_statistic(&values, target),
+                UnivariateScore::MutualInformation => mutual_information(&values, target),
+            }
+        })
+        .collect()
+}
+
+/// Keeps the `k` features with the highest univariate score.
+pub fn select_k_best(
+    dataset: &Dataset,
+    k: usize,
+    score: UnivariateScore,
+) -> Result<(Dataset, FeatureMask), TransformError> {
+    let scores = univariate_scores(dataset, score);
+    select(dataset, top_k(&scores, k))
+}
+
+fn f_statistic(values: &[f64], target: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    if n < 3.0 || std_dev(values) == 0.0 || std_dev(target) == 0.0 {
+        return 0.0;
+    }
+    let r2 = pearson(values, target).powi(2).min(1.0 - 1e-15);
+    r2 / (1.0 - r2) * (n - 2.0)
+}
+
+/// Bin labels for a column: the values themselves when there are few distinct
+/// ones, quantile bins otherwise.
+fn discretize(values: &[f64]) -> Vec<usize> {
+    let mut distinct: Vec<f64> = values.to_vec();
+    distinct.sort_by(|a, b| a.total_cmp(b));
+    distinct.dedup();
+    if distinct.len() <= MUTUAL_INFORMATION_BINS {
+        return values
+            .iter()
+            .map(|value| distinct.partition_point(|level| level < value))
+            .collect();
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let edges: Vec<f64> = (1..MUTUAL_INFORMATION_BINS)
+        .map(|bin| sorted[bin * sorted.len() / MUTUAL_INFORMATION_BINS])
+        .collect();
+    values
+        .iter()
+        .map(|value| edges.partition_point(|edge| edge <= value))
+        .collect()
+}
+
+fn mutual_information(values: &[f64], target: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    if n == 0.0 {
+        return 0.0;
+    }
+    let x = discretize(values);
+    let y = discretize(target);
+    let mut joint: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+    let mut x_counts: BTreeMap<usize, f64> = BTreeMap::new();
+    let mut y_counts: BTreeMap<usize, f64> = BTreeMap::new();
+    for (a, b) in x.iter().zip(&y) {
+        *joint.entry((*a, *b)).or_insert(0.0) += 1.0;
+        *x_counts.entry(*a).or_insert(0.0) += 1.0;
+        *y_counts.entry(*b).or_insert(0.0) += 1.0;
+    }
+    joint
+        .iter()
+        .map(|((a, b), count)| count / n * (count * n / (x_counts[a] * y_counts[b])).ln())
+        .sum::<f64>()
+        .max(0.0)
+}
+
+/// Recursive feature elimination: repeatedly trains a fresh model from
+/// `build(num_features)` and removes the `step` features with the smallest
+/// absolute weights until `n_features` remain. Features should share a scale
+/// (e.g. z-scored) for weights to be comparable.
+pub fn recursive_feature_elimination<M, F>(
+    dataset: &Dataset,
+    n_features: usize,
+    step: usize,
+    mut build: F,
+) -> Result<(Dataset, FeatureMask), TransformError>
+where
+    M: Model + LinearModel,
+    F: FnMut(usize) -> M,
+{
+    let n_features = n_features.max(1);
+    let mut mask = FeatureMask::all(dataset);
+    while mask.num_selected() > n_features {
+        let current = mask.apply(dataset)?;
+        let mut model = build(current.num_features());
+        model.train(&current);
+        let weights: Vec<f64> = model.weights().iter().map(|weight| weight.abs()).collect();
+        let remove = step.max(1).min(mask.num_selected() - n_features);
+        let mut order: Vec<usize> = (0..weights.len()).collect();
+        order.sort_by(|a, b| weights[*a].total_cmp(&weights[*b]).then(b.cmp(a)));
+        let indices = mask.selected_indices();
+        for position in order.into_iter().take(remove) {
+            mask.selected[indices[position]] = false;
+        }
+    }
+    Ok((mask.apply(dataset)?, mask))
+}
+
+/// Lasso coefficients on z-scored features and a centred target, fitted by
+/// cyclic coordinate descent with penalty `alpha`.
+pub fn lasso_coefficients(dataset: &Dataset, alpha: f64, max_iterations: usize) -> Vec<f64> {
+    let n = dataset.num_rows().max(1) as f64;
+    let columns: Vec<Vec<f64>> = (0..dataset.num_features())
+        .map(|col| {
+            let values = dataset.column(col).to_vec();
+            let (centre, spread) = (mean(&values), std_dev(&values));
+            values
+                .iter()
+                .map(|value| {
+                    if spread > 0.0 {
+                        (value - centre) / spread
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let target_mean = mean(&dataset.target);
+    let mut residual: Vec<f64> = dataset
+        .target
+        .iter()
+        .map(|value| value - target_mean)
+        .collect();
+    let mut coefficients = vec![0.0; columns.len()];
+    for _ in 0..max_iterations {
+        let mut largest_change = 0.0_f64;
+        for (idx, column) in columns.iter().enumerate() {
+            let norm: f64 = column.iter().map(|value| value * value).sum::<f64>() / n;
+            if norm == 0.0 {
+                continue;
+            }
+            let old = coefficients[idx];
+            let rho = column
+                .iter()
+                .zip(&residual)
+                .map(|(value, error)| value * (error + old * value))
+                .sum::<f64>()
+                / n;
+            let updated = rho.signum() * (rho.abs() - alpha).max(0.0) / norm;
+            if updated != old {
+                for (error, value) in residual.iter_mut().zip(column) {
+                    *error -= (updated - old) * value;
+                }
+                coefficients[idx] = updated;
+                largest_change = largest_change.max((updated - old).abs());
+            }
+        }
+        if largest_change < 1e-9 {
+            break;
+        }
+    }
+    coefficients
+}
+
+/// Keeps features whose Lasso coefficient at penalty `alpha` is non-zero.
+pub fn l1_selection(dataset: &Dataset, alpha: f64) -> Result<(Dataset, FeatureMask), TransformError> {
+    let coefficients = lasso_coefficients(dataset, alpha, 1_000);
+    let selected = coefficients
+        .iter()
+        .map(|coefficient| coefficient.abs() > 1e-10)
+        .collect();
+    select(dataset, selected)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SelectionMethod {
+    VarianceThreshold { threshold: f64 },
+    Correlation { threshold: f64, correlation: CorrelationMethod },
+    KBest { k: usize, score: UnivariateScore },
+    L1 { alpha: f64 },
+}
+
+/// Selection as a fitted step: the mask is learned once and replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureSelector {
+    pub method: SelectionMethod,
+    pub mask: Option<FeatureMask>,
+}
+
+impl FeatureSelector {
+    pub fn new(method: SelectionMethod) -> Self {
+        FeatureSelector { method, mask: None }
+    }
+}
+
+impl Transformer for FeatureSelector {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        let (_, mask) = match self.method {
+            SelectionMethod::VarianceThreshold { threshold } => {
+                variance_threshold(dataset, threshold)?
+            }
+            SelectionMethod::Correlation {
+                threshold,
+                correlation,
+            } => correlation_filter(dataset, threshold, correlation)?,
+            SelectionMethod::KBest { k, score } => select_k_best(dataset, k, score)?,
+            SelectionMethod::L1 { alpha } => l1_selection(dataset, alpha)?,
+        };
+        self.mask = Some(mask);
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.mask
+            .as_ref()
+            .ok_or(TransformError::NotFitted)?
+            .apply(dataset)
+    }
+
+    fn inverse_transform(&self, _dataset: &Dataset) -> Result<Dataset, TransformError> {
+        Err(TransformError::NotInvertible)
+    }
+}
@@ -1,32 +1,159 @@
 use crate::data::dataset::Dataset;
+use crate::math::matrix::Matrix;
 use crate::math::random::DeterministicRng;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
 
 #[derive(Debug, Clone, Copy)]
 pub struct SyntheticConfig {
     pub rows: usize,
     pub seed: u64,
+    pub samples_per_day: usize,
+    pub diurnal_amplitude: f64,
+    pub burst_probability: f64,
+    pub tail_index: f64,
+    pub anomaly_rate: f64,
+    pub noise_level: f64,
 }
 
 impl Default for SyntheticConfig {
     fn default() -> Self {
-        SyntheticConfig { rows: 256, seed: 42 }
+        SyntheticConfig {
+            rows: 256,
+            seed: 42,
+            samples_per_day: 96,
+            diurnal_amplitude: 0.5,
+            burst_probability: 0.05,
+            tail_index: 1.5,
+            anomaly_rate: 0.02,
+            noise_level: 0.5,
+        }
     }
 }
 
-pub fn generate_network_dataset(config: SyntheticConfig) -> Dataset {
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TrafficLabel {
+    Normal,
+    Ddos,
+    LinkFailure,
+    Scan,
+}
+
+impl TrafficLabel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrafficLabel::Normal => "normal",
+            TrafficLabel::Ddos => "ddos",
+            TrafficLabel::LinkFailure => "link_failure",
+            TrafficLabel::Scan => "scan",
+        }
+    }
+
+    pub fn is_anomaly(&self) -> bool {
+        *self != TrafficLabel::Normal
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntheticTraffic {
+    pub dataset: Dataset,
+    pub labels: Vec<TrafficLabel>,
+}
+
+impl SyntheticTraffic {
+    pub fn write_labels(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = csv::WriterBuilder::new().has_headers(true).from_path(path)?;
+        writer.write_record(["row", "label"])?;
+        for (row, label) in self.labels.iter().enumerate() {
+            writer.write_record([row.to_string().as_str(), label.as_str()])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+pub const NETWORK_FEATURES: [&str; 6] = [
+    "flow_duration",
+    "src_bytes",
+    "dst_bytes",
+    "packet_rate",
+    "jitter",
+    "loss_rate",
+];
+
+fn pareto(rng: &mut DeterministicRng, tail_index: f64) -> f64 {
+    (1.0 - rng.next_f64()).powf(-1.0 / tail_index.max(0.1))
+}
+
+fn diurnal_load(config: &SyntheticConfig, row: usize) -> f64 {
+    let samples_per_day = config.samples_per_day.max(1);
+    let phase = (row % samples_per_day) as f64 / samples_per_day as f64;
+    // Trough at midnight, peak at midday.
+    1.0 - config.diurnal_amplitude * (2.0 * std::f64::consts::PI * phase).cos()
+}
+
+pub fn generate_network_traffic(config: SyntheticConfig) -> SyntheticTraffic {
     let mut rng = DeterministicRng::new(config.seed);
-    let mut data = Vec::with_capacity(config.rows);
+    let mut values = Vec::with_capacity(config.rows * NETWORK_FEATURES.len());
     let mut target = Vec::with_capacity(config.rows);
+    let mut labels = Vec::with_capacity(config.rows);
     for i in 0..config.rows {
-        let flow_duration = rng.gen_range(1.0, 2500.0);
-        let src_bytes = rng.gen_range(64.0, 1024.0);
-        let dst_bytes = rng.gen_range(64.0, 4096.0);
-        let packet_rate = rng.gen_range(1.0, 120.0);
-        let jitter = rng.gen_range(0.1, 20.0);
-        let loss_rate = rng.gen_range(0.0, 0.2);
-        let congestion_score =
-            0.3 * packet_rate + 0.2 * jitter + 1.5 * loss_rate + (i % 5) as f64 * 0.1;
-        data.push(vec![
+        let load = diurnal_load(&config, i).max(0.05);
+        let burst = if rng.bernoulli(config.burst_probability) {
+            pareto(&mut rng, config.tail_index)
+        } else {
+            1.0
+        };
+
+        let mut flow_duration = (20.0 * pareto(&mut rng, config.tail_index) * burst).min(1e6);
+        let mut packet_rate = (40.0 * load * burst * rng.log_normal(0.0, 0.25)).max(1.0);
+        let mut src_bytes = rng.log_normal(6.0, 0.5).clamp(64.0, 1500.0);
+        let mut dst_bytes = (src_bytes * rng.log_normal(1.0, 0.6)).clamp(64.0, 65_535.0);
+        let mut loss_rate = (0.005
+            + 0.0015 * (packet_rate - 60.0).max(0.0)
+            + rng.normal(0.0, 0.005).abs())
+        .min(1.0);
+        let mut jitter =
+            (0.5 + 0.08 * packet_rate + 30.0 * loss_rate + rng.normal(0.0, 0.5)).max(0.0);
+
+        let label = if rng.bernoulli(config.anomaly_rate) {
+            match rng.gen_index(3) {
+                0 => TrafficLabel::Ddos,
+                1 => TrafficLabel::LinkFailure,
+                _ => TrafficLabel::Scan,
+            }
+        } else {
+            TrafficLabel::Normal
+        };
+        match label {
+            TrafficLabel::Normal => {}
+            TrafficLabel::Ddos => {
+                packet_rate *= rng.gen_range(5.0, 10.0);
+                src_bytes = rng.gen_range(64.0, 128.0);
+                flow_duration = rng.gen_range(1.0, 50.0);
+                loss_rate = (loss_rate + rng.gen_range(0.05, 0.2)).min(1.0);
+                jitter += rng.gen_range(5.0, 15.0);
+            }
+            TrafficLabel::LinkFailure => {
+                packet_rate *= rng.gen_range(0.05, 0.2);
+                loss_rate = rng.gen_range(0.3, 0.9);
+                jitter += rng.gen_range(20.0, 50.0);
+            }
+            TrafficLabel::Scan => {
+                flow_duration = rng.gen_range(1.0, 5.0);
+                src_bytes = rng.gen_range(40.0, 80.0);
+                dst_bytes = rng.gen_range(0.0, 80.0);
+                packet_rate = rng.gen_range(20.0, 60.0);
+            }
+        }
+
+        let congestion_score = 0.3 * packet_rate
+            + 0.2 * jitter
+            + 1.5 * loss_rate
+            + rng.normal(0.0, config.noise_level.max(0.0));
+        values.extend_from_slice(&[
             flow_duration,
             src_bytes,
             dst_bytes,
@@ -35,19 +162,280 @@ pub fn generate_network_dataset(config: SyntheticConfig) -> Dataset {
             loss_rate,
         ]);
         target.push(congestion_score);
+        labels.push(label);
     }
-    Dataset::from_records(
-        vec![
-            "flow_duration".into(),
-            "src_bytes".into(),
-            "dst_bytes".into(),
-            "packet_rate".into(),
-            "jitter".into(),
-            "loss_rate".into(),
-        ],
+    let data = Matrix::new(config.rows, NETWORK_FEATURES.len(), values)
+        .expect("synthetic rows have one value per feature");
+    let dataset = Dataset::from_matrix(
+        NETWORK_FEATURES.iter().map(|name| name.to_string()).collect(),
         "congestion_score",
         data,
         target,
     )
-    .expect("synthetic dataset should be valid")
+    .expect("synthetic dataset should be valid");
+    SyntheticTraffic { dataset, labels }
+}
+
+pub fn generate_network_dataset(config: SyntheticConfig) -> Dataset {
+    generate_network_traffic(config).dataset
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TrafficClass {
+    Web,
+    Video,
+    Voip,
+    Bulk,
+    Attack,
+}
+
+impl TrafficClass {
+    pub const ALL: [TrafficClass; 5] = [
+        TrafficClass::Web,
+        TrafficClass::Video,
+        TrafficClass::Voip,
+        TrafficClass::Bulk,
+        TrafficClass::Attack,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrafficClass::Web => "web",
+            TrafficClass::Video => "video",
+            TrafficClass::Voip => "voip",
+            TrafficClass::Bulk => "bulk",
+            TrafficClass::Attack => "attack",
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<TrafficClass> {
+        TrafficClass::ALL.get(index).copied()
+    }
+
+    // Median and log-scale spread of each network feature for the class.
+    fn profile(&self) -> [(f64, f64); 6] {
+        match self {
+            TrafficClass::Web => [
+                (20.0, 1.0),
+                (400.0, 0.4),
+                (5_000.0, 0.8),
+                (20.0, 0.5),
+                (2.0, 0.4),
+                (0.01, 0.5),
+            ],
+            TrafficClass::Video => [
+                (1_200.0, 0.5),
+                (200.0, 0.3),
+                (50_000.0, 0.4),
+                (120.0, 0.3),
+                (3.0, 0.3),
+                (0.005, 0.5),
+            ],
+            TrafficClass::Voip => [
+                (150.0, 0.5),
+                (160.0, 0.1),
+                (160.0, 0.1),
+                (50.0, 0.1),
+                (0.5, 0.3),
+                (0.002, 0.5),
+            ],
+            TrafficClass::Bulk => [
+                (900.0, 0.6),
+                (1_400.0, 0.05),
+                (100.0, 0.3),
+                (200.0, 0.4),
+                (5.0, 0.4),
+                (0.02, 0.5),
+            ],
+            TrafficClass::Attack => [
+                (2.0, 0.5),
+                (64.0, 0.1),
+                (40.0, 0.5),
+                (800.0, 0.4),
+                (10.0, 0.4),
+                (0.1, 0.4),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClassificationConfig {
+    pub rows: usize,
+    pub seed: u64,
+    /// Relative frequency of web, video, VoIP, bulk and attack flows.
+    pub class_weights: [f64; 5],
+    pub noise_level: f64,
+}
+
+impl Default for ClassificationConfig {
+    fn default() -> Self {
+        ClassificationConfig {
+            rows: 256,
+            seed: 42,
+            class_weights: [0.4, 0.25, 0.15, 0.15, 0.05],
+            noise_level: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntheticError {
+    /// Class weights must be finite, non-negative and not all zero.
+    InvalidClassWeights([f64; 5]),
+    /// A row, link or step count of zero, which would generate no data.
+    ZeroCount(&'static str),
+}
+
+impl fmt::Display for SyntheticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyntheticError::InvalidClassWeights(weights) => write!(
+                f,
+                "class weights must be non-negative with at least one positive, got {weights:?}"
+            ),
+            SyntheticError::ZeroCount(name) => write!(f, "{name} must be at least 1"),
+        }
+    }
+}
+
+impl Error for SyntheticError {}
+
+/// Labelled flows whose target is the `TrafficClass` index.
+pub fn generate_traffic_classes(config: ClassificationConfig) -> Result<Dataset, SyntheticError> {
+    if config.rows == 0 {
+        return Err(SyntheticError::ZeroCount("rows"));
+    }
+    let weights = config.class_weights;
+    if weights
+        .iter()
+        .any(|weight| !weight.is_finite() || *weight < 0.0)
+        || weights.iter().all(|weight| *weight == 0.0)
+    {
+        return Err(SyntheticError::InvalidClassWeights(weights));
+    }
+    let mut rng = DeterministicRng::new(config.seed);
+    let mut values = Vec::with_capacity(config.rows * NETWORK_FEATURES.len());
+    let mut target = Vec::with_capacity(config.rows);
+    for _ in 0..config.rows {
+        let class = rng
+            .categorical(&weights)
+            .and_then(TrafficClass::from_index)
+            .expect("weights were validated");
+        for (median, spread) in class.profile().iter() {
+            values.push(median * rng.log_normal(0.0, spread * config.noise_level.max(0.0)));
+        }
+        target.push(class.index() as f64);
+    }
+    let data = Matrix::new(config.rows, NETWORK_FEATURES.len(), values)
+        .expect("synthetic rows have one value per feature");
+    Ok(Dataset::from_matrix(
+        NETWORK_FEATURES
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        "traffic_class",
+        data,
+        target,
+    )
+    .expect("synthetic dataset should be valid"))
+}
+
+/// Binary copy of a class-labelled dataset with target 1.0 for `class` and 0.0 otherwise.
+pub fn one_vs_rest(dataset: &Dataset, class: TrafficClass) -> Dataset {
+    let mut binary = dataset.clone();
+    for value in &mut binary.target {
+        *value = if *value as usize == class.index() { 1.0 } else { 0.0 };
+    }
+    binary.target_name = format!("is_{}", class.as_str());
+    binary
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TimeSeriesConfig {
+    pub links: usize,
+    pub steps: usize,
+    pub seed: u64,
+    pub start_timestamp: f64,
+    pub interval_seconds: f64,
+    /// Utilization change per step, in percentage points.
+    pub trend: f64,
+    pub seasonal_period: usize,
+    pub seasonal_amplitude: f64,
+    pub noise_level: f64,
+}
+
+impl Default for TimeSeriesConfig {
+    fn default() -> Self {
+        TimeSeriesConfig {
+            links: 4,
+            steps: 288,
+            seed: 42,
+            start_timestamp: 1_700_000_000.0,
+            interval_seconds: 300.0,
+            trend: 0.01,
+            seasonal_period: 288,
+            seasonal_amplitude: 20.0,
+            noise_level: 2.0,
+        }
+    }
+}
+
+pub const TIME_SERIES_FEATURES: [&str; 5] = ["timestamp", "link_id", "packet_rate", "jitter", "loss_rate"];
+
+/// Per-link telemetry ordered by timestamp, then link, with link utilization
+/// (percent) as the target.
+pub fn generate_link_time_series(config: TimeSeriesConfig) -> Result<Dataset, SyntheticError> {
+    if config.links == 0 {
+        return Err(SyntheticError::ZeroCount("links"));
+    }
+    if config.steps == 0 {
+        return Err(SyntheticError::ZeroCount("steps"));
+    }
+    let mut rng = DeterministicRng::new(config.seed);
+    let baselines: Vec<f64> = (0..config.links)
+        .map(|_| rng.gen_range(25.0, 55.0))
+        .collect();
+    let phases: Vec<f64> = (0..config.links)
+        .map(|_| rng.gen_range(-0.05, 0.05))
+        .collect();
+    let mut residuals = vec![0.0; config.links];
+    let rows = config.links * config.steps;
+    let mut values = Vec::with_capacity(rows * TIME_SERIES_FEATURES.len());
+    let mut target = Vec::with_capacity(rows);
+    let period = config.seasonal_period.max(1) as f64;
+    for step in 0..config.steps {
+        let timestamp = config.start_timestamp + step as f64 * config.interval_seconds;
+        for link in 0..config.links {
+            // AR(1) residuals keep neighbouring samples correlated.
+            residuals[link] = 0.7 * residuals[link] + rng.normal(0.0, config.noise_level.max(0.0));
+            let season = (2.0 * std::f64::consts::PI * (step as f64 / period + phases[link])).sin();
+            let utilization = (baselines[link]
+                + config.trend * step as f64
+                + config.seasonal_amplitude * season
+                + residuals[link])
+                .clamp(0.0, 100.0);
+            let packet_rate = (utilization * 12.0 * rng.log_normal(0.0, 0.05)).max(0.0);
+            let loss_rate = (0.001 + 0.002 * (utilization - 80.0).max(0.0)
+                + rng.normal(0.0, 0.0005).abs())
+            .min(1.0);
+            let jitter = (0.3 + 0.05 * utilization + rng.normal(0.0, 0.2)).max(0.0);
+            values.extend_from_slice(&[timestamp, link as f64, packet_rate, jitter, loss_rate]);
+            target.push(utilization);
+        }
+    }
+    let data = Matrix::new(rows, TIME_SERIES_FEATURES.len(), values)
+        .expect("time series rows have one value per feature");
+    Ok(Dataset::from_matrix(
+        TIME_SERIES_FEATURES.iter().map(|name| name.to_string()).collect(),
+        "utilization",
+        data,
+        target,
+    )
+    .expect("time series dataset should be valid"))
 }
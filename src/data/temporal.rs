@@ -0,0 +1,287 @@
+use crate::data::dataset::{Dataset, DatasetError};
+use crate::data::TransformError;
+use crate::math::stats::RunningStats;
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmUpPolicy {
+    /// Remove rows that do not yet have the full lag and window history.
+    #[default]
+    Drop,
+    /// Keep every row and fill incomplete lag and window features with NaN.
+    Nan,
+    /// Keep every row; windows use the history available so far and lags
+    /// without history are NaN.
+    Partial,
+}
+
+/// Time-window features computed per series. Rows are ordered by
+/// `time_column` (or by row position when absent) within each value of
+/// `group_column`; the output keeps the input row order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemporalConfig {
+    pub time_column: Option<String>,
+    pub group_column: Option<String>,
+    pub columns: Vec<String>,
+    pub lags: Vec<usize>,
+    pub target_lags: Vec<usize>,
+    /// Rolling windows, in rows, each including the current row.
+    pub windows: Vec<usize>,
+    pub ewma_alphas: Vec<f64>,
+    pub deltas: bool,
+    /// Deltas divided by the elapsed time, or per row without a time column.
+    pub rates: bool,
+    /// Hour-of-day and day-of-week features from a Unix-seconds time column.
+    pub calendar: bool,
+    pub warm_up: WarmUpPolicy,
+}
+
+impl Default for TemporalConfig {
+    fn default() -> Self {
+        TemporalConfig {
+            time_column: None,
+            group_column: None,
+            columns: Vec::new(),
+            lags: vec![1],
+            target_lags: Vec::new(),
+            windows: Vec::new(),
+            ewma_alphas: Vec::new(),
+            deltas: false,
+            rates: false,
+            calendar: false,
+            warm_up: WarmUpPolicy::Drop,
+        }
+    }
+}
+
+impl TemporalConfig {
+    /// Number of earlier rows a series needs before every feature is complete.
+    pub fn warm_up_rows(&self) -> usize {
+        let lag = self
+            .lags
+            .iter()
+            .chain(self.target_lags.iter())
+            .cloned()
+            .max()
+            .unwrap_or(0);
+        let window = self
+            .windows
+            .iter()
+            .map(|window| window.saturating_sub(1))
+            .max()
+            .unwrap_or(0);
+        let delta = usize::from(self.deltas || self.rates);
+        lag.max(window).max(delta)
+    }
+}
+
+fn column_index(dataset: &Dataset, name: &str) -> Result<usize, TransformError> {
+    dataset
+        .feature_index(name)
+        .ok_or_else(|| TransformError::UnknownFeature {
+            name: name.to_string(),
+        })
+}
+
+/// Original row indices of each series, in time order.
+fn series_order(
+    dataset: &Dataset,
+    config: &TemporalConfig,
+) -> Result<Vec<Vec<usize>>, TransformError> {
+    let time = match &config.time_column {
+        Some(name) => Some(column_index(dataset, name)?),
+        None => None,
+    };
+    let group = match &config.group_column {
+        Some(name) => Some(column_index(dataset, name)?),
+        None => None,
+    };
+    let mut order: Vec<usize> = (0..dataset.num_rows()).collect();
+    let key = |row: usize, column: Option<usize>| column.map_or(0.0, |col| dataset.row(row)[col]);
+    order.sort_by(|a, b| {
+        key(*a, group)
+            .total_cmp(&key(*b, group))
+            .then(key(*a, time).total_cmp(&key(*b, time)))
+            .then(a.cmp(b))
+    });
+    let mut series: Vec<Vec<usize>> = Vec::new();
+    for row in order {
+        match series.last_mut() {
+            Some(current) if key(current[0], group).total_cmp(&key(row, group)).is_eq() => {
+                current.push(row)
+            }
+            _ => series.push(vec![row]),
+        }
+    }
+    Ok(series)
+}
+
+pub fn add_temporal_features(
+    dataset: &Dataset,
+    config: &TemporalConfig,
+) -> Result<Dataset, TransformError> {
+    if config.calendar && config.time_column.is_none() {
+        return Err(TransformError::MissingTimestamp);
+    }
+    let series = series_order(dataset, config)?;
+    let time = match &config.time_column {
+        Some(name) => Some(column_index(dataset, name)?),
+        None => None,
+    };
+    let rows = dataset.num_rows();
+    let partial = config.warm_up == WarmUpPolicy::Partial;
+    let mut features: Vec<(String, Vec<f64>)> = Vec::new();
+    let mut position = vec![0; rows];
+    for rows_in_series in &series {
+        for (step, row) in rows_in_series.iter().enumerate() {
+            position[*row] = step;
+        }
+    }
+
+    let mut sources: Vec<(String, Vec<f64>)> = Vec::new();
+    for name in &config.columns {
+        let index = column_index(dataset, name)?;
+        sources.push((name.clone(), dataset.column(index).to_vec()));
+    }
+    for (name, values) in &sources {
+        for &lag in &config.lags {
+            features.push((format!("{name}_lag{lag}"), lagged(values, &series, lag)));
+        }
+        for &window in &config.windows {
+            let window = window.max(1);
+            let mut stats = vec![vec![f64::NAN; rows]; 4];
+            for rows_in_series in &series {
+                for (step, row) in rows_in_series.iter().enumerate() {
+                    if step + 1 < window && !partial {
+                        continue;
+                    }
+                    let start = (step + 1).saturating_sub(window);
+                    let mut running = RunningStats::new();
+                    running.extend(rows_in_series[start..=step].iter().map(|idx| values[*idx]));
+                    stats[0][*row] = running.mean();
+                    stats[1][*row] = running.std_dev();
+                    stats[2][*row] = running.min().unwrap_or(f64::NAN);
+                    stats[3][*row] = running.max().unwrap_or(f64::NAN);
+                }
+            }
+            for (label, column) in ["mean", "std", "min", "max"].iter().zip(stats) {
+                features.push((format!("{name}_{label}{window}"), column));
+            }
+        }
+        for &alpha in &config.ewma_alphas {
+            let mut smoothed = vec![f64::NAN; rows];
+            for rows_in_series in &series {
+                let mut state: Option<f64> = None;
+                for row in rows_in_series {
+                    let value = values[*row];
+                    let next = match state {
+                        Some(previous) => alpha * value + (1.0 - alpha) * previous,
+                        None => value,
+                    };
+                    state = Some(next);
+                    smoothed[*row] = next;
+                }
+            }
+            features.push((format!("{name}_ewma{alpha}"), smoothed));
+        }
+        if config.deltas || config.rates {
+            let previous = lagged(values, &series, 1);
+            let deltas: Vec<f64> = values
+                .iter()
+                .zip(&previous)
+                .map(|(current, previous)| current - previous)
+                .collect();
+            if config.rates {
+                let elapsed: Vec<f64> = match time {
+                    Some(col) => {
+                        let stamps = dataset.column(col).to_vec();
+                        let previous_stamps = lagged(&stamps, &series, 1);
+                        stamps
+                            .iter()
+                            .zip(previous_stamps)
+                            .map(|(current, previous)| current - previous)
+                            .collect()
+                    }
+                    None => vec![1.0; rows],
+                };
+                let rates = deltas
+                    .iter()
+                    .zip(elapsed)
+                    .map(|(delta, seconds)| {
+                        if seconds > 0.0 {
+                            delta / seconds
+                        } else {
+                            f64::NAN
+                        }
+                    })
+                    .collect();
+                if config.deltas {
+                    features.push((format!("{name}_delta"), deltas));
+                }
+                features.push((format!("{name}_rate"), rates));
+            } else {
+                features.push((format!("{name}_delta"), deltas));
+            }
+        }
+    }
+    for &lag in &config.target_lags {
+        features.push((
+            format!("{}_lag{lag}", dataset.target_name),
+            lagged(&dataset.target, &series, lag),
+        ));
+    }
+    if let Some(col) = time.filter(|_| config.calendar) {
+        let stamps = dataset.column(col).to_vec();
+        let hours: Vec<f64> = stamps
+            .iter()
+            .map(|stamp| stamp.rem_euclid(SECONDS_PER_DAY) / 3_600.0)
+            .collect();
+        // 1970-01-01 was a Thursday; Monday is day 0.
+        let days: Vec<f64> = stamps
+            .iter()
+            .map(|stamp| ((stamp / SECONDS_PER_DAY).floor() + 3.0).rem_euclid(7.0))
+            .collect();
+        let angle = |hour: &f64| 2.0 * std::f64::consts::PI * hour / 24.0;
+        features.push((
+            "hour_sin".to_string(),
+            hours.iter().map(|hour| angle(hour).sin()).collect(),
+        ));
+        features.push((
+            "hour_cos".to_string(),
+            hours.iter().map(|hour| angle(hour).cos()).collect(),
+        ));
+        features.push((
+            "is_weekend".to_string(),
+            days.iter().map(|day| f64::from(*day >= 5.0)).collect(),
+        ));
+        features.push(("hour_of_day".to_string(), hours));
+        features.push(("day_of_week".to_string(), days));
+    }
+
+    let mut expanded = dataset.clone();
+    for (name, values) in features {
+        expanded.add_feature(name, values)?;
+    }
+    if config.warm_up != WarmUpPolicy::Drop {
+        return Ok(expanded);
+    }
+    let required = config.warm_up_rows();
+    let complete: Vec<usize> = (0..rows).filter(|row| position[*row] >= required).collect();
+    if complete.is_empty() {
+        return Err(DatasetError::EmptyDataset.into());
+    }
+    Ok(expanded.subset(&complete)?)
+}
+
+fn lagged(values: &[f64], series: &[Vec<usize>], lag: usize) -> Vec<f64> {
+    let mut shifted = vec![f64::NAN; values.len()];
+    for rows_in_series in series {
+        for (step, row) in rows_in_series.iter().enumerate().skip(lag) {
+            shifted[*row] = values[rows_in_series[step - lag]];
+        }
+    }
+    shifted
+}
@@ -0,0 +1,357 @@
+//! Dataset versioning: manifests that pin down exactly which data a model
+//! saw, and a diff between two versions of a CSV.
+
+use crate::data::dataset::Dataset;
+use crate::math::stats;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Summary statistics of one column; NaN values count as missing and are
+/// left out of the rest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSummary {
+    pub name: String,
+    pub count: usize,
+    pub missing: usize,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub std_dev: Option<f64>,
+}
+
+impl ColumnSummary {
+    pub fn new(name: &str, values: impl Iterator<Item = f64>) -> Self {
+        let (present, missing): (Vec<f64>, Vec<f64>) = values.partition(|value| !value.is_nan());
+        let observed = |value: f64| Some(value).filter(|_| !present.is_empty());
+        ColumnSummary {
+            name: name.to_string(),
+            count: present.len(),
+            missing: missing.len(),
+            min: stats::min(&present),
+            max: stats::max(&present),
+            mean: observed(stats::mean(&present)),
+            std_dev: observed(stats::std_dev(&present)),
+        }
+    }
+}
+
+/// Where a dataset came from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// File the dataset was derived from, if any.
+    pub source: Option<String>,
+    /// Fingerprint of the source dataset.
+    pub parent: Option<String>,
+    /// Every step applied since the original data, oldest first.
+    pub transformations: Vec<String>,
+    /// Why the source's history was not carried forward, when its sidecar
+    /// manifest could not be loaded or describes different data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetManifest {
+    pub fingerprint: String,
+    pub schema_hash: String,
+    pub content_hash: String,
+    pub rows: usize,
+    pub target: String,
+    /// The features followed by the target.
+    pub columns: Vec<ColumnSummary>,
+    pub provenance: Provenance,
+    /// Unix seconds.
+    pub created_at: u64,
+}
+
+/// The sidecar manifest path for a CSV, `<path>.manifest.json`.
+pub fn manifest_path(csv_path: &str) -> String {
+    format!("{csv_path}.manifest.json")
+}
+
+impl DatasetManifest {
+    pub fn new(dataset: &Dataset, provenance: Provenance) -> Self {
+        let mut columns: Vec<ColumnSummary> = dataset
+            .feature_names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| ColumnSummary::new(name, dataset.column(idx).iter()))
+            .collect();
+        columns.push(ColumnSummary::new(
+            &dataset.target_name,
+            dataset.target.iter().copied(),
+        ));
+        DatasetManifest {
+            fingerprint: dataset.fingerprint(),
+            schema_hash: dataset.schema_hash(),
+            content_hash: dataset.content_hash(),
+            rows: dataset.num_rows(),
+            target: dataset.target_name.clone(),
+            columns,
+            provenance,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Manifest for `dataset`, produced by `step` from the CSV at `source`.
+    /// The source's own history is carried forward when it has a sidecar
+    /// manifest describing `parent`; an unreadable or stale sidecar is
+    /// recorded in `history_error` instead.
+    pub fn derived(dataset: &Dataset, source: &str, parent: &Dataset, step: &str) -> Self {
+        let path = manifest_path(source);
+        let (mut transformations, history_error) = if !Path::new(&path).exists() {
+            (Vec::new(), None)
+        } else {
+            match DatasetManifest::load(&path) {
+                Ok(manifest) if manifest.matches(parent) => {
+                    (manifest.provenance.transformations, None)
+                }
+                Ok(_) => (Vec::new(), Some(format!("{path} describes different data"))),
+                Err(err) => (Vec::new(), Some(format!("failed to load {path}: {err}"))),
+            }
+        };
+        transformations.push(step.to_string());
+        DatasetManifest::new(
+            dataset,
+            Provenance {
+                source: Some(source.to_string()),
+                parent: Some(parent.fingerprint()),
+                transformations,
+                history_error,
+            },
+        )
+    }
+
+    /// Whether `dataset` is exactly the data this manifest describes.
+    pub fn matches(&self, dataset: &Dataset) -> bool {
+        self.schema_hash == dataset.schema_hash() && self.content_hash == dataset.content_hash()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Writes the manifest next to the CSV at `csv_path`.
+    pub fn save_beside(&self, csv_path: &str) -> Result<String, Box<dyn Error>> {
+        let path = manifest_path(csv_path);
+        self.save(&path)?;
+        Ok(path)
+    }
+}
+
+#[derive(Debug)]
+pub enum DiffError {
+    UnknownKey { column: String, file: String },
+    DuplicateKey { value: String, file: String },
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffError::UnknownKey { column, file } => {
+                write!(f, "key column '{column}' is missing from {file}")
+            }
+            DiffError::DuplicateKey { value, file } => {
+                write!(f, "key '{value}' appears more than once in {file}")
+            }
+        }
+    }
+}
+
+impl Error for DiffError {}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CellChange {
+    pub column: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowChange {
+    /// The key column's value, or the 1-based row number without a key.
+    pub key: String,
+    pub cells: Vec<CellChange>,
+}
+
+/// What changed between two versions of a CSV. Rows are matched by a key
+/// column when given, otherwise by position; only columns present in both
+/// files are compared cell by cell.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CsvDiff {
+    pub rows_before: usize,
+    pub rows_after: usize,
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    pub added_rows: Vec<String>,
+    pub removed_rows: Vec<String>,
+    pub changed_rows: Vec<RowChange>,
+    pub unchanged_rows: usize,
+}
+
+impl CsvDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.added_rows.is_empty()
+            && self.removed_rows.is_empty()
+            && self.changed_rows.is_empty()
+    }
+}
+
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn read(path: &str) -> Result<Table, Box<dyn Error>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_path(path)?;
+        let headers = reader.headers()?.iter().map(str::to_string).collect();
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            rows.push(record?.iter().map(str::to_string).collect());
+        }
+        Ok(Table { headers, rows })
+    }
+
+    fn column(&self, name: &str) -> Option<usize> {
+        self.headers.iter().position(|header| header == name)
+    }
+
+    /// Rows by key, keeping file order.
+    fn keyed(&self, key: Option<&str>, path: &str) -> Result<Vec<(String, usize)>, DiffError> {
+        let column = match key {
+            Some(column) => Some(self.column(column).ok_or_else(|| DiffError::UnknownKey {
+                column: column.to_string(),
+                file: path.to_string(),
+            })?),
+            None => None,
+        };
+        let mut seen = HashSet::new();
+        let mut keyed = Vec::with_capacity(self.rows.len());
+        for (idx, row) in self.rows.iter().enumerate() {
+            let value = match column {
+                Some(column) => row.get(column).cloned().unwrap_or_default(),
+                None => (idx + 1).to_string(),
+            };
+            if !seen.insert(value.clone()) {
+                return Err(DiffError::DuplicateKey {
+                    value,
+                    file: path.to_string(),
+                });
+            }
+            keyed.push((value, idx));
+        }
+        Ok(keyed)
+    }
+}
+
+/// Cells are equal as text or, when both parse, as numbers (`1` and `1.0`).
+fn same_cell(before: &str, after: &str) -> bool {
+    if before == after {
+        return true;
+    }
+    match (before.trim().parse::<f64>(), after.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a == b || (a.is_nan() && b.is_nan()),
+        _ => false,
+    }
+}
+
+pub fn diff_csv(
+    old_path: &str,
+    new_path: &str,
+    key: Option<&str>,
+) -> Result<CsvDiff, Box<dyn Error>> {
+    let old = Table::read(old_path)?;
+    let new = Table::read(new_path)?;
+    let shared: Vec<(String, usize, usize)> = old
+        .headers
+        .iter()
+        .enumerate()
+        .filter_map(|(old_idx, name)| {
+            new.column(name)
+                .map(|new_idx| (name.clone(), old_idx, new_idx))
+        })
+        .collect();
+
+    let old_rows = old.keyed(key, old_path)?;
+    let new_keyed = new.keyed(key, new_path)?;
+    let new_rows: HashMap<&str, usize> = new_keyed
+        .iter()
+        .map(|(row_key, idx)| (row_key.as_str(), *idx))
+        .collect();
+    let mut matched = HashSet::new();
+    let mut removed_rows = Vec::new();
+    let mut changed_rows = Vec::new();
+    let mut unchanged_rows = 0;
+    for (row_key, old_idx) in &old_rows {
+        let new_idx = match new_rows.get(row_key.as_str()) {
+            Some(new_idx) => *new_idx,
+            None => {
+                removed_rows.push(row_key.clone());
+                continue;
+            }
+        };
+        matched.insert(row_key.clone());
+        let cell = |row: &Vec<String>, idx: usize| row.get(idx).cloned().unwrap_or_default();
+        let cells: Vec<CellChange> = shared
+            .iter()
+            .map(|(column, old_col, new_col)| CellChange {
+                column: column.clone(),
+                before: cell(&old.rows[*old_idx], *old_col),
+                after: cell(&new.rows[new_idx], *new_col),
+            })
+            .filter(|change| !same_cell(&change.before, &change.after))
+            .collect();
+        if cells.is_empty() {
+            unchanged_rows += 1;
+        } else {
+            changed_rows.push(RowChange {
+                key: row_key.clone(),
+                cells,
+            });
+        }
+    }
+    let added_rows = new_keyed
+        .into_iter()
+        .map(|(row_key, _)| row_key)
+        .filter(|row_key| !matched.contains(row_key))
+        .collect();
+
+    Ok(CsvDiff {
+        rows_before: old.rows.len(),
+        rows_after: new.rows.len(),
+        added_columns: new
+            .headers
+            .iter()
+            .filter(|name| old.column(name).is_none())
+            .cloned()
+            .collect(),
+        removed_columns: old
+            .headers
+            .iter()
+            .filter(|name| new.column(name).is_none())
+            .cloned()
+            .collect(),
+        added_rows,
+        removed_rows,
+        changed_rows,
+        unchanged_rows,
+    })
+}
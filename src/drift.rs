@@ -0,0 +1,385 @@
+//! Drift monitoring: a compact reference profile of the training inputs and
+//! predictions, compared against live batches with PSI, the two-sample
+//! Kolmogorov–Smirnov statistic and Jensen–Shannon divergence.
+
+use crate::data::dataset::Dataset;
+use crate::data::TransformError;
+use crate::math::stats::{self, QuantileMethod};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Bins used by `ReferenceProfile::new` when no count is given.
+pub const DEFAULT_BINS: usize = 10;
+/// Number of intervals between the stored percentiles used for the KS test.
+const PERCENTILE_STEPS: usize = 100;
+/// Floor for empty bins, so PSI stays finite.
+const MIN_PROPORTION: f64 = 1e-4;
+
+/// Quantile summary of one distribution: equal-frequency bins for PSI and
+/// Jensen–Shannon, and percentiles approximating the CDF for KS.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributionSketch {
+    /// Interior bin boundaries; value `x` falls in the bin counting the cuts
+    /// `<= x`, so the outer bins are open-ended.
+    pub cuts: Vec<f64>,
+    pub proportions: Vec<f64>,
+    /// The 0th through 100th percentiles.
+    pub percentiles: Vec<f64>,
+    pub count: usize,
+}
+
+fn finite(values: &[f64]) -> Vec<f64> {
+    let mut finite: Vec<f64> = values
+        .iter()
+        .copied()
+        .filter(|value| value.is_finite())
+        .collect();
+    finite.sort_by(|a, b| a.total_cmp(b));
+    finite
+}
+
+impl DistributionSketch {
+    /// Sketch of the finite `values`; `None` when there are none.
+    pub fn new(values: &[f64], bins: usize) -> Option<DistributionSketch> {
+        let sorted = finite(values);
+        if sorted.is_empty() {
+            return None;
+        }
+        let bins = bins.max(1);
+        let mut cuts: Vec<f64> = (1..bins)
+            .filter_map(|idx| {
+                stats::quantile_sorted(&sorted, idx as f64 / bins as f64, QuantileMethod::Linear)
+            })
+            .collect();
+        cuts.dedup();
+        // A cut at the minimum would leave the first bin empty by construction.
+        cuts.retain(|cut| *cut > sorted[0]);
+        let percentiles = (0..=PERCENTILE_STEPS)
+            .filter_map(|idx| {
+                let q = idx as f64 / PERCENTILE_STEPS as f64;
+                stats::quantile_sorted(&sorted, q, QuantileMethod::Linear)
+            })
+            .collect();
+        let mut sketch = DistributionSketch {
+            cuts,
+            proportions: Vec::new(),
+            percentiles,
+            count: sorted.len(),
+        };
+        sketch.proportions = sketch.bin_proportions(&sorted);
+        Some(sketch)
+    }
+
+    pub fn bin(&self, value: f64) -> usize {
+        self.cuts.partition_point(|cut| *cut <= value)
+    }
+
+    /// Share of the finite `values` in each of this sketch's bins.
+    pub fn bin_proportions(&self, values: &[f64]) -> Vec<f64> {
+        let mut counts = vec![0usize; self.cuts.len() + 1];
+        let mut total = 0;
+        for value in values.iter().filter(|value| value.is_finite()) {
+            counts[self.bin(*value)] += 1;
+            total += 1;
+        }
+        counts
+            .into_iter()
+            .map(|count| count as f64 / total.max(1) as f64)
+            .collect()
+    }
+
+    /// Reference CDF at `x`, interpolated between percentiles.
+    pub fn cdf(&self, x: f64) -> f64 {
+        let below = self.percentiles.partition_point(|p| *p <= x);
+        if below == 0 {
+            return 0.0;
+        }
+        if below == self.percentiles.len() {
+            return 1.0;
+        }
+        let (lower, upper) = (self.percentiles[below - 1], self.percentiles[below]);
+        let within = if upper > lower {
+            (x - lower) / (upper - lower)
+        } else {
+            0.0
+        };
+        (below - 1) as f64 / PERCENTILE_STEPS as f64 + within / PERCENTILE_STEPS as f64
+    }
+
+    pub fn psi(&self, values: &[f64]) -> f64 {
+        self.proportions
+            .iter()
+            .zip(self.bin_proportions(values))
+            .map(|(expected, actual)| {
+                let (expected, actual) = (expected.max(MIN_PROPORTION), actual.max(MIN_PROPORTION));
+                (actual - expected) * (actual / expected).ln()
+            })
+            .sum()
+    }
+
+    /// Largest gap between the live ECDF and the reference CDF, checked at
+    /// every live value and every stored percentile.
+    pub fn ks(&self, values: &[f64]) -> f64 {
+        let sorted = finite(values);
+        if sorted.is_empty() {
+            return 0.0;
+        }
+        let n = sorted.len() as f64;
+        let live_cdf = |x: f64| sorted.partition_point(|value| *value <= x) as f64 / n;
+        let at_live = sorted.iter().map(|x| (live_cdf(*x) - self.cdf(*x)).abs());
+        let at_reference = self
+            .percentiles
+            .iter()
+            .map(|x| (live_cdf(*x) - self.cdf(*x)).abs());
+        at_live.chain(at_reference).fold(0.0, f64::max)
+    }
+
+    /// Jensen–Shannon divergence over the reference bins, in bits (0 to 1).
+    pub fn jensen_shannon(&self, values: &[f64]) -> f64 {
+        let kl = |p: f64, m: f64| if p > 0.0 { p * (p / m).log2() } else { 0.0 };
+        self.proportions
+            .iter()
+            .zip(self.bin_proportions(values))
+            .map(|(p, q)| {
+                let m = (p + q) / 2.0;
+                0.5 * kl(*p, m) + 0.5 * kl(q, m)
+            })
+            .sum::<f64>()
+            .max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureReference {
+    pub name: String,
+    pub sketch: DistributionSketch,
+}
+
+/// Training-time distributions of every input feature and of the model's
+/// predictions, stored in the model artifact.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceProfile {
+    pub rows: usize,
+    /// Features with at least one finite value, in dataset order.
+    pub features: Vec<FeatureReference>,
+    pub predictions: Option<DistributionSketch>,
+}
+
+impl ReferenceProfile {
+    pub fn new(dataset: &Dataset, predictions: &[f64], bins: usize) -> ReferenceProfile {
+        let features = dataset
+            .feature_names
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, name)| {
+                DistributionSketch::new(&dataset.column(idx).to_vec(), bins).map(|sketch| {
+                    FeatureReference {
+                        name: name.clone(),
+                        sketch,
+                    }
+                })
+            })
+            .collect();
+        ReferenceProfile {
+            rows: dataset.num_rows(),
+            features,
+            predictions: DistributionSketch::new(predictions, bins),
+        }
+    }
+
+    /// Scores a live batch, and its predictions when given, against the
+    /// reference.
+    pub fn compare(
+        &self,
+        dataset: &Dataset,
+        predictions: Option<&[f64]>,
+        config: &DriftConfig,
+    ) -> Result<DriftReport, TransformError> {
+        let evaluated = dataset.num_rows() >= config.min_rows;
+        let mut features = Vec::with_capacity(self.features.len());
+        for reference in &self.features {
+            let idx = dataset.feature_index(&reference.name).ok_or_else(|| {
+                TransformError::UnknownFeature {
+                    name: reference.name.clone(),
+                }
+            })?;
+            let values = dataset.column(idx).to_vec();
+            features.push(FeatureDrift::new(
+                &reference.name,
+                &reference.sketch,
+                &values,
+                config.thresholds_for(&reference.name),
+                evaluated,
+            ));
+        }
+        let prediction = match (&self.predictions, predictions) {
+            (Some(sketch), Some(values)) => Some(FeatureDrift::new(
+                PREDICTION,
+                sketch,
+                values,
+                config.thresholds_for(PREDICTION),
+                evaluated,
+            )),
+            _ => None,
+        };
+        let alerts = features
+            .iter()
+            .chain(&prediction)
+            .flat_map(|drift| drift.alerts.iter().cloned())
+            .collect();
+        Ok(DriftReport {
+            rows: dataset.num_rows(),
+            evaluated,
+            features,
+            prediction,
+            alerts,
+        })
+    }
+}
+
+/// Name under which prediction drift is reported and configured.
+pub const PREDICTION: &str = "prediction";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftMetric {
+    Psi,
+    Ks,
+    JensenShannon,
+}
+
+/// Alert when a score exceeds its threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DriftThresholds {
+    pub psi: f64,
+    pub ks: f64,
+    pub jensen_shannon: f64,
+}
+
+impl Default for DriftThresholds {
+    fn default() -> Self {
+        DriftThresholds {
+            psi: 0.25,
+            ks: 0.2,
+            jensen_shannon: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DriftConfig {
+    pub thresholds: DriftThresholds,
+    /// Per-feature thresholds; `prediction` configures prediction drift.
+    pub overrides: BTreeMap<String, DriftThresholds>,
+    /// Smaller batches are scored but never alert.
+    pub min_rows: usize,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        DriftConfig {
+            thresholds: DriftThresholds::default(),
+            overrides: BTreeMap::new(),
+            min_rows: 30,
+        }
+    }
+}
+
+impl DriftConfig {
+    pub fn load(path: &str) -> Result<DriftConfig, Box<dyn Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn thresholds_for(&self, feature: &str) -> DriftThresholds {
+        self.overrides
+            .get(feature)
+            .copied()
+            .unwrap_or(self.thresholds)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriftAlert {
+    pub feature: String,
+    pub metric: DriftMetric,
+    pub value: f64,
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureDrift {
+    pub name: String,
+    pub psi: f64,
+    pub ks: f64,
+    pub jensen_shannon: f64,
+    pub alerts: Vec<DriftAlert>,
+}
+
+impl FeatureDrift {
+    fn new(
+        name: &str,
+        sketch: &DistributionSketch,
+        values: &[f64],
+        thresholds: DriftThresholds,
+        evaluated: bool,
+    ) -> FeatureDrift {
+        let (psi, ks, jensen_shannon) = (
+            sketch.psi(values),
+            sketch.ks(values),
+            sketch.jensen_shannon(values),
+        );
+        let alerts = if evaluated {
+            [
+                (DriftMetric::Psi, psi, thresholds.psi),
+                (DriftMetric::Ks, ks, thresholds.ks),
+                (
+                    DriftMetric::JensenShannon,
+                    jensen_shannon,
+                    thresholds.jensen_shannon,
+                ),
+            ]
+            .iter()
+            .filter(|(_, value, threshold)| value > threshold)
+            .map(|(metric, value, threshold)| DriftAlert {
+                feature: name.to_string(),
+                metric: *metric,
+                value: *value,
+                threshold: *threshold,
+            })
+            .collect()
+        } else {
+            Vec::new()
+        };
+        FeatureDrift {
+            name: name.to_string(),
+            psi,
+            ks,
+            jensen_shannon,
+            alerts,
+        }
+    }
+
+    pub fn drifted(&self) -> bool {
+        !self.alerts.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriftReport {
+    pub rows: usize,
+    /// Whether the batch had `min_rows` rows; alerts are only raised if so.
+    pub evaluated: bool,
+    pub features: Vec<FeatureDrift>,
+    pub prediction: Option<FeatureDrift>,
+    pub alerts: Vec<DriftAlert>,
+}
+
+impl DriftReport {
+    pub fn has_alerts(&self) -> bool {
+        !self.alerts.is_empty()
+    }
+}
@@ -0,0 +1,733 @@
+//! Compact binary encoding for artifacts.
+//!
+//! A file is `MAGIC`, a little-endian `u16` format version, a `u64` payload
+//! length, the payload and a CRC-32 of the payload. The payload is a
+//! self-describing tree of tagged values, so every serde type the artifacts
+//! use (including internally tagged enums) round-trips. Floats are stored as
+//! their raw IEEE-754 bits and come back bit for bit, NaN payloads included.
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use std::error::Error;
+use std::fmt;
+
+pub const MAGIC: [u8; 4] = *b"RMLB";
+pub const FORMAT_VERSION: u16 = 1;
+
+const HEADER_LEN: usize = 4 + 2 + 8;
+const CHECKSUM_LEN: usize = 4;
+/// Deepest sequence/map nesting the decoder follows before giving up, so a
+/// crafted payload cannot exhaust the stack.
+const MAX_DEPTH: usize = 128;
+
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_U64: u8 = 3;
+const TAG_I64: u8 = 4;
+const TAG_F64: u8 = 5;
+const TAG_STR: u8 = 6;
+const TAG_BYTES: u8 = 7;
+const TAG_SEQ: u8 = 8;
+const TAG_MAP: u8 = 9;
+
+#[derive(Debug, PartialEq)]
+pub enum BinaryError {
+    BadMagic,
+    UnsupportedFormat(u16),
+    Truncated,
+    ChecksumMismatch { expected: u32, found: u32 },
+    InvalidTag(u8),
+    InvalidUtf8,
+    TrailingBytes(usize),
+    TooDeep(usize),
+    Message(String),
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::BadMagic => write!(f, "not a binary artifact (bad magic bytes)"),
+            BinaryError::UnsupportedFormat(version) => {
+                write!(f, "unsupported binary format version {version}")
+            }
+            BinaryError::Truncated => write!(f, "binary artifact is truncated"),
+            BinaryError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "binary artifact checksum mismatch: expected {expected:08x}, found {found:08x}"
+            ),
+            BinaryError::InvalidTag(tag) => write!(f, "invalid value tag {tag}"),
+            BinaryError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            BinaryError::TrailingBytes(count) => {
+                write!(f, "{count} unexpected bytes after the payload")
+            }
+            BinaryError::TooDeep(limit) => {
+                write!(f, "values are nested more than {limit} levels deep")
+            }
+            BinaryError::Message(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Error for BinaryError {}
+
+impl ser::Error for BinaryError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        BinaryError::Message(msg.to_string())
+    }
+}
+
+impl de::Error for BinaryError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        BinaryError::Message(msg.to_string())
+    }
+}
+
+/// CRC-32 (IEEE 802.3, reflected polynomial 0xEDB88320).
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.starts_with(&MAGIC)
+}
+
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, BinaryError> {
+    let mut encoder = Encoder { output: Vec::new() };
+    value.serialize(&mut encoder)?;
+    let payload = encoder.output;
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len() + CHECKSUM_LEN);
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&payload);
+    bytes.extend_from_slice(&crc32(&payload).to_le_bytes());
+    Ok(bytes)
+}
+
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, BinaryError> {
+    if !is_binary(bytes) {
+        return Err(BinaryError::BadMagic);
+    }
+    if bytes.len() < HEADER_LEN {
+        return Err(BinaryError::Truncated);
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != FORMAT_VERSION {
+        return Err(BinaryError::UnsupportedFormat(version));
+    }
+    let mut length = [0u8; 8];
+    length.copy_from_slice(&bytes[6..HEADER_LEN]);
+    let length = u64::from_le_bytes(length) as usize;
+    let end = HEADER_LEN
+        .checked_add(length)
+        .filter(|end| end.saturating_add(CHECKSUM_LEN) <= bytes.len())
+        .ok_or(BinaryError::Truncated)?;
+    if bytes.len() > end + CHECKSUM_LEN {
+        return Err(BinaryError::TrailingBytes(bytes.len() - end - CHECKSUM_LEN));
+    }
+    let payload = &bytes[HEADER_LEN..end];
+    let mut stored = [0u8; 4];
+    stored.copy_from_slice(&bytes[end..]);
+    let expected = u32::from_le_bytes(stored);
+    let found = crc32(payload);
+    if expected != found {
+        return Err(BinaryError::ChecksumMismatch { expected, found });
+    }
+    let mut decoder = Decoder {
+        input: payload,
+        pos: 0,
+        depth: 0,
+    };
+    let value = T::deserialize(&mut decoder)?;
+    if decoder.pos != payload.len() {
+        return Err(BinaryError::TrailingBytes(payload.len() - decoder.pos));
+    }
+    Ok(value)
+}
+
+struct Encoder {
+    output: Vec<u8>,
+}
+
+impl Encoder {
+    fn write_u64(&mut self, value: u64) {
+        self.output.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.output.push(TAG_STR);
+        self.write_u64(value.len() as u64);
+        self.output.extend_from_slice(value.as_bytes());
+    }
+
+    /// Starts a sequence or map whose length is patched in by `Compound::finish`.
+    fn begin(&mut self, tag: u8) -> Compound<'_> {
+        self.output.push(tag);
+        let count_at = self.output.len();
+        self.write_u64(0);
+        Compound {
+            encoder: self,
+            count_at,
+            count: 0,
+        }
+    }
+
+    /// Opens the single-entry `{variant: ...}` map used for data-carrying
+    /// enum variants.
+    fn begin_variant(&mut self, variant: &str, tag: u8) -> Compound<'_> {
+        self.output.push(TAG_MAP);
+        self.write_u64(1);
+        self.write_str(variant);
+        self.begin(tag)
+    }
+}
+
+struct Compound<'a> {
+    encoder: &'a mut Encoder,
+    count_at: usize,
+    count: u64,
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        self.count += 1;
+        value.serialize(&mut *self.encoder)
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), BinaryError> {
+        self.count += 1;
+        self.encoder.write_str(key);
+        value.serialize(&mut *self.encoder)
+    }
+
+    fn finish(self) -> Result<(), BinaryError> {
+        let count = self.count.to_le_bytes();
+        self.encoder.output[self.count_at..self.count_at + 8].copy_from_slice(&count);
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Encoder {
+    type Ok = ();
+    type Error = BinaryError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, value: bool) -> Result<(), BinaryError> {
+        self.output.push(if value { TAG_TRUE } else { TAG_FALSE });
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), BinaryError> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), BinaryError> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), BinaryError> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), BinaryError> {
+        self.output.push(TAG_I64);
+        self.output.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), BinaryError> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), BinaryError> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), BinaryError> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), BinaryError> {
+        self.output.push(TAG_U64);
+        self.write_u64(value);
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), BinaryError> {
+        self.serialize_f64(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), BinaryError> {
+        self.output.push(TAG_F64);
+        self.write_u64(value.to_bits());
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), BinaryError> {
+        self.write_str(value.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), BinaryError> {
+        self.write_str(value);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), BinaryError> {
+        self.output.push(TAG_BYTES);
+        self.write_u64(value.len() as u64);
+        self.output.extend_from_slice(value);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), BinaryError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), BinaryError> {
+        self.output.push(TAG_NULL);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), BinaryError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), BinaryError> {
+        self.write_str(variant);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        self.output.push(TAG_MAP);
+        self.write_u64(1);
+        self.write_str(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, BinaryError> {
+        Ok(self.begin(TAG_SEQ))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>, BinaryError> {
+        Ok(self.begin(TAG_SEQ))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, BinaryError> {
+        Ok(self.begin(TAG_SEQ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, BinaryError> {
+        Ok(self.begin_variant(variant, TAG_SEQ))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, BinaryError> {
+        Ok(self.begin(TAG_MAP))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, BinaryError> {
+        Ok(self.begin(TAG_MAP))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, BinaryError> {
+        Ok(self.begin_variant(variant, TAG_MAP))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), BinaryError> {
+        self.count += 1;
+        key.serialize(&mut *self.encoder)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BinaryError> {
+        value.serialize(&mut *self.encoder)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), BinaryError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), BinaryError> {
+        self.finish()
+    }
+}
+
+struct Decoder<'de> {
+    input: &'de [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'de> Decoder<'de> {
+    fn take(&mut self, count: usize) -> Result<&'de [u8], BinaryError> {
+        let end = self
+            .pos
+            .checked_add(count)
+            .filter(|end| *end <= self.input.len())
+            .ok_or(BinaryError::Truncated)?;
+        let bytes = &self.input[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn peek_tag(&self) -> Result<u8, BinaryError> {
+        self.input
+            .get(self.pos)
+            .cloned()
+            .ok_or(BinaryError::Truncated)
+    }
+
+    fn read_tag(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u64(&mut self) -> Result<u64, BinaryError> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Reads a length prefix, rejecting counts the remaining input cannot hold.
+    fn read_len(&mut self) -> Result<usize, BinaryError> {
+        let len = self.read_u64()?;
+        if len > (self.input.len() - self.pos) as u64 {
+            return Err(BinaryError::Truncated);
+        }
+        Ok(len as usize)
+    }
+
+    fn read_str(&mut self) -> Result<&'de str, BinaryError> {
+        let len = self.read_len()?;
+        std::str::from_utf8(self.take(len)?).map_err(|_| BinaryError::InvalidUtf8)
+    }
+
+    /// Runs `visit` one nesting level deeper, failing past `MAX_DEPTH`.
+    fn nested<T>(
+        &mut self,
+        visit: impl FnOnce(&mut Self) -> Result<T, BinaryError>,
+    ) -> Result<T, BinaryError> {
+        if self.depth == MAX_DEPTH {
+            return Err(BinaryError::TooDeep(MAX_DEPTH));
+        }
+        self.depth += 1;
+        let result = visit(self);
+        self.depth -= 1;
+        result
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Decoder<'de> {
+    type Error = BinaryError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        match self.read_tag()? {
+            TAG_NULL => visitor.visit_unit(),
+            TAG_FALSE => visitor.visit_bool(false),
+            TAG_TRUE => visitor.visit_bool(true),
+            TAG_U64 => visitor.visit_u64(self.read_u64()?),
+            TAG_I64 => visitor.visit_i64(self.read_u64()? as i64),
+            TAG_F64 => visitor.visit_f64(f64::from_bits(self.read_u64()?)),
+            TAG_STR => visitor.visit_borrowed_str(self.read_str()?),
+            TAG_BYTES => {
+                let len = self.read_len()?;
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            }
+            TAG_SEQ => {
+                let remaining = self.read_len()?;
+                self.nested(|decoder| visitor.visit_seq(Elements { decoder, remaining }))
+            }
+            TAG_MAP => {
+                let remaining = self.read_len()?;
+                self.nested(|decoder| visitor.visit_map(Elements { decoder, remaining }))
+            }
+            tag => Err(BinaryError::InvalidTag(tag)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BinaryError> {
+        if self.peek_tag()? == TAG_NULL {
+            self.pos += 1;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        match self.read_tag()? {
+            TAG_STR => visitor.visit_enum(self.read_str()?.into_deserializer()),
+            TAG_MAP => {
+                if self.read_u64()? != 1 {
+                    return Err(de::Error::custom("enum variant must be a single-entry map"));
+                }
+                self.nested(|decoder| visitor.visit_enum(decoder))
+            }
+            tag => Err(BinaryError::InvalidTag(tag)),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct Elements<'a, 'de> {
+    decoder: &'a mut Decoder<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_, 'de> {
+    type Error = BinaryError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, BinaryError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Elements<'_, 'de> {
+    type Error = BinaryError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, BinaryError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, BinaryError> {
+        seed.deserialize(&mut *self.decoder)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Decoder<'de> {
+    type Error = BinaryError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), BinaryError> {
+        let variant = seed.deserialize(&mut *self)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Decoder<'de> {
+    type Error = BinaryError;
+
+    fn unit_variant(self) -> Result<(), BinaryError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, BinaryError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BinaryError> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+}
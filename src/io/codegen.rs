@@ -0,0 +1,192 @@
+//! Generates dependency-free Rust or C source for a `LinearExport`: a single
+//! `predict(features)` function with every constant inlined, for embedding
+//! in agents that cannot link this crate.
+
+use crate::io::export::LinearExport;
+use std::error::Error;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    C,
+}
+
+impl Language {
+    pub fn from_name(name: &str) -> Option<Language> {
+        match name {
+            "rust" | "rs" => Some(Language::Rust),
+            "c" => Some(Language::C),
+            _ => None,
+        }
+    }
+}
+
+/// A float literal that parses back to exactly `value`.
+fn literal(value: f64, language: Language) -> String {
+    match (language, value) {
+        (_, value) if value.is_finite() => format!("{value:?}"),
+        (Language::Rust, value) if value.is_nan() => "f64::NAN".to_string(),
+        (Language::Rust, value) if value > 0.0 => "f64::INFINITY".to_string(),
+        (Language::Rust, _) => "f64::NEG_INFINITY".to_string(),
+        (Language::C, value) if value.is_nan() => "NAN".to_string(),
+        (Language::C, value) if value > 0.0 => "INFINITY".to_string(),
+        (Language::C, _) => "-INFINITY".to_string(),
+    }
+}
+
+fn array(values: &[f64], language: Language) -> String {
+    let items: Vec<String> = values
+        .iter()
+        .map(|value| literal(*value, language))
+        .collect();
+    items.join(", ")
+}
+
+/// Per-feature constant arrays in the order they are applied.
+fn constants(export: &LinearExport) -> Vec<(&'static str, &[f64])> {
+    let mut constants = Vec::new();
+    if let Some((lower, upper)) = &export.clip {
+        constants.push(("CLIP_LOWER", lower.as_slice()));
+        constants.push(("CLIP_UPPER", upper.as_slice()));
+    }
+    if let Some((mean, std_dev)) = &export.scaling {
+        constants.push(("MEAN", mean.as_slice()));
+        constants.push(("STD_DEV", std_dev.as_slice()));
+    }
+    constants.push(("WEIGHTS", export.weights.as_slice()));
+    constants
+}
+
+fn header(export: &LinearExport, source: &mut String) {
+    let kind = if export.logistic {
+        "logistic regression (returns a probability)"
+    } else {
+        "linear regression"
+    };
+    let _ = writeln!(
+        source,
+        "// Generated by rustml {}: {kind} over {} features.",
+        env!("CARGO_PKG_VERSION"),
+        export.num_features()
+    );
+    let _ = writeln!(source, "// Feature order:");
+    for (idx, name) in export.feature_names.iter().enumerate() {
+        let _ = writeln!(source, "//   [{idx}] {name:?}");
+    }
+    let _ = writeln!(source);
+}
+
+pub fn to_rust(export: &LinearExport) -> String {
+    let lang = Language::Rust;
+    let count = export.num_features();
+    let mut source = String::new();
+    header(export, &mut source);
+    let _ = writeln!(source, "pub const NUM_FEATURES: usize = {count};");
+    for (name, values) in constants(export) {
+        let _ = writeln!(
+            source,
+            "const {name}: [f64; NUM_FEATURES] = [{}];",
+            array(values, lang)
+        );
+    }
+    let _ = writeln!(source, "const BIAS: f64 = {};", literal(export.bias, lang));
+    let _ = writeln!(source);
+    let _ = writeln!(
+        source,
+        "pub fn predict(features: &[f64; NUM_FEATURES]) -> f64 {{"
+    );
+    let _ = writeln!(source, "    let mut linear = 0.0;");
+    let _ = writeln!(source, "    for i in 0..NUM_FEATURES {{");
+    let binding = if export.clip.is_some() || export.scaling.is_some() {
+        "let mut"
+    } else {
+        "let"
+    };
+    let _ = writeln!(source, "        {binding} value = features[i];");
+    if export.clip.is_some() {
+        let _ = writeln!(
+            source,
+            "        value = value.max(CLIP_LOWER[i]).min(CLIP_UPPER[i]);"
+        );
+    }
+    if export.scaling.is_some() {
+        let _ = writeln!(source, "        value = (value - MEAN[i]) / STD_DEV[i];");
+    }
+    let _ = writeln!(source, "        linear += value * WEIGHTS[i];");
+    let _ = writeln!(source, "    }}");
+    if export.logistic {
+        let _ = writeln!(source, "    let score = linear + BIAS;");
+        let _ = writeln!(source, "    1.0 / (1.0 + (-score).exp())");
+    } else {
+        let _ = writeln!(source, "    linear + BIAS");
+    }
+    let _ = writeln!(source, "}}");
+    source
+}
+
+pub fn to_c(export: &LinearExport) -> String {
+    let lang = Language::C;
+    let count = export.num_features();
+    let mut source = String::new();
+    header(export, &mut source);
+    let _ = writeln!(source, "#include <math.h>");
+    let _ = writeln!(source);
+    let _ = writeln!(source, "#define NUM_FEATURES {count}");
+    let _ = writeln!(source);
+    for (name, values) in constants(export) {
+        let _ = writeln!(
+            source,
+            "static const double {name}[NUM_FEATURES] = {{{}}};",
+            array(values, lang)
+        );
+    }
+    let _ = writeln!(
+        source,
+        "static const double BIAS = {};",
+        literal(export.bias, lang)
+    );
+    let _ = writeln!(source);
+    let _ = writeln!(
+        source,
+        "double predict(const double features[NUM_FEATURES]) {{"
+    );
+    let _ = writeln!(source, "    double linear = 0.0;");
+    let _ = writeln!(source, "    for (int i = 0; i < NUM_FEATURES; i++) {{");
+    let _ = writeln!(source, "        double value = features[i];");
+    if export.clip.is_some() {
+        let _ = writeln!(
+            source,
+            "        value = fmin(fmax(value, CLIP_LOWER[i]), CLIP_UPPER[i]);"
+        );
+    }
+    if export.scaling.is_some() {
+        let _ = writeln!(source, "        value = (value - MEAN[i]) / STD_DEV[i];");
+    }
+    let _ = writeln!(source, "        linear += value * WEIGHTS[i];");
+    let _ = writeln!(source, "    }}");
+    if export.logistic {
+        let _ = writeln!(source, "    double score = linear + BIAS;");
+        let _ = writeln!(source, "    return 1.0 / (1.0 + exp(-score));");
+    } else {
+        let _ = writeln!(source, "    return linear + BIAS;");
+    }
+    let _ = writeln!(source, "}}");
+    source
+}
+
+pub fn generate(export: &LinearExport, language: Language) -> String {
+    match language {
+        Language::Rust => to_rust(export),
+        Language::C => to_c(export),
+    }
+}
+
+pub fn save_source(
+    path: &str,
+    export: &LinearExport,
+    language: Language,
+) -> Result<(), Box<dyn Error>> {
+    std::fs::write(path, generate(export, language))?;
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use crate::io::ModelArtifact;
+use crate::ml::{Estimator, LinearModel};
+use crate::pipeline::{PipelineStep, TransformStep};
+use crate::utils::normalization::{NormalizationStats, Scaler};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// A preprocessing step with no equivalent in the export formats.
+    UnsupportedStep { name: String, kind: String },
+    LengthMismatch {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// An exported file that the in-crate interpreters cannot read back.
+    Malformed(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::UnsupportedStep { name, kind } => write!(
+                f,
+                "preprocessing step '{name}' ({kind}) cannot be exported; only outlier clipping and z-score scaling are supported"
+            ),
+            ExportError::LengthMismatch {
+                what,
+                expected,
+                found,
+            } => write!(f, "{what} has {found} values, expected {expected}"),
+            ExportError::Malformed(message) => write!(f, "malformed export: {message}"),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+/// A linear or logistic model reduced to the constants every exporter needs:
+/// optional per-feature clipping, then optional z-scoring, then `w·x + b`,
+/// passed through a sigmoid for logistic models.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearExport {
+    pub feature_names: Vec<String>,
+    pub target_name: String,
+    pub weights: Vec<f64>,
+    pub bias: f64,
+    pub logistic: bool,
+    /// Per-feature `(lower, upper)` bounds.
+    pub clip: Option<(Vec<f64>, Vec<f64>)>,
+    /// Per-feature `(mean, std_dev)`.
+    pub scaling: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl LinearExport {
+    pub fn new(
+        model: &Estimator,
+        feature_names: &[String],
+        target_name: &str,
+    ) -> Result<Self, ExportError> {
+        let (weights, bias, logistic) = match model {
+            Estimator::LinearRegression(model) => (model.weights.clone(), model.bias, false),
+            Estimator::LogisticRegression(model) => (model.weights.clone(), model.bias, true),
+        };
+        check_len("feature names", weights.len(), feature_names.len())?;
+        Ok(LinearExport {
+            feature_names: feature_names.to_vec(),
+            target_name: target_name.to_string(),
+            weights,
+            bias,
+            logistic,
+            clip: None,
+            scaling: None,
+        })
+    }
+
+    pub fn with_normalization(self, stats: &NormalizationStats) -> Result<Self, ExportError> {
+        self.with_scaling(stats.mean.clone(), stats.std_dev.clone())
+    }
+
+    pub fn with_scaling(mut self, mean: Vec<f64>, std_dev: Vec<f64>) -> Result<Self, ExportError> {
+        check_len("mean", self.weights.len(), mean.len())?;
+        check_len("std_dev", self.weights.len(), std_dev.len())?;
+        self.scaling = Some((mean, std_dev));
+        Ok(self)
+    }
+
+    pub fn with_clipping(mut self, lower: Vec<f64>, upper: Vec<f64>) -> Result<Self, ExportError> {
+        check_len("lower bounds", self.weights.len(), lower.len())?;
+        check_len("upper bounds", self.weights.len(), upper.len())?;
+        self.clip = Some((lower, upper));
+        Ok(self)
+    }
+
+    /// Model plus fitted steps, which may be an outlier clipper followed by a
+    /// standard scaler (either one optional).
+    pub fn from_steps(
+        model: &Estimator,
+        steps: &[PipelineStep],
+        feature_names: &[String],
+        target_name: &str,
+    ) -> Result<Self, ExportError> {
+        let mut clip = None;
+        let mut scaling = None;
+        for (position, step) in steps.iter().enumerate() {
+            match &step.step {
+                TransformStep::ClipOutliers(clipper) if position == 0 => {
+                    clip = Some((clipper.lower.clone(), clipper.upper.clone()))
+                }
+                TransformStep::Scale(Scaler::Standard(scaler)) if scaling.is_none() => {
+                    scaling = Some((scaler.mean.clone(), scaler.std_dev.clone()))
+                }
+                other => {
+                    return Err(ExportError::UnsupportedStep {
+                        name: step.name.clone(),
+                        kind: step_kind(other).to_string(),
+                    })
+                }
+            }
+        }
+        let mut export = LinearExport::new(model, feature_names, target_name)?;
+        if let Some((lower, upper)) = clip {
+            export = export.with_clipping(lower, upper)?;
+        }
+        if let Some((mean, std_dev)) = scaling {
+            export = export.with_scaling(mean, std_dev)?;
+        }
+        Ok(export)
+    }
+
+    pub fn from_artifact(artifact: &ModelArtifact) -> Result<Self, ExportError> {
+        let names = if artifact.feature_names.is_empty() {
+            default_names(&artifact.model)
+        } else {
+            artifact.feature_names.clone()
+        };
+        LinearExport::from_steps(
+            &artifact.model,
+            &artifact.preprocessing,
+            &names,
+            &artifact.target_name,
+        )
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Reference evaluation of one row, in the same order of operations as
+    /// `Model::predict` behind the same preprocessing.
+    pub fn predict_row(&self, row: &[f64]) -> f64 {
+        let mut linear = 0.0;
+        for (idx, weight) in self.weights.iter().enumerate() {
+            let mut value = row[idx];
+            if let Some((lower, upper)) = &self.clip {
+                value = value.max(lower[idx]).min(upper[idx]);
+            }
+            if let Some((mean, std_dev)) = &self.scaling {
+                value = (value - mean[idx]) / std_dev[idx];
+            }
+            linear += value * weight;
+        }
+        let score = linear + self.bias;
+        if self.logistic {
+            sigmoid(score)
+        } else {
+            score
+        }
+    }
+}
+
+pub(crate) fn sigmoid(value: f64) -> f64 {
+    1.0 / (1.0 + (-value).exp())
+}
+
+fn check_len(what: &'static str, expected: usize, found: usize) -> Result<(), ExportError> {
+    if expected != found {
+        return Err(ExportError::LengthMismatch {
+            what,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
+
+/// Names for artifacts migrated from the unversioned format, which did not
+/// record their input columns.
+fn default_names(model: &Estimator) -> Vec<String> {
+    (0..model.weights().len())
+        .map(|idx| format!("x{idx}"))
+        .collect()
+}
+
+fn step_kind(step: &TransformStep) -> &'static str {
+    match step {
+        TransformStep::ClipOutliers(_) => "clip_outliers",
+        TransformStep::Scale(_) => "scale",
+        TransformStep::Log(_) => "log",
+        TransformStep::Power(_) => "power",
+        TransformStep::Discretize(_) => "discretize",
+        TransformStep::Spline(_) => "spline",
+        TransformStep::Derived(_) => "derived",
+        TransformStep::Polynomial(_) => "polynomial",
+        TransformStep::Select(_) => "select",
+    }
+}
@@ -0,0 +1,397 @@
+pub mod binary;
+pub mod codegen;
+pub mod export;
+pub mod onnx;
+pub mod pmml;
+
+use crate::data::dataset::Dataset;
+use crate::data::{TransformError, Transformer};
+use crate::drift::{ReferenceProfile, DEFAULT_BINS};
+use crate::ml::linear_regression::LinearRegression;
+use crate::ml::{Estimator, LinearModel, Model};
+use crate::pipeline::{Pipeline, PipelineStep, TrainingSummary};
+use crate::utils::hashing::hash_bytes;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the artifact layout written by `save_model`. Version 1 is the
+/// unversioned `{model, summary, preprocessing}` format, which always held a
+/// `LinearRegression`; version 2 is the hashed envelope with the model kind,
+/// feature schema and drift reference profile.
+pub const ARTIFACT_SCHEMA_VERSION: u32 = 2;
+
+/// One way a dataset's columns differ from the schema an artifact was trained on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeatureMismatch {
+    Count {
+        expected: usize,
+        found: usize,
+    },
+    Missing {
+        name: String,
+    },
+    Unexpected {
+        name: String,
+    },
+    OutOfOrder {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for FeatureMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatureMismatch::Count { expected, found } => {
+                write!(f, "expected {expected} features, found {found}")
+            }
+            FeatureMismatch::Missing { name } => write!(f, "missing feature '{name}'"),
+            FeatureMismatch::Unexpected { name } => write!(f, "unexpected feature '{name}'"),
+            FeatureMismatch::OutOfOrder {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "feature '{name}' is at position {found}, expected {expected}"
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ArtifactError {
+    UnsupportedVersion { found: u32, supported: u32 },
+    Malformed { version: u32, message: String },
+    HashMismatch { expected: String, found: String },
+    SchemaMismatch(Vec<FeatureMismatch>),
+    Transform(TransformError),
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArtifactError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "artifact schema version {found} is newer than the supported version {supported}"
+            ),
+            ArtifactError::Malformed { version, message } => {
+                write!(f, "malformed version {version} artifact: {message}")
+            }
+            ArtifactError::HashMismatch { expected, found } => write!(
+                f,
+                "artifact content hash mismatch (recorded {expected}, computed {found}); the file is corrupted or was edited"
+            ),
+            ArtifactError::SchemaMismatch(mismatches) => {
+                write!(f, "dataset does not match the model's feature schema:")?;
+                for mismatch in mismatches {
+                    write!(f, "\n  - {mismatch}")?;
+                }
+                Ok(())
+            }
+            ArtifactError::Transform(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for ArtifactError {}
+
+impl From<TransformError> for ArtifactError {
+    fn from(err: TransformError) -> Self {
+        ArtifactError::Transform(err)
+    }
+}
+
+/// Hyperparameters the model was trained with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingConfig {
+    pub learning_rate: f64,
+    pub epochs: usize,
+    pub drop_incomplete_rows: bool,
+}
+
+impl From<&Pipeline> for TrainingConfig {
+    fn from(pipeline: &Pipeline) -> Self {
+        let (learning_rate, epochs) = match &pipeline.model {
+            Estimator::LinearRegression(model) => (model.learning_rate, model.epochs),
+            Estimator::LogisticRegression(model) => (model.learning_rate, model.epochs),
+        };
+        TrainingConfig {
+            learning_rate,
+            epochs,
+            drop_incomplete_rows: pipeline.drop_incomplete_rows,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelArtifact {
+    pub schema_version: u32,
+    pub model: Estimator,
+    /// Input columns, in order, the artifact expects before preprocessing.
+    pub feature_names: Vec<String>,
+    pub target_name: String,
+    /// Fitted preprocessing the model was trained behind, replayed in order
+    /// by `predict`.
+    #[serde(default)]
+    pub preprocessing: Vec<PipelineStep>,
+    pub training: TrainingConfig,
+    pub summary: TrainingSummary,
+    pub crate_version: String,
+    /// Unix seconds.
+    pub created_at: u64,
+    /// Training-data distributions for drift monitoring.
+    pub reference: Option<ReferenceProfile>,
+    /// Hash of every other field, set on save and verified on load.
+    pub content_hash: String,
+}
+
+/// The unversioned artifact layout, kept for migration.
+#[derive(Deserialize)]
+struct ArtifactV1 {
+    model: LinearRegression,
+    summary: TrainingSummary,
+    #[serde(default)]
+    preprocessing: Vec<PipelineStep>,
+}
+
+impl From<ArtifactV1> for ModelArtifact {
+    fn from(legacy: ArtifactV1) -> Self {
+        let training = TrainingConfig {
+            learning_rate: legacy.model.learning_rate,
+            epochs: legacy.model.epochs,
+            drop_incomplete_rows: true,
+        };
+        ModelArtifact {
+            schema_version: ARTIFACT_SCHEMA_VERSION,
+            model: legacy.model.into(),
+            feature_names: Vec::new(),
+            target_name: String::new(),
+            preprocessing: legacy.preprocessing,
+            training,
+            summary: legacy.summary,
+            crate_version: String::new(),
+            created_at: 0,
+            reference: None,
+            content_hash: String::new(),
+        }
+    }
+}
+
+impl ModelArtifact {
+    /// Packages a fitted pipeline trained on `dataset`, with a drift
+    /// reference profile of its inputs and predictions. Fails if the pipeline
+    /// cannot predict on `dataset`.
+    pub fn from_pipeline(
+        pipeline: &Pipeline,
+        dataset: &Dataset,
+        summary: TrainingSummary,
+    ) -> Result<Self, TransformError> {
+        let predictions = pipeline.predict(dataset)?;
+        let mut artifact = ModelArtifact {
+            schema_version: ARTIFACT_SCHEMA_VERSION,
+            model: pipeline.model.clone(),
+            feature_names: dataset.feature_names.clone(),
+            target_name: dataset.target_name.clone(),
+            preprocessing: pipeline.steps.clone(),
+            training: TrainingConfig::from(pipeline),
+            summary,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0),
+            reference: Some(ReferenceProfile::new(dataset, &predictions, DEFAULT_BINS)),
+            content_hash: String::new(),
+        };
+        artifact.seal();
+        Ok(artifact)
+    }
+
+    /// Hash over the binary encoding of every field except `content_hash`.
+    pub fn compute_hash(&self) -> String {
+        let unsealed = ModelArtifact {
+            content_hash: String::new(),
+            ..self.clone()
+        };
+        let bytes = binary::to_bytes(&unsealed).expect("artifacts always encode");
+        hash_bytes(&bytes)
+    }
+
+    pub fn seal(&mut self) {
+        self.content_hash = self.compute_hash();
+    }
+
+    pub fn verify_hash(&self) -> Result<(), ArtifactError> {
+        let found = self.compute_hash();
+        if found != self.content_hash {
+            return Err(ArtifactError::HashMismatch {
+                expected: self.content_hash.clone(),
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    /// Finishes loading a decoded artifact, which must match its hash.
+    fn verify_loaded(self) -> Result<ModelArtifact, ArtifactError> {
+        match self.schema_version {
+            ARTIFACT_SCHEMA_VERSION => {
+                self.verify_hash()?;
+                Ok(self)
+            }
+            0 | 1 => Err(ArtifactError::Malformed {
+                version: self.schema_version,
+                message: "schema_version does not describe a hashed artifact".to_string(),
+            }),
+            found => Err(ArtifactError::UnsupportedVersion {
+                found,
+                supported: ARTIFACT_SCHEMA_VERSION,
+            }),
+        }
+    }
+
+    /// Compares a dataset's columns against the training schema. Artifacts
+    /// migrated from version 1 recorded no names and are not checked.
+    pub fn check_features(&self, dataset: &Dataset) -> Result<(), ArtifactError> {
+        if self.feature_names.is_empty() || dataset.feature_names == self.feature_names {
+            return Ok(());
+        }
+        let mut mismatches = Vec::new();
+        if dataset.num_features() != self.feature_names.len() {
+            mismatches.push(FeatureMismatch::Count {
+                expected: self.feature_names.len(),
+                found: dataset.num_features(),
+            });
+        }
+        for (expected, name) in self.feature_names.iter().enumerate() {
+            match dataset.feature_index(name) {
+                None => mismatches.push(FeatureMismatch::Missing { name: name.clone() }),
+                Some(found) if found != expected => mismatches.push(FeatureMismatch::OutOfOrder {
+                    name: name.clone(),
+                    expected,
+                    found,
+                }),
+                Some(_) => {}
+            }
+        }
+        for name in &dataset.feature_names {
+            if !self.feature_names.contains(name) {
+                mismatches.push(FeatureMismatch::Unexpected { name: name.clone() });
+            }
+        }
+        Err(ArtifactError::SchemaMismatch(mismatches))
+    }
+
+    /// Upgrades a parsed artifact of any supported version to the current layout.
+    pub fn migrate(value: serde_json::Value) -> Result<ModelArtifact, ArtifactError> {
+        let version = match value.get("schema_version") {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .filter(|version| *version > 0)
+                .ok_or_else(|| ArtifactError::Malformed {
+                    version: 0,
+                    message: format!("schema_version must be a positive integer, got {version}"),
+                })?,
+        };
+        let malformed = |err: serde_json::Error| ArtifactError::Malformed {
+            version,
+            message: err.to_string(),
+        };
+        match version {
+            1 => {
+                let mut artifact: ModelArtifact = serde_json::from_value::<ArtifactV1>(value)
+                    .map_err(malformed)?
+                    .into();
+                artifact.seal();
+                Ok(artifact)
+            }
+            ARTIFACT_SCHEMA_VERSION => serde_json::from_value::<ModelArtifact>(value)
+                .map_err(malformed)?
+                .verify_loaded(),
+            found => Err(ArtifactError::UnsupportedVersion {
+                found,
+                supported: ARTIFACT_SCHEMA_VERSION,
+            }),
+        }
+    }
+
+    pub fn preprocess(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let mut current = dataset.clone();
+        for step in &self.preprocessing {
+            current = step.step.transform(&current)?;
+        }
+        Ok(current)
+    }
+
+    pub fn predict(&self, dataset: &Dataset) -> Result<Vec<f64>, ArtifactError> {
+        self.check_features(dataset)?;
+        let features = self.preprocess(dataset)?;
+        let expected = self.model.weights().len();
+        if features.num_features() != expected {
+            return Err(ArtifactError::SchemaMismatch(vec![
+                FeatureMismatch::Count {
+                    expected,
+                    found: features.num_features(),
+                },
+            ]));
+        }
+        Ok(self.model.predict(&features))
+    }
+}
+
+/// On-disk encoding of a `ModelArtifact`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactFormat {
+    /// Pretty-printed JSON.
+    Json,
+    /// The compact encoding from `io::binary`, with exact float round-trips.
+    Binary,
+}
+
+impl ArtifactFormat {
+    pub fn from_name(name: &str) -> Option<ArtifactFormat> {
+        match name {
+            "json" => Some(ArtifactFormat::Json),
+            "binary" | "bin" => Some(ArtifactFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
+pub fn save_model(path: &str, artifact: &ModelArtifact) -> Result<(), Box<dyn Error>> {
+    save_model_as(path, artifact, ArtifactFormat::Json)
+}
+
+pub fn save_model_as(
+    path: &str,
+    artifact: &ModelArtifact,
+    format: ArtifactFormat,
+) -> Result<(), Box<dyn Error>> {
+    let mut artifact = artifact.clone();
+    artifact.seal();
+    let content = match format {
+        ArtifactFormat::Json => serde_json::to_string_pretty(&artifact)?.into_bytes(),
+        ArtifactFormat::Binary => binary::to_bytes(&artifact)?,
+    };
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Loads a JSON or binary artifact of any supported schema version,
+/// dispatching on the model kind it records.
+pub fn load_model(path: &str) -> Result<ModelArtifact, Box<dyn Error>> {
+    let content = std::fs::read(path)?;
+    if binary::is_binary(&content) {
+        let artifact: ModelArtifact = binary::from_bytes(&content)?;
+        return Ok(artifact.verify_loaded()?);
+    }
+    let value: serde_json::Value = serde_json::from_slice(&content)?;
+    Ok(ModelArtifact::migrate(value)?)
+}
@@ -0,0 +1,418 @@
+//! ONNX export for linear and logistic models.
+//!
+//! The graph takes a `double` tensor `input` of shape `[batch, features]` and
+//! yields `output` of shape `[batch, 1]`:
+//! `Max`/`Min` clipping, `Sub`/`Div` z-scoring, `MatMul` + `Add`, then
+//! `Sigmoid` for logistic models. Protobuf is encoded by hand, and
+//! `evaluate` runs the same graph with a small interpreter so exports can be
+//! checked against `Model::predict` without an ONNX runtime.
+
+use crate::io::export::{sigmoid, ExportError, LinearExport};
+use std::collections::HashMap;
+use std::error::Error;
+
+pub const IR_VERSION: u64 = 7;
+pub const OPSET_VERSION: u64 = 13;
+const DOUBLE: u64 = 11;
+
+const INPUT: &str = "input";
+const OUTPUT: &str = "output";
+
+/// Serializes `export` as an ONNX `ModelProto`.
+pub fn to_onnx(export: &LinearExport) -> Vec<u8> {
+    let features = export.num_features() as u64;
+    let mut initializers = Vec::new();
+    let mut nodes = Vec::new();
+    let mut current = INPUT.to_string();
+    let mut apply = |op: &str, operand: Option<(&str, Vec<u64>, &[f64])>, output: &str| {
+        let mut inputs = vec![current.clone()];
+        if let Some((name, dims, values)) = operand {
+            initializers.push(tensor(name, &dims, values));
+            inputs.push(name.to_string());
+        }
+        nodes.push(node(op, &inputs, output));
+        current = output.to_string();
+    };
+    if let Some((lower, upper)) = &export.clip {
+        apply(
+            "Max",
+            Some(("clip_lower", vec![features], lower)),
+            "clipped_low",
+        );
+        apply(
+            "Min",
+            Some(("clip_upper", vec![features], upper)),
+            "clipped",
+        );
+    }
+    if let Some((mean, std_dev)) = &export.scaling {
+        apply("Sub", Some(("mean", vec![features], mean)), "centered");
+        apply("Div", Some(("std_dev", vec![features], std_dev)), "scaled");
+    }
+    apply(
+        "MatMul",
+        Some(("weights", vec![features, 1], &export.weights)),
+        "linear",
+    );
+    if export.logistic {
+        apply("Add", Some(("bias", vec![1], &[export.bias])), "score");
+        apply("Sigmoid", None, OUTPUT);
+    } else {
+        apply("Add", Some(("bias", vec![1], &[export.bias])), OUTPUT);
+    }
+
+    let mut graph = Vec::new();
+    for encoded in &nodes {
+        put_bytes(&mut graph, 1, encoded);
+    }
+    put_str(&mut graph, 2, "rustml");
+    for encoded in &initializers {
+        put_bytes(&mut graph, 5, encoded);
+    }
+    put_bytes(&mut graph, 11, &value_info(INPUT, features));
+    put_bytes(&mut graph, 12, &value_info(OUTPUT, 1));
+
+    let mut opset = Vec::new();
+    put_str(&mut opset, 1, "");
+    put_varint_field(&mut opset, 2, OPSET_VERSION);
+
+    let mut model = Vec::new();
+    put_varint_field(&mut model, 1, IR_VERSION);
+    put_str(&mut model, 2, "rustml");
+    put_str(&mut model, 3, env!("CARGO_PKG_VERSION"));
+    put_bytes(&mut model, 7, &graph);
+    put_bytes(&mut model, 8, &opset);
+    let names = serde_json::to_string(&export.feature_names).expect("names always serialize");
+    put_bytes(&mut model, 14, &string_entry("feature_names", &names));
+    put_bytes(
+        &mut model,
+        14,
+        &string_entry("target_name", &export.target_name),
+    );
+    model
+}
+
+pub fn save_onnx(path: &str, export: &LinearExport) -> Result<(), Box<dyn Error>> {
+    std::fs::write(path, to_onnx(export))?;
+    Ok(())
+}
+
+fn put_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn put_varint_field(buffer: &mut Vec<u8>, field: u64, value: u64) {
+    put_varint(buffer, field << 3);
+    put_varint(buffer, value);
+}
+
+fn put_bytes(buffer: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    put_varint(buffer, (field << 3) | 2);
+    put_varint(buffer, bytes.len() as u64);
+    buffer.extend_from_slice(bytes);
+}
+
+fn put_str(buffer: &mut Vec<u8>, field: u64, value: &str) {
+    put_bytes(buffer, field, value.as_bytes());
+}
+
+fn tensor(name: &str, dims: &[u64], values: &[f64]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    for dim in dims {
+        put_varint_field(&mut encoded, 1, *dim);
+    }
+    put_varint_field(&mut encoded, 2, DOUBLE);
+    put_str(&mut encoded, 8, name);
+    let raw: Vec<u8> = values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    put_bytes(&mut encoded, 9, &raw);
+    encoded
+}
+
+fn node(op: &str, inputs: &[String], output: &str) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    for input in inputs {
+        put_str(&mut encoded, 1, input);
+    }
+    put_str(&mut encoded, 2, output);
+    put_str(&mut encoded, 3, output);
+    put_str(&mut encoded, 4, op);
+    encoded
+}
+
+/// A `[batch, width]` double tensor description.
+fn value_info(name: &str, width: u64) -> Vec<u8> {
+    let mut batch = Vec::new();
+    put_str(&mut batch, 2, "batch");
+    let mut dim = Vec::new();
+    put_varint_field(&mut dim, 1, width);
+    let mut shape = Vec::new();
+    put_bytes(&mut shape, 1, &batch);
+    put_bytes(&mut shape, 1, &dim);
+    let mut tensor_type = Vec::new();
+    put_varint_field(&mut tensor_type, 1, DOUBLE);
+    put_bytes(&mut tensor_type, 2, &shape);
+    let mut type_proto = Vec::new();
+    put_bytes(&mut type_proto, 1, &tensor_type);
+    let mut encoded = Vec::new();
+    put_str(&mut encoded, 1, name);
+    put_bytes(&mut encoded, 2, &type_proto);
+    encoded
+}
+
+fn string_entry(key: &str, value: &str) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    put_str(&mut encoded, 1, key);
+    put_str(&mut encoded, 2, value);
+    encoded
+}
+
+enum Field<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+fn malformed(message: impl Into<String>) -> ExportError {
+    ExportError::Malformed(message.into())
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64, ExportError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes
+            .get(*pos)
+            .ok_or_else(|| malformed("truncated varint"))?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(malformed("varint is too long"))
+}
+
+/// Splits a protobuf message into `(field number, value)` pairs.
+fn fields(bytes: &[u8]) -> Result<Vec<(u64, Field<'_>)>, ExportError> {
+    let mut pos = 0;
+    let mut fields = Vec::new();
+    while pos < bytes.len() {
+        let key = read_varint(bytes, &mut pos)?;
+        let value = match key & 7 {
+            0 => Field::Varint(read_varint(bytes, &mut pos)?),
+            1 | 5 => {
+                let width = if key & 7 == 1 { 8 } else { 4 };
+                pos += width;
+                if pos > bytes.len() {
+                    return Err(malformed("truncated fixed-width field"));
+                }
+                continue;
+            }
+            2 => {
+                let len = read_varint(bytes, &mut pos)? as usize;
+                let end = pos
+                    .checked_add(len)
+                    .filter(|end| *end <= bytes.len())
+                    .ok_or_else(|| malformed("truncated length-delimited field"))?;
+                let slice = &bytes[pos..end];
+                pos = end;
+                Field::Bytes(slice)
+            }
+            wire => return Err(malformed(format!("unsupported wire type {wire}"))),
+        };
+        fields.push((key >> 3, value));
+    }
+    Ok(fields)
+}
+
+fn text(bytes: &[u8]) -> Result<String, ExportError> {
+    String::from_utf8(bytes.to_vec()).map_err(|_| malformed("string is not UTF-8"))
+}
+
+#[derive(Debug, Clone)]
+struct Tensor {
+    dims: Vec<usize>,
+    data: Vec<f64>,
+}
+
+impl Tensor {
+    fn decode(bytes: &[u8]) -> Result<(String, Tensor), ExportError> {
+        let mut name = String::new();
+        let mut dims = Vec::new();
+        let mut data = Vec::new();
+        for (field, value) in fields(bytes)? {
+            match (field, value) {
+                (1, Field::Varint(dim)) => dims.push(dim as usize),
+                (2, Field::Varint(kind)) if kind != DOUBLE => {
+                    return Err(malformed(format!("tensor data type {kind} is not double")))
+                }
+                (8, Field::Bytes(raw)) => name = text(raw)?,
+                (9, Field::Bytes(raw)) => {
+                    data = raw
+                        .chunks_exact(8)
+                        .map(|chunk| {
+                            let mut bits = [0u8; 8];
+                            bits.copy_from_slice(chunk);
+                            f64::from_le_bytes(bits)
+                        })
+                        .collect()
+                }
+                _ => {}
+            }
+        }
+        if dims.iter().product::<usize>() != data.len() {
+            return Err(malformed(format!(
+                "tensor '{name}' size does not match its dims"
+            )));
+        }
+        Ok((name, Tensor { dims, data }))
+    }
+
+    fn broadcast(
+        &self,
+        other: &Tensor,
+        op: impl Fn(f64, f64) -> f64,
+    ) -> Result<Tensor, ExportError> {
+        let rank = self.dims.len().max(other.dims.len());
+        let padded = |dims: &[usize]| {
+            let mut full = vec![1; rank - dims.len()];
+            full.extend_from_slice(dims);
+            full
+        };
+        let (left, right) = (padded(&self.dims), padded(&other.dims));
+        let mut dims = Vec::with_capacity(rank);
+        for (a, b) in left.iter().zip(&right) {
+            dims.push(match (a, b) {
+                (a, b) if a == b => *a,
+                (1, b) => *b,
+                (a, 1) => *a,
+                _ => return Err(malformed("operands cannot be broadcast together")),
+            });
+        }
+        let size: usize = dims.iter().product();
+        let offset = |shape: &[usize], mut flat: usize| {
+            let mut index = 0;
+            let mut stride = 1;
+            for axis in (0..rank).rev() {
+                let coordinate = flat % dims[axis];
+                flat /= dims[axis];
+                if shape[axis] != 1 {
+                    index += coordinate * stride;
+                }
+                stride *= shape[axis];
+            }
+            index
+        };
+        let data = (0..size)
+            .map(|flat| {
+                op(
+                    self.data[offset(&left, flat)],
+                    other.data[offset(&right, flat)],
+                )
+            })
+            .collect();
+        Ok(Tensor { dims, data })
+    }
+
+    fn matmul(&self, other: &Tensor) -> Result<Tensor, ExportError> {
+        match (self.dims.as_slice(), other.dims.as_slice()) {
+            ([rows, inner], [inner_other, cols]) if inner == inner_other => {
+                let mut data = vec![0.0; rows * cols];
+                for row in 0..*rows {
+                    for col in 0..*cols {
+                        let mut sum = 0.0;
+                        for k in 0..*inner {
+                            sum += self.data[row * inner + k] * other.data[k * cols + col];
+                        }
+                        data[row * cols + col] = sum;
+                    }
+                }
+                Ok(Tensor {
+                    dims: vec![*rows, *cols],
+                    data,
+                })
+            }
+            _ => Err(malformed("MatMul needs [n, k] x [k, m] operands")),
+        }
+    }
+}
+
+/// Runs an exported graph on `rows`, returning one output per row.
+pub fn evaluate(model: &[u8], rows: &[Vec<f64>]) -> Result<Vec<f64>, ExportError> {
+    let mut graph = None;
+    for (field, value) in fields(model)? {
+        if let (7, Field::Bytes(bytes)) = (field, value) {
+            graph = Some(bytes);
+        }
+    }
+    let graph = graph.ok_or_else(|| malformed("model has no graph"))?;
+    let mut values: HashMap<String, Tensor> = HashMap::new();
+    let mut nodes = Vec::new();
+    for (field, value) in fields(graph)? {
+        match (field, value) {
+            (1, Field::Bytes(bytes)) => nodes.push(bytes),
+            (5, Field::Bytes(bytes)) => {
+                let (name, tensor) = Tensor::decode(bytes)?;
+                values.insert(name, tensor);
+            }
+            _ => {}
+        }
+    }
+    let width = rows.first().map_or(0, |row| row.len());
+    if rows.iter().any(|row| row.len() != width) {
+        return Err(malformed("input rows have different lengths"));
+    }
+    values.insert(
+        INPUT.to_string(),
+        Tensor {
+            dims: vec![rows.len(), width],
+            data: rows.iter().flatten().cloned().collect(),
+        },
+    );
+
+    for bytes in nodes {
+        let mut inputs = Vec::new();
+        let mut output = String::new();
+        let mut op = String::new();
+        for (field, value) in fields(bytes)? {
+            match (field, value) {
+                (1, Field::Bytes(name)) => inputs.push(text(name)?),
+                (2, Field::Bytes(name)) => output = text(name)?,
+                (4, Field::Bytes(name)) => op = text(name)?,
+                _ => {}
+            }
+        }
+        let operand = |idx: usize| {
+            inputs
+                .get(idx)
+                .and_then(|name| values.get(name))
+                .ok_or_else(|| malformed(format!("{op} is missing input {idx}")))
+        };
+        let result = match op.as_str() {
+            "Max" => operand(0)?.broadcast(operand(1)?, f64::max)?,
+            "Min" => operand(0)?.broadcast(operand(1)?, f64::min)?,
+            "Sub" => operand(0)?.broadcast(operand(1)?, |a, b| a - b)?,
+            "Div" => operand(0)?.broadcast(operand(1)?, |a, b| a / b)?,
+            "Add" => operand(0)?.broadcast(operand(1)?, |a, b| a + b)?,
+            "MatMul" => operand(0)?.matmul(operand(1)?)?,
+            "Sigmoid" => {
+                let input = operand(0)?;
+                Tensor {
+                    dims: input.dims.clone(),
+                    data: input.data.iter().map(|value| sigmoid(*value)).collect(),
+                }
+            }
+            other => return Err(malformed(format!("unsupported operator {other}"))),
+        };
+        values.insert(output, result);
+    }
+    values
+        .remove(OUTPUT)
+        .map(|tensor| tensor.data)
+        .ok_or_else(|| malformed("graph does not produce an output"))
+}
@@ -0,0 +1,421 @@
+//! PMML 4.4 export for linear and logistic models.
+//!
+//! Clipping and z-scoring become `DerivedField`s in the
+//! `TransformationDictionary`, and the model a `RegressionModel` whose
+//! `logit` normalization yields probabilities for logistic models.
+//! `evaluate` reads the document back with a minimal XML parser so exports
+//! can be checked against `Model::predict`.
+
+use crate::io::export::{sigmoid, ExportError, LinearExport};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+
+pub const PMML_VERSION: &str = "4.4";
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+fn field_ref(name: &str) -> String {
+    format!("<FieldRef field=\"{}\"/>", escape(name))
+}
+
+fn constant(value: f64) -> String {
+    format!("<Constant dataType=\"double\">{value:?}</Constant>")
+}
+
+fn apply(function: &str, left: String, right: String) -> String {
+    format!(
+        "<Apply function=\"{}\">{left}{right}</Apply>",
+        escape(function)
+    )
+}
+
+/// Serializes `export` as a PMML document.
+pub fn to_pmml(export: &LinearExport) -> String {
+    let mut xml = String::new();
+    let target = if export.target_name.is_empty() {
+        "target"
+    } else {
+        &export.target_name
+    };
+    let kind = if export.logistic {
+        "logistic_regression"
+    } else {
+        "linear_regression"
+    };
+    let _ = writeln!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    let _ = writeln!(
+        xml,
+        "<PMML xmlns=\"http://www.dmg.org/PMML-4_4\" version=\"{PMML_VERSION}\">"
+    );
+    let _ = writeln!(
+        xml,
+        "  <Header description=\"rustml {kind}\">\n    <Application name=\"rustml\" version=\"{}\"/>\n  </Header>",
+        env!("CARGO_PKG_VERSION")
+    );
+    let _ = writeln!(
+        xml,
+        "  <DataDictionary numberOfFields=\"{}\">",
+        export.num_features() + 1
+    );
+    for name in export
+        .feature_names
+        .iter()
+        .map(String::as_str)
+        .chain([target])
+    {
+        let _ = writeln!(
+            xml,
+            "    <DataField name=\"{}\" optype=\"continuous\" dataType=\"double\"/>",
+            escape(name)
+        );
+    }
+    let _ = writeln!(xml, "  </DataDictionary>");
+
+    let mut predictors: Vec<String> = export.feature_names.clone();
+    if export.clip.is_some() || export.scaling.is_some() {
+        let _ = writeln!(xml, "  <TransformationDictionary>");
+        for (idx, name) in export.feature_names.iter().enumerate() {
+            let mut expression = field_ref(name);
+            let mut derived = name.clone();
+            if let Some((lower, upper)) = &export.clip {
+                expression = apply(
+                    "min",
+                    apply("max", expression, constant(lower[idx])),
+                    constant(upper[idx]),
+                );
+                derived = format!("clipped_{name}");
+            }
+            if let Some((mean, std_dev)) = &export.scaling {
+                expression = apply(
+                    "/",
+                    apply("-", expression, constant(mean[idx])),
+                    constant(std_dev[idx]),
+                );
+                derived = format!("scaled_{name}");
+            }
+            let _ = writeln!(
+                xml,
+                "    <DerivedField name=\"{}\" optype=\"continuous\" dataType=\"double\">{expression}</DerivedField>",
+                escape(&derived)
+            );
+            predictors[idx] = derived;
+        }
+        let _ = writeln!(xml, "  </TransformationDictionary>");
+    }
+
+    let normalization = if export.logistic { "logit" } else { "none" };
+    let _ = writeln!(
+        xml,
+        "  <RegressionModel modelName=\"{kind}\" functionName=\"regression\" normalizationMethod=\"{normalization}\">"
+    );
+    let _ = writeln!(xml, "    <MiningSchema>");
+    for name in &export.feature_names {
+        let _ = writeln!(xml, "      <MiningField name=\"{}\"/>", escape(name));
+    }
+    let _ = writeln!(
+        xml,
+        "      <MiningField name=\"{}\" usageType=\"target\"/>",
+        escape(target)
+    );
+    let _ = writeln!(xml, "    </MiningSchema>");
+    let _ = writeln!(xml, "    <RegressionTable intercept=\"{:?}\">", export.bias);
+    for (name, weight) in predictors.iter().zip(&export.weights) {
+        let _ = writeln!(
+            xml,
+            "      <NumericPredictor name=\"{}\" coefficient=\"{weight:?}\"/>",
+            escape(name)
+        );
+    }
+    let _ = writeln!(xml, "    </RegressionTable>");
+    let _ = writeln!(xml, "  </RegressionModel>");
+    let _ = writeln!(xml, "</PMML>");
+    xml
+}
+
+pub fn save_pmml(path: &str, export: &LinearExport) -> Result<(), Box<dyn Error>> {
+    std::fs::write(path, to_pmml(export))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct Element {
+    name: String,
+    attributes: HashMap<String, String>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn attribute(&self, key: &str) -> Result<&str, ExportError> {
+        self.attributes
+            .get(key)
+            .map(String::as_str)
+            .ok_or_else(|| malformed(format!("<{}> has no {key} attribute", self.name)))
+    }
+
+    fn number(&self, key: &str) -> Result<f64, ExportError> {
+        let value = self.attribute(key)?;
+        value
+            .parse()
+            .map_err(|_| malformed(format!("{key}=\"{value}\" is not a number")))
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+fn malformed(message: impl Into<String>) -> ExportError {
+    ExportError::Malformed(message.into())
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Parses the element subset `to_pmml` writes: nested elements, quoted
+/// attributes, text and the XML declaration.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ExportError> {
+        if !self.rest().starts_with(token) {
+            return Err(malformed(format!(
+                "expected '{token}' at byte {}",
+                self.pos
+            )));
+        }
+        self.pos += token.len();
+        Ok(())
+    }
+
+    fn name(&mut self) -> Result<String, ExportError> {
+        let end = self
+            .rest()
+            .find(|ch: char| ch.is_whitespace() || ch == '>' || ch == '/' || ch == '=')
+            .ok_or_else(|| malformed("unterminated tag"))?;
+        if end == 0 {
+            return Err(malformed(format!("expected a name at byte {}", self.pos)));
+        }
+        let name = self.rest()[..end].to_string();
+        self.pos += end;
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, ExportError> {
+        self.expect("<")?;
+        let name = self.name()?;
+        let mut attributes = HashMap::new();
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(Element {
+                    name,
+                    attributes,
+                    children: Vec::new(),
+                    text: String::new(),
+                });
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let key = self.name()?;
+            self.expect("=\"")?;
+            let end = self
+                .rest()
+                .find('"')
+                .ok_or_else(|| malformed("unterminated attribute"))?;
+            attributes.insert(key, unescape(&self.rest()[..end]));
+            self.pos += end + 1;
+        }
+        let mut children = Vec::new();
+        let mut text = String::new();
+        loop {
+            let next = self
+                .rest()
+                .find('<')
+                .ok_or_else(|| malformed(format!("<{name}> is never closed")))?;
+            text.push_str(&unescape(&self.rest()[..next]));
+            self.pos += next;
+            if self.rest().starts_with("</") {
+                self.pos += 2;
+                let closing = self.name()?;
+                if closing != name {
+                    return Err(malformed(format!("<{name}> closed by </{closing}>")));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                break;
+            }
+            children.push(self.element()?);
+        }
+        Ok(Element {
+            name,
+            attributes,
+            children,
+            text: text.trim().to_string(),
+        })
+    }
+
+    fn document(input: &'a str) -> Result<Element, ExportError> {
+        let mut parser = Parser { input, pos: 0 };
+        parser.skip_whitespace();
+        if parser.rest().starts_with("<?") {
+            let end = parser
+                .rest()
+                .find("?>")
+                .ok_or_else(|| malformed("unterminated XML declaration"))?;
+            parser.pos += end + 2;
+            parser.skip_whitespace();
+        }
+        parser.element()
+    }
+}
+
+fn evaluate_expression(
+    expression: &Element,
+    fields: &HashMap<String, f64>,
+) -> Result<f64, ExportError> {
+    match expression.name.as_str() {
+        "FieldRef" => {
+            let name = expression.attribute("field")?;
+            fields
+                .get(name)
+                .cloned()
+                .ok_or_else(|| malformed(format!("unknown field '{name}'")))
+        }
+        "Constant" => expression
+            .text
+            .parse()
+            .map_err(|_| malformed(format!("constant '{}' is not a number", expression.text))),
+        "Apply" => {
+            let function = expression.attribute("function")?;
+            let arguments = expression
+                .children
+                .iter()
+                .map(|argument| evaluate_expression(argument, fields))
+                .collect::<Result<Vec<_>, _>>()?;
+            match (function, arguments.as_slice()) {
+                ("+", [a, b]) => Ok(a + b),
+                ("-", [a, b]) => Ok(a - b),
+                ("*", [a, b]) => Ok(a * b),
+                ("/", [a, b]) => Ok(a / b),
+                ("min", [a, b]) => Ok(a.min(*b)),
+                ("max", [a, b]) => Ok(a.max(*b)),
+                _ => Err(malformed(format!(
+                    "unsupported function '{function}' with {} arguments",
+                    arguments.len()
+                ))),
+            }
+        }
+        other => Err(malformed(format!("unsupported expression <{other}>"))),
+    }
+}
+
+/// Scores `rows` (in `MiningSchema` input order) with an exported document.
+pub fn evaluate(document: &str, rows: &[Vec<f64>]) -> Result<Vec<f64>, ExportError> {
+    let root = Parser::document(document)?;
+    let model = root
+        .child("RegressionModel")
+        .ok_or_else(|| malformed("document has no RegressionModel"))?;
+    let inputs: Vec<&str> = model
+        .child("MiningSchema")
+        .ok_or_else(|| malformed("model has no MiningSchema"))?
+        .children_named("MiningField")
+        .filter(|field| field.attributes.get("usageType").map(String::as_str) != Some("target"))
+        .map(|field| field.attribute("name"))
+        .collect::<Result<_, _>>()?;
+    let derived: Vec<&Element> = root
+        .child("TransformationDictionary")
+        .map(|dictionary| dictionary.children_named("DerivedField").collect())
+        .unwrap_or_default();
+    let table = model
+        .child("RegressionTable")
+        .ok_or_else(|| malformed("model has no RegressionTable"))?;
+    let intercept = table.number("intercept")?;
+    let predictors: Vec<(&str, f64)> = table
+        .children_named("NumericPredictor")
+        .map(|predictor| {
+            Ok((
+                predictor.attribute("name")?,
+                predictor.number("coefficient")?,
+            ))
+        })
+        .collect::<Result<_, ExportError>>()?;
+    let logit = match model.attribute("normalizationMethod").unwrap_or("none") {
+        "none" => false,
+        "logit" => true,
+        other => return Err(malformed(format!("unsupported normalization '{other}'"))),
+    };
+
+    rows.iter()
+        .map(|row| {
+            if row.len() != inputs.len() {
+                return Err(malformed(format!(
+                    "row has {} values, the model expects {}",
+                    row.len(),
+                    inputs.len()
+                )));
+            }
+            let mut fields: HashMap<String, f64> = inputs
+                .iter()
+                .map(|name| name.to_string())
+                .zip(row.iter().cloned())
+                .collect();
+            for field in &derived {
+                let expression = field
+                    .children
+                    .first()
+                    .ok_or_else(|| malformed("DerivedField has no expression"))?;
+                let value = evaluate_expression(expression, &fields)?;
+                fields.insert(field.attribute("name")?.to_string(), value);
+            }
+            let mut linear = 0.0;
+            for (name, coefficient) in &predictors {
+                let value = fields
+                    .get(*name)
+                    .ok_or_else(|| malformed(format!("unknown predictor '{name}'")))?;
+                linear += value * coefficient;
+            }
+            let score = linear + intercept;
+            Ok(if logit { sigmoid(score) } else { score })
+        })
+        .collect()
+}
@@ -6,3 +6,5 @@ pub mod pipeline;
 pub mod io;
 pub mod config;
 pub mod deployment;
+pub mod registry;
+pub mod drift;
@@ -0,0 +1,360 @@
+use crate::math::matrix::Matrix;
+use crate::math::random::DeterministicRng;
+use crate::math::special::{chi_square_sf, kolmogorov_sf, normal_cdf, student_t_cdf};
+use crate::math::stats::{self, mean, ranks, sample_variance, QuantileMethod};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+    pub degrees_of_freedom: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ConfidenceInterval {
+    pub estimate: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HypothesisError {
+    InsufficientData { required: usize, found: usize },
+    MismatchedLengths { left: usize, right: usize },
+    ZeroVariance,
+    InvalidParameter { name: &'static str, value: f64 },
+}
+
+impl fmt::Display for HypothesisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HypothesisError::InsufficientData { required, found } => write!(
+                f,
+                "insufficient data: need at least {required} observations, found {found}"
+            ),
+            HypothesisError::MismatchedLengths { left, right } => {
+                write!(f, "sample length mismatch: {left} vs {right}")
+            }
+            HypothesisError::ZeroVariance => {
+                write!(f, "test statistic undefined for zero variance")
+            }
+            HypothesisError::InvalidParameter { name, value } => {
+                write!(f, "invalid value {value} for parameter {name}")
+            }
+        }
+    }
+}
+
+impl Error for HypothesisError {}
+
+fn require_len(values: &[f64], required: usize) -> Result<(), HypothesisError> {
+    if values.len() < required {
+        return Err(HypothesisError::InsufficientData {
+            required,
+            found: values.len(),
+        });
+    }
+    Ok(())
+}
+
+fn require_same_len(a: &[f64], b: &[f64]) -> Result<(), HypothesisError> {
+    if a.len() != b.len() {
+        return Err(HypothesisError::MismatchedLengths {
+            left: a.len(),
+            right: b.len(),
+        });
+    }
+    Ok(())
+}
+
+fn two_sided_t(statistic: f64, degrees_of_freedom: f64) -> TestResult {
+    TestResult {
+        statistic,
+        p_value: 2.0 * student_t_cdf(-statistic.abs(), degrees_of_freedom),
+        degrees_of_freedom: Some(degrees_of_freedom),
+    }
+}
+
+fn two_sided_normal(statistic: f64) -> TestResult {
+    TestResult {
+        statistic,
+        p_value: (2.0 * normal_cdf(-statistic.abs())).min(1.0),
+        degrees_of_freedom: None,
+    }
+}
+
+pub fn paired_t_test(a: &[f64], b: &[f64]) -> Result<TestResult, HypothesisError> {
+    require_same_len(a, b)?;
+    require_len(a, 2)?;
+    let differences: Vec<f64> = a.iter().zip(b).map(|(x, y)| x - y).collect();
+    let variance = sample_variance(&differences);
+    if variance == 0.0 {
+        return Err(HypothesisError::ZeroVariance);
+    }
+    let n = differences.len() as f64;
+    let statistic = mean(&differences) / (variance / n).sqrt();
+    Ok(two_sided_t(statistic, n - 1.0))
+}
+
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> Result<TestResult, HypothesisError> {
+    require_len(a, 2)?;
+    require_len(b, 2)?;
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let se_a = sample_variance(a) / n_a;
+    let se_b = sample_variance(b) / n_b;
+    if se_a + se_b == 0.0 {
+        return Err(HypothesisError::ZeroVariance);
+    }
+    let statistic = (mean(a) - mean(b)) / (se_a + se_b).sqrt();
+    let degrees_of_freedom =
+        (se_a + se_b).powi(2) / (se_a.powi(2) / (n_a - 1.0) + se_b.powi(2) / (n_b - 1.0));
+    Ok(two_sided_t(statistic, degrees_of_freedom))
+}
+
+/// Goodness-of-fit test of observed counts against expected counts.
+pub fn chi_square_test(observed: &[f64], expected: &[f64]) -> Result<TestResult, HypothesisError> {
+    require_same_len(observed, expected)?;
+    require_len(observed, 2)?;
+    let mut statistic = 0.0;
+    for (o, e) in observed.iter().zip(expected) {
+        if *e <= 0.0 {
+            return Err(HypothesisError::InvalidParameter {
+                name: "expected",
+                value: *e,
+            });
+        }
+        statistic += (o - e).powi(2) / e;
+    }
+    let degrees_of_freedom = (observed.len() - 1) as f64;
+    Ok(TestResult {
+        statistic,
+        p_value: chi_square_sf(statistic, degrees_of_freedom),
+        degrees_of_freedom: Some(degrees_of_freedom),
+    })
+}
+
+/// Test of independence for a contingency table of counts.
+pub fn chi_square_independence(table: &Matrix) -> Result<TestResult, HypothesisError> {
+    let (rows, cols) = table.shape();
+    if rows < 2 || cols < 2 {
+        return Err(HypothesisError::InsufficientData {
+            required: 2,
+            found: rows.min(cols),
+        });
+    }
+    let row_totals: Vec<f64> = table.iter_rows().map(|row| row.iter().sum()).collect();
+    let col_totals: Vec<f64> = (0..cols)
+        .map(|col| table.column(col).iter().sum())
+        .collect();
+    let total: f64 = row_totals.iter().sum();
+    let mut statistic = 0.0;
+    for row in 0..rows {
+        for col in 0..cols {
+            let expected = row_totals[row] * col_totals[col] / total;
+            if expected <= 0.0 {
+                return Err(HypothesisError::InvalidParameter {
+                    name: "expected",
+                    value: expected,
+                });
+            }
+            statistic += (table[(row, col)] - expected).powi(2) / expected;
+        }
+    }
+    let degrees_of_freedom = ((rows - 1) * (cols - 1)) as f64;
+    Ok(TestResult {
+        statistic,
+        p_value: chi_square_sf(statistic, degrees_of_freedom),
+        degrees_of_freedom: Some(degrees_of_freedom),
+    })
+}
+
+/// Two-sample Kolmogorov–Smirnov test; the statistic is the maximum ECDF gap.
+pub fn ks_two_sample(a: &[f64], b: &[f64]) -> Result<TestResult, HypothesisError> {
+    require_len(a, 1)?;
+    require_len(b, 1)?;
+    let statistic = ks_statistic(a, b);
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let effective = (n_a * n_b / (n_a + n_b)).sqrt();
+    Ok(TestResult {
+        statistic,
+        p_value: kolmogorov_sf((effective + 0.12 + 0.11 / effective) * statistic),
+        degrees_of_freedom: None,
+    })
+}
+
+pub fn ks_statistic(a: &[f64], b: &[f64]) -> f64 {
+    let mut sorted_a = a.to_vec();
+    let mut sorted_b = b.to_vec();
+    sorted_a.sort_by(|x, y| x.total_cmp(y));
+    sorted_b.sort_by(|x, y| x.total_cmp(y));
+    let (n_a, n_b) = (sorted_a.len() as f64, sorted_b.len() as f64);
+    let (mut i, mut j) = (0, 0);
+    let mut statistic: f64 = 0.0;
+    while i < sorted_a.len() && j < sorted_b.len() {
+        let value = sorted_a[i].min(sorted_b[j]);
+        while i < sorted_a.len() && sorted_a[i] <= value {
+            i += 1;
+        }
+        while j < sorted_b.len() && sorted_b[j] <= value {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / n_a - j as f64 / n_b).abs());
+    }
+    statistic
+}
+
+/// Mann–Whitney U test using the tie-corrected normal approximation.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> Result<TestResult, HypothesisError> {
+    require_len(a, 1)?;
+    require_len(b, 1)?;
+    let combined: Vec<f64> = a.iter().chain(b).cloned().collect();
+    let ranked = ranks(&combined);
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let rank_sum_a: f64 = ranked[..a.len()].iter().sum();
+    let u_a = rank_sum_a - n_a * (n_a + 1.0) / 2.0;
+    let u = u_a.min(n_a * n_b - u_a);
+
+    let mut sorted = combined;
+    sorted.sort_by(|x, y| x.total_cmp(y));
+    let mut tie_term = 0.0;
+    let mut idx = 0;
+    while idx < sorted.len() {
+        let mut end = idx + 1;
+        while end < sorted.len() && sorted[end] == sorted[idx] {
+            end += 1;
+        }
+        let t = (end - idx) as f64;
+        tie_term += t * t * t - t;
+        idx = end;
+    }
+    let n = n_a + n_b;
+    let variance = n_a * n_b / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)).max(1.0));
+    if variance <= 0.0 {
+        return Err(HypothesisError::ZeroVariance);
+    }
+    let z = (u - n_a * n_b / 2.0 + 0.5).min(0.0) / variance.sqrt();
+    Ok(TestResult {
+        statistic: u,
+        p_value: two_sided_normal(z).p_value,
+        degrees_of_freedom: None,
+    })
+}
+
+/// McNemar's test with continuity correction, comparing two classifiers on the
+/// same targets. Predictions and targets are binarized at `threshold`, matching
+/// `utils::evaluation::confusion_matrix`.
+pub fn mcnemar_test(
+    predictions_a: &[f64],
+    predictions_b: &[f64],
+    targets: &[f64],
+    threshold: f64,
+) -> Result<TestResult, HypothesisError> {
+    require_same_len(predictions_a, predictions_b)?;
+    require_same_len(predictions_a, targets)?;
+    let mut only_a_correct = 0.0_f64;
+    let mut only_b_correct = 0.0;
+    for ((a, b), target) in predictions_a.iter().zip(predictions_b).zip(targets) {
+        let actual = *target >= threshold;
+        let a_correct = (*a >= threshold) == actual;
+        let b_correct = (*b >= threshold) == actual;
+        if a_correct && !b_correct {
+            only_a_correct += 1.0;
+        } else if b_correct && !a_correct {
+            only_b_correct += 1.0;
+        }
+    }
+    let discordant = only_a_correct + only_b_correct;
+    if discordant == 0.0 {
+        return Ok(TestResult {
+            statistic: 0.0,
+            p_value: 1.0,
+            degrees_of_freedom: Some(1.0),
+        });
+    }
+    let statistic = (only_a_correct - only_b_correct).abs() - 1.0;
+    let statistic = statistic.max(0.0).powi(2) / discordant;
+    Ok(TestResult {
+        statistic,
+        p_value: chi_square_sf(statistic, 1.0),
+        degrees_of_freedom: Some(1.0),
+    })
+}
+
+/// Percentile bootstrap interval for a metric such as
+/// `utils::evaluation::root_mean_squared_error`.
+pub fn bootstrap_confidence_interval<F>(
+    predictions: &[f64],
+    targets: &[f64],
+    metric: F,
+    resamples: usize,
+    confidence: f64,
+    seed: u64,
+) -> Result<ConfidenceInterval, HypothesisError>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+{
+    require_same_len(predictions, targets)?;
+    require_len(predictions, 1)?;
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(HypothesisError::InvalidParameter {
+            name: "confidence",
+            value: confidence,
+        });
+    }
+    if resamples == 0 {
+        return Err(HypothesisError::InvalidParameter {
+            name: "resamples",
+            value: 0.0,
+        });
+    }
+    let n = predictions.len();
+    let mut rng = DeterministicRng::new(seed);
+    let mut sample_predictions = vec![0.0; n];
+    let mut sample_targets = vec![0.0; n];
+    let mut estimates = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        for slot in 0..n {
+            let index = rng.gen_index(n);
+            sample_predictions[slot] = predictions[index];
+            sample_targets[slot] = targets[index];
+        }
+        estimates.push(metric(&sample_predictions, &sample_targets));
+    }
+    let alpha = (1.0 - confidence) / 2.0;
+    let bounds = stats::quantiles(&estimates, &[alpha, 1.0 - alpha], QuantileMethod::Linear);
+    Ok(ConfidenceInterval {
+        estimate: metric(predictions, targets),
+        lower: bounds[0].unwrap_or(f64::NAN),
+        upper: bounds[1].unwrap_or(f64::NAN),
+        confidence,
+    })
+}
+
+/// Nadeau–Bengio corrected resampled t-test over per-fold scores of two models.
+/// `test_train_ratio` is the size of each test fold divided by its training set.
+pub fn corrected_resampled_t_test(
+    scores_a: &[f64],
+    scores_b: &[f64],
+    test_train_ratio: f64,
+) -> Result<TestResult, HypothesisError> {
+    require_same_len(scores_a, scores_b)?;
+    require_len(scores_a, 2)?;
+    if test_train_ratio < 0.0 {
+        return Err(HypothesisError::InvalidParameter {
+            name: "test_train_ratio",
+            value: test_train_ratio,
+        });
+    }
+    let differences: Vec<f64> = scores_a.iter().zip(scores_b).map(|(a, b)| a - b).collect();
+    let variance = sample_variance(&differences);
+    if variance == 0.0 {
+        return Err(HypothesisError::ZeroVariance);
+    }
+    let k = differences.len() as f64;
+    let statistic = mean(&differences) / ((1.0 / k + test_train_ratio) * variance).sqrt();
+    Ok(two_sided_t(statistic, k - 1.0))
+}
@@ -0,0 +1,448 @@
+use crate::math::matrix::{Matrix, MatrixError};
+
+const EPSILON: f64 = 1e-12;
+const MAX_SWEEPS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct Lu {
+    lu: Matrix,
+    pivots: Vec<usize>,
+    sign: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Qr {
+    pub q: Matrix,
+    pub r: Matrix,
+}
+
+#[derive(Debug, Clone)]
+pub struct SymmetricEigen {
+    pub values: Vec<f64>,
+    pub vectors: Matrix,
+}
+
+#[derive(Debug, Clone)]
+pub struct Svd {
+    pub u: Matrix,
+    pub singular_values: Vec<f64>,
+    pub v: Matrix,
+}
+
+fn ensure_square(matrix: &Matrix) -> Result<usize, MatrixError> {
+    if !matrix.is_square() {
+        return Err(MatrixError::NotSquare {
+            rows: matrix.rows(),
+            cols: matrix.cols(),
+        });
+    }
+    Ok(matrix.rows())
+}
+
+fn symmetry_tolerance(matrix: &Matrix) -> f64 {
+    1e-9 * matrix.frobenius_norm().max(1.0)
+}
+
+pub fn lu(matrix: &Matrix) -> Result<Lu, MatrixError> {
+    let n = ensure_square(matrix)?;
+    let mut lu = matrix.clone();
+    let mut pivots: Vec<usize> = (0..n).collect();
+    let mut sign = 1.0;
+    for k in 0..n {
+        let mut pivot_row = k;
+        let mut pivot_value = lu[(k, k)].abs();
+        for row in (k + 1)..n {
+            if lu[(row, k)].abs() > pivot_value {
+                pivot_row = row;
+                pivot_value = lu[(row, k)].abs();
+            }
+        }
+        if pivot_value < EPSILON {
+            return Err(MatrixError::Singular);
+        }
+        if pivot_row != k {
+            for col in 0..n {
+                let tmp = lu[(k, col)];
+                lu[(k, col)] = lu[(pivot_row, col)];
+                lu[(pivot_row, col)] = tmp;
+            }
+            pivots.swap(k, pivot_row);
+            sign = -sign;
+        }
+        for row in (k + 1)..n {
+            let factor = lu[(row, k)] / lu[(k, k)];
+            lu[(row, k)] = factor;
+            for col in (k + 1)..n {
+                let delta = factor * lu[(k, col)];
+                lu[(row, col)] -= delta;
+            }
+        }
+    }
+    Ok(Lu { lu, pivots, sign })
+}
+
+impl Lu {
+    pub fn solve(&self, rhs: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        let n = self.lu.rows();
+        if rhs.len() != n {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (n, 1),
+                found: (rhs.len(), 1),
+            });
+        }
+        let mut x: Vec<f64> = self.pivots.iter().map(|&idx| rhs[idx]).collect();
+        for row in 0..n {
+            for col in 0..row {
+                x[row] -= self.lu[(row, col)] * x[col];
+            }
+        }
+        for row in (0..n).rev() {
+            for col in (row + 1)..n {
+                x[row] -= self.lu[(row, col)] * x[col];
+            }
+            x[row] /= self.lu[(row, row)];
+        }
+        Ok(x)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.lu.diagonal().iter().product::<f64>() * self.sign
+    }
+
+    pub fn inverse(&self) -> Result<Matrix, MatrixError> {
+        let n = self.lu.rows();
+        let mut inverse = Matrix::zeros(n, n);
+        let mut unit = vec![0.0; n];
+        for col in 0..n {
+            unit[col] = 1.0;
+            let solution = self.solve(&unit)?;
+            for (row, value) in solution.into_iter().enumerate() {
+                inverse[(row, col)] = value;
+            }
+            unit[col] = 0.0;
+        }
+        Ok(inverse)
+    }
+}
+
+pub fn solve(matrix: &Matrix, rhs: &[f64]) -> Result<Vec<f64>, MatrixError> {
+    lu(matrix)?.solve(rhs)
+}
+
+pub fn inverse(matrix: &Matrix) -> Result<Matrix, MatrixError> {
+    lu(matrix)?.inverse()
+}
+
+pub fn determinant(matrix: &Matrix) -> Result<f64, MatrixError> {
+    match lu(matrix) {
+        Ok(decomposition) => Ok(decomposition.determinant()),
+        Err(MatrixError::Singular) => Ok(0.0),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns the lower-triangular factor `L` such that `matrix = L * L^T`.
+pub fn cholesky(matrix: &Matrix) -> Result<Matrix, MatrixError> {
+    let n = ensure_square(matrix)?;
+    if !matrix.is_symmetric(symmetry_tolerance(matrix)) {
+        return Err(MatrixError::NotSymmetric);
+    }
+    let mut lower = Matrix::zeros(n, n);
+    for j in 0..n {
+        let mut diagonal = matrix[(j, j)];
+        for k in 0..j {
+            diagonal -= lower[(j, k)] * lower[(j, k)];
+        }
+        if diagonal <= 0.0 {
+            return Err(MatrixError::NotPositiveDefinite);
+        }
+        let diagonal = diagonal.sqrt();
+        lower[(j, j)] = diagonal;
+        for i in (j + 1)..n {
+            let mut value = matrix[(i, j)];
+            for k in 0..j {
+                value -= lower[(i, k)] * lower[(j, k)];
+            }
+            lower[(i, j)] = value / diagonal;
+        }
+    }
+    Ok(lower)
+}
+
+/// Householder QR. For an `m x n` matrix returns the thin factors `Q` (`m x p`)
+/// and `R` (`p x n`) with `p = min(m, n)`.
+pub fn qr(matrix: &Matrix) -> Qr {
+    let (m, n) = matrix.shape();
+    let p = m.min(n);
+    let mut r = matrix.clone();
+    let mut reflectors: Vec<Option<Vec<f64>>> = Vec::with_capacity(p);
+    for k in 0..p {
+        let column: Vec<f64> = (k..m).map(|row| r[(row, k)]).collect();
+        let norm = column.iter().map(|value| value * value).sum::<f64>().sqrt();
+        if norm < EPSILON {
+            reflectors.push(None);
+            continue;
+        }
+        let alpha = if column[0] > 0.0 { -norm } else { norm };
+        let mut v = column;
+        v[0] -= alpha;
+        let v_norm = v.iter().map(|value| value * value).sum::<f64>().sqrt();
+        if v_norm < EPSILON {
+            reflectors.push(None);
+            continue;
+        }
+        for value in &mut v {
+            *value /= v_norm;
+        }
+        for col in k..n {
+            let projection: f64 = (k..m).map(|row| v[row - k] * r[(row, col)]).sum();
+            for row in k..m {
+                r[(row, col)] -= 2.0 * v[row - k] * projection;
+            }
+        }
+        reflectors.push(Some(v));
+    }
+
+    let mut q = Matrix::zeros(m, p);
+    for idx in 0..p {
+        q[(idx, idx)] = 1.0;
+    }
+    for (k, reflector) in reflectors.iter().enumerate().rev() {
+        if let Some(v) = reflector {
+            for col in 0..p {
+                let projection: f64 = (k..m).map(|row| v[row - k] * q[(row, col)]).sum();
+                for row in k..m {
+                    q[(row, col)] -= 2.0 * v[row - k] * projection;
+                }
+            }
+        }
+    }
+
+    let mut upper = Matrix::zeros(p, n);
+    for row in 0..p {
+        for col in row..n {
+            upper[(row, col)] = r[(row, col)];
+        }
+    }
+    Qr { q, r: upper }
+}
+
+impl Qr {
+    /// Least-squares solution of `A x = b` for a full-rank `A` with at least as many rows as columns.
+    pub fn solve_least_squares(&self, rhs: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        let (m, p) = self.q.shape();
+        let n = self.r.cols();
+        if rhs.len() != m {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (m, 1),
+                found: (rhs.len(), 1),
+            });
+        }
+        if p < n {
+            return Err(MatrixError::Singular);
+        }
+        let mut x = self.q.transpose().mul_vector(rhs)?;
+        for row in (0..n).rev() {
+            for col in (row + 1)..n {
+                x[row] -= self.r[(row, col)] * x[col];
+            }
+            if self.r[(row, row)].abs() < EPSILON {
+                return Err(MatrixError::Singular);
+            }
+            x[row] /= self.r[(row, row)];
+        }
+        Ok(x)
+    }
+}
+
+/// Cyclic Jacobi eigen-decomposition of a symmetric matrix. Eigenvalues are
+/// sorted in descending order and eigenvectors are stored as columns.
+pub fn symmetric_eigen(matrix: &Matrix) -> Result<SymmetricEigen, MatrixError> {
+    let n = ensure_square(matrix)?;
+    if !matrix.is_symmetric(symmetry_tolerance(matrix)) {
+        return Err(MatrixError::NotSymmetric);
+    }
+    let mut a = matrix.clone();
+    let mut vectors = Matrix::identity(n);
+    let scale = matrix.frobenius_norm().max(EPSILON);
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut off_diagonal = 0.0;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                off_diagonal += a[(p, q)] * a[(p, q)];
+            }
+        }
+        if off_diagonal.sqrt() <= EPSILON * scale {
+            converged = true;
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let apq = a[(p, q)];
+                if apq.abs() < f64::MIN_POSITIVE {
+                    continue;
+                }
+                let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let akp = a[(k, p)];
+                    let akq = a[(k, q)];
+                    a[(k, p)] = c * akp - s * akq;
+                    a[(k, q)] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let apk = a[(p, k)];
+                    let aqk = a[(q, k)];
+                    a[(p, k)] = c * apk - s * aqk;
+                    a[(q, k)] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let vkp = vectors[(k, p)];
+                    let vkq = vectors[(k, q)];
+                    vectors[(k, p)] = c * vkp - s * vkq;
+                    vectors[(k, q)] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    if !converged {
+        return Err(MatrixError::NoConvergence {
+            iterations: MAX_SWEEPS,
+        });
+    }
+    let diagonal = a.diagonal();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| diagonal[j].total_cmp(&diagonal[i]));
+    let values = order.iter().map(|&idx| diagonal[idx]).collect();
+    let mut sorted = Matrix::zeros(n, n);
+    for (new_col, &old_col) in order.iter().enumerate() {
+        for row in 0..n {
+            sorted[(row, new_col)] = vectors[(row, old_col)];
+        }
+    }
+    Ok(SymmetricEigen {
+        values,
+        vectors: sorted,
+    })
+}
+
+/// One-sided Jacobi SVD returning the thin factors with singular values in
+/// descending order, so that `matrix = U * diag(s) * V^T`.
+pub fn svd(matrix: &Matrix) -> Result<Svd, MatrixError> {
+    if matrix.rows() < matrix.cols() {
+        let transposed = svd(&matrix.transpose())?;
+        return Ok(Svd {
+            u: transposed.v,
+            singular_values: transposed.singular_values,
+            v: transposed.u,
+        });
+    }
+    let (m, n) = matrix.shape();
+    let mut u = matrix.clone();
+    let mut v = Matrix::identity(n);
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in (p + 1)..n {
+                let mut alpha = 0.0;
+                let mut beta = 0.0;
+                let mut gamma = 0.0;
+                for k in 0..m {
+                    alpha += u[(k, p)] * u[(k, p)];
+                    beta += u[(k, q)] * u[(k, q)];
+                    gamma += u[(k, p)] * u[(k, q)];
+                }
+                if gamma.abs() <= EPSILON * (alpha * beta).sqrt() || gamma.abs() < f64::MIN_POSITIVE
+                {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+                for k in 0..m {
+                    let ukp = u[(k, p)];
+                    let ukq = u[(k, q)];
+                    u[(k, p)] = c * ukp - s * ukq;
+                    u[(k, q)] = s * ukp + c * ukq;
+                }
+                for k in 0..n {
+                    let vkp = v[(k, p)];
+                    let vkq = v[(k, q)];
+                    v[(k, p)] = c * vkp - s * vkq;
+                    v[(k, q)] = s * vkp + c * vkq;
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(MatrixError::NoConvergence {
+            iterations: MAX_SWEEPS,
+        });
+    }
+    let norms: Vec<f64> = (0..n)
+        .map(|col| {
+            (0..m)
+                .map(|row| u[(row, col)] * u[(row, col)])
+                .sum::<f64>()
+                .sqrt()
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+    let mut sorted_u = Matrix::zeros(m, n);
+    let mut sorted_v = Matrix::zeros(n, n);
+    for (new_col, &old_col) in order.iter().enumerate() {
+        let sigma = norms[old_col];
+        for row in 0..m {
+            sorted_u[(row, new_col)] = if sigma > EPSILON {
+                u[(row, old_col)] / sigma
+            } else {
+                0.0
+            };
+        }
+        for row in 0..n {
+            sorted_v[(row, new_col)] = v[(row, old_col)];
+        }
+    }
+    Ok(Svd {
+        u: sorted_u,
+        singular_values: order.iter().map(|&idx| norms[idx]).collect(),
+        v: sorted_v,
+    })
+}
+
+impl Svd {
+    pub fn rank(&self, tolerance: f64) -> usize {
+        self.singular_values
+            .iter()
+            .filter(|value| **value > tolerance)
+            .count()
+    }
+
+    pub fn pseudo_inverse(&self, tolerance: f64) -> Matrix {
+        let inverted: Vec<f64> = self
+            .singular_values
+            .iter()
+            .map(|value| if *value > tolerance { 1.0 / value } else { 0.0 })
+            .collect();
+        let mut scaled_v = self.v.clone();
+        for row in 0..scaled_v.rows() {
+            for (col, factor) in inverted.iter().enumerate() {
+                scaled_v[(row, col)] *= factor;
+            }
+        }
+        scaled_v
+            .matmul(&self.u.transpose())
+            .expect("svd factors have compatible shapes")
+    }
+}
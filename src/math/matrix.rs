@@ -0,0 +1,377 @@
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "RawMatrix")]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+/// Deserialized form of `Matrix`, checked by `Matrix::new` before use.
+#[derive(Deserialize)]
+struct RawMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl TryFrom<RawMatrix> for Matrix {
+    type Error = MatrixError;
+
+    fn try_from(raw: RawMatrix) -> Result<Self, MatrixError> {
+        Matrix::new(raw.rows, raw.cols, raw.data)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    InvalidBufferLength {
+        expected: usize,
+        found: usize,
+    },
+    MismatchedRowLengths {
+        expected: usize,
+        found: usize,
+    },
+    DimensionMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    NotSquare {
+        rows: usize,
+        cols: usize,
+    },
+    NotSymmetric,
+    NotPositiveDefinite,
+    Singular,
+    NoConvergence {
+        iterations: usize,
+    },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::InvalidBufferLength { expected, found } => write!(
+                f,
+                "invalid buffer length: expected {expected} values, found {found}"
+            ),
+            MatrixError::MismatchedRowLengths { expected, found } => write!(
+                f,
+                "row length mismatch: expected {expected} columns, found {found}"
+            ),
+            MatrixError::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimension mismatch: expected {}x{}, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            MatrixError::NotSquare { rows, cols } => {
+                write!(f, "matrix is not square: {rows}x{cols}")
+            }
+            MatrixError::NotSymmetric => write!(f, "matrix is not symmetric"),
+            MatrixError::NotPositiveDefinite => write!(f, "matrix is not positive definite"),
+            MatrixError::Singular => write!(f, "matrix is singular"),
+            MatrixError::NoConvergence { iterations } => {
+                write!(
+                    f,
+                    "decomposition did not converge after {iterations} iterations"
+                )
+            }
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+impl Matrix {
+    pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Result<Self, MatrixError> {
+        if rows.checked_mul(cols) != Some(data.len()) {
+            return Err(MatrixError::InvalidBufferLength {
+                expected: rows.saturating_mul(cols),
+                found: data.len(),
+            });
+        }
+        Ok(Matrix { rows, cols, data })
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        let mut matrix = Matrix::zeros(size, size);
+        for idx in 0..size {
+            matrix[(idx, idx)] = 1.0;
+        }
+        matrix
+    }
+
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self, MatrixError> {
+        let cols = rows.first().map(|row| row.len()).unwrap_or(0);
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for row in rows {
+            if row.len() != cols {
+                return Err(MatrixError::MismatchedRowLengths {
+                    expected: cols,
+                    found: row.len(),
+                });
+            }
+            data.extend_from_slice(row);
+        }
+        Ok(Matrix {
+            rows: rows.len(),
+            cols,
+            data,
+        })
+    }
+
+    pub fn from_diagonal(values: &[f64]) -> Self {
+        let mut matrix = Matrix::zeros(values.len(), values.len());
+        for (idx, value) in values.iter().enumerate() {
+            matrix[(idx, idx)] = *value;
+        }
+        matrix
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<f64> {
+        self.data
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<f64> {
+        if row < self.rows && col < self.cols {
+            Some(self.data[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[f64] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [f64] {
+        &mut self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> Vec<f64> {
+        (0..self.rows).map(|row| self[(row, col)]).collect()
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[f64]> {
+        (0..self.rows).map(move |row| self.row(row))
+    }
+
+    pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = &mut [f64]> {
+        let cols = self.cols.max(1);
+        let rows = self.rows;
+        self.data.chunks_mut(cols).take(rows)
+    }
+
+    pub fn push_row(&mut self, row: &[f64]) -> Result<(), MatrixError> {
+        if row.len() != self.cols {
+            return Err(MatrixError::MismatchedRowLengths {
+                expected: self.cols,
+                found: row.len(),
+            });
+        }
+        self.data.extend_from_slice(row);
+        self.rows += 1;
+        Ok(())
+    }
+
+    pub fn push_column(&mut self, values: &[f64]) -> Result<(), MatrixError> {
+        if values.len() != self.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.rows, 1),
+                found: (values.len(), 1),
+            });
+        }
+        let mut data = Vec::with_capacity(self.rows * (self.cols + 1));
+        for (row, value) in values.iter().enumerate() {
+            data.extend_from_slice(self.row(row));
+            data.push(*value);
+        }
+        self.data = data;
+        self.cols += 1;
+        Ok(())
+    }
+
+    pub fn select_rows(&self, indices: &[usize]) -> Matrix {
+        let mut data = Vec::with_capacity(indices.len() * self.cols);
+        for &index in indices {
+            data.extend_from_slice(self.row(index));
+        }
+        Matrix {
+            rows: indices.len(),
+            cols: self.cols,
+            data,
+        }
+    }
+
+    pub fn diagonal(&self) -> Vec<f64> {
+        (0..self.rows.min(self.cols))
+            .map(|idx| self[(idx, idx)])
+            .collect()
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut transposed = Matrix::zeros(self.cols, self.rows);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                transposed[(col, row)] = self[(row, col)];
+            }
+        }
+        transposed
+    }
+
+    pub fn mul_vector(&self, vector: &[f64]) -> Result<Vec<f64>, MatrixError> {
+        if vector.len() != self.cols {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.cols, 1),
+                found: (vector.len(), 1),
+            });
+        }
+        Ok(self
+            .iter_rows()
+            .map(|row| crate::math::vector::dot(row, vector))
+            .collect())
+    }
+
+    pub fn matmul(&self, other: &Matrix) -> Result<Matrix, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.cols, other.cols),
+                found: other.shape(),
+            });
+        }
+        let mut product = Matrix::zeros(self.rows, other.cols);
+        for row in 0..self.rows {
+            for k in 0..self.cols {
+                let value = self[(row, k)];
+                if value == 0.0 {
+                    continue;
+                }
+                let other_row = other.row(k);
+                for (out, rhs) in product.row_mut(row).iter_mut().zip(other_row) {
+                    *out += value * rhs;
+                }
+            }
+        }
+        Ok(product)
+    }
+
+    pub fn add(&self, other: &Matrix) -> Result<Matrix, MatrixError> {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    pub fn subtract(&self, other: &Matrix) -> Result<Matrix, MatrixError> {
+        self.zip_with(other, |a, b| a - b)
+    }
+
+    pub fn scale(&self, factor: f64) -> Matrix {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|value| value * factor).collect(),
+        }
+    }
+
+    pub fn frobenius_norm(&self) -> f64 {
+        self.data
+            .iter()
+            .map(|value| value * value)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    pub fn is_symmetric(&self, tolerance: f64) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+        for row in 0..self.rows {
+            for col in (row + 1)..self.cols {
+                if (self[(row, col)] - self[(col, row)]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn zip_with<F>(&self, other: &Matrix, func: F) -> Result<Matrix, MatrixError>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::DimensionMismatch {
+                expected: self.shape(),
+                found: other.shape(),
+            });
+        }
+        Ok(Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(a, b)| func(*a, *b))
+                .collect(),
+        })
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        assert!(
+            row < self.rows && col < self.cols,
+            "matrix index out of bounds"
+        );
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        assert!(
+            row < self.rows && col < self.cols,
+            "matrix index out of bounds"
+        );
+        &mut self.data[row * self.cols + col]
+    }
+}
@@ -1,3 +1,7 @@
 pub mod vector;
 pub mod stats;
 pub mod random;
+pub mod matrix;
+pub mod linalg;
+pub mod special;
+pub mod hypothesis;
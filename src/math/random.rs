@@ -1,17 +1,56 @@
-#[derive(Debug, Clone)]
+use crate::math::special::ln_gamma;
+
+/// Seedable xoshiro256** generator. Seeds are expanded with SplitMix64 and the
+/// integer output is identical on every platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DeterministicRng {
-    state: u64,
+    state: [u64; 4],
+}
+
+const JUMP: [u64; 4] = [
+    0x180e_c6d3_3cfd_0aba,
+    0xd5a6_1266_f0c9_392c,
+    0xa958_2618_e03f_c9aa,
+    0x39ab_dc45_29b1_661c,
+];
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
 }
 
 impl DeterministicRng {
     pub fn new(seed: u64) -> Self {
-        DeterministicRng { state: seed }
+        let mut seed_state = seed;
+        let mut state = [0u64; 4];
+        for word in &mut state {
+            *word = splitmix64(&mut seed_state);
+        }
+        DeterministicRng { state }
+    }
+
+    /// Builds a generator from raw xoshiro state. An all-zero state is replaced
+    /// by the state for seed 0 because xoshiro cannot leave it.
+    pub fn from_state(state: [u64; 4]) -> Self {
+        if state == [0; 4] {
+            return DeterministicRng::new(0);
+        }
+        DeterministicRng { state }
     }
 
     pub fn next_u64(&mut self) -> u64 {
-        // LCG constants from Numerical Recipes
-        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
-        self.state
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+        result
     }
 
     pub fn next_f64(&mut self) -> f64 {
@@ -22,4 +61,216 @@ impl DeterministicRng {
     pub fn gen_range(&mut self, low: f64, high: f64) -> f64 {
         low + (high - low) * self.next_f64()
     }
+
+    /// Uniform integer in `0..bound` without modulo bias.
+    pub fn gen_index(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "gen_index bound must be positive");
+        let bound = bound as u64;
+        let mut product = u128::from(self.next_u64()) * u128::from(bound);
+        let mut low = product as u64;
+        if low < bound {
+            let threshold = bound.wrapping_neg() % bound;
+            while low < threshold {
+                product = u128::from(self.next_u64()) * u128::from(bound);
+                low = product as u64;
+            }
+        }
+        (product >> 64) as usize
+    }
+
+    /// Advances the generator by 2^128 steps.
+    pub fn jump(&mut self) {
+        let mut jumped = [0u64; 4];
+        for word in JUMP.iter() {
+            for bit in 0..64 {
+                if word & (1u64 << bit) != 0 {
+                    for (target, source) in jumped.iter_mut().zip(self.state.iter()) {
+                        *target ^= source;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.state = jumped;
+    }
+
+    /// Returns `count` non-overlapping generators for parallel workers, each
+    /// 2^128 steps apart starting from the current state.
+    pub fn streams(&self, count: usize) -> Vec<DeterministicRng> {
+        let mut current = self.clone();
+        let mut streams = Vec::with_capacity(count);
+        for _ in 0..count {
+            current.jump();
+            streams.push(current.clone());
+        }
+        streams
+    }
+
+    pub fn bernoulli(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+    pub fn normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        let radius = (-2.0 * u1.ln()).sqrt();
+        mean + std_dev * radius * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    pub fn log_normal(&mut self, mu: f64, sigma: f64) -> f64 {
+        self.normal(mu, sigma).exp()
+    }
+
+    pub fn exponential(&mut self, rate: f64) -> f64 {
+        -(1.0 - self.next_f64()).ln() / rate
+    }
+
+    pub fn poisson(&mut self, lambda: f64) -> u64 {
+        if lambda <= 0.0 {
+            return 0;
+        }
+        if lambda < 30.0 {
+            let limit = (-lambda).exp();
+            let mut count = 0;
+            let mut product = self.next_f64();
+            while product > limit {
+                count += 1;
+                product *= self.next_f64();
+            }
+            return count;
+        }
+        // Hörmann's transformed rejection with squeeze (PTRS).
+        let sqrt_lambda = lambda.sqrt();
+        let log_lambda = lambda.ln();
+        let b = 0.931 + 2.53 * sqrt_lambda;
+        let a = -0.059 + 0.02483 * b;
+        let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+        let v_r = 0.9277 - 3.6224 / (b - 2.0);
+        loop {
+            let u = self.next_f64() - 0.5;
+            let v = self.next_f64();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+            if us >= 0.07 && v <= v_r {
+                return k as u64;
+            }
+            if k < 0.0 || (us < 0.013 && v > us) {
+                continue;
+            }
+            if v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln()
+                <= -lambda + k * log_lambda - ln_gamma(k + 1.0)
+            {
+                return k as u64;
+            }
+        }
+    }
+
+    /// Index drawn with probability proportional to `weights`; `None` when no
+    /// weight is positive.
+    pub fn categorical(&mut self, weights: &[f64]) -> Option<usize> {
+        let total: f64 = weights.iter().filter(|weight| **weight > 0.0).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let target = self.next_f64() * total;
+        let mut cumulative = 0.0;
+        let mut last_positive = None;
+        for (idx, weight) in weights.iter().enumerate() {
+            if *weight <= 0.0 {
+                continue;
+            }
+            cumulative += weight;
+            last_positive = Some(idx);
+            if target < cumulative {
+                return Some(idx);
+            }
+        }
+        last_positive
+    }
+
+    /// Zipf-distributed rank in `1..=n` with the given exponent, using
+    /// Hörmann and Derflinger's rejection-inversion method.
+    pub fn zipf(&mut self, n: usize, exponent: f64) -> usize {
+        assert!(n > 0, "zipf requires at least one element");
+        let zipf = ZipfSampler::new(n as f64, exponent);
+        loop {
+            let u = zipf.h_integral_n + self.next_f64() * (zipf.h_integral_x1 - zipf.h_integral_n);
+            let x = zipf.h_integral_inverse(u);
+            let k = (x + 0.5).floor().clamp(1.0, n as f64);
+            if k - x <= zipf.s || u >= zipf.h_integral(k + 0.5) - zipf.h(k) {
+                return k as usize;
+            }
+        }
+    }
+
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for idx in (1..values.len()).rev() {
+            let other = self.gen_index(idx + 1);
+            values.swap(idx, other);
+        }
+    }
+
+    /// `count` distinct indices from `0..population`, in selection order.
+    pub fn sample_without_replacement(&mut self, population: usize, count: usize) -> Vec<usize> {
+        let count = count.min(population);
+        let mut indices: Vec<usize> = (0..population).collect();
+        for idx in 0..count {
+            let other = idx + self.gen_index(population - idx);
+            indices.swap(idx, other);
+        }
+        indices.truncate(count);
+        indices
+    }
+}
+
+struct ZipfSampler {
+    exponent: f64,
+    h_integral_x1: f64,
+    h_integral_n: f64,
+    s: f64,
+}
+
+impl ZipfSampler {
+    fn new(n: f64, exponent: f64) -> Self {
+        let mut sampler = ZipfSampler {
+            exponent,
+            h_integral_x1: 0.0,
+            h_integral_n: 0.0,
+            s: 0.0,
+        };
+        sampler.h_integral_x1 = sampler.h_integral(1.5) - 1.0;
+        sampler.h_integral_n = sampler.h_integral(n + 0.5);
+        sampler.s = 2.0 - sampler.h_integral_inverse(sampler.h_integral(2.5) - sampler.h(2.0));
+        sampler
+    }
+
+    fn h(&self, x: f64) -> f64 {
+        (-self.exponent * x.ln()).exp()
+    }
+
+    fn h_integral(&self, x: f64) -> f64 {
+        let log_x = x.ln();
+        helper_expm1_ratio((1.0 - self.exponent) * log_x) * log_x
+    }
+
+    fn h_integral_inverse(&self, x: f64) -> f64 {
+        let t = (x * (1.0 - self.exponent)).max(-1.0);
+        (helper_ln1p_ratio(t) * x).exp()
+    }
+}
+
+fn helper_ln1p_ratio(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.ln_1p() / x
+    } else {
+        1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x))
+    }
+}
+
+fn helper_expm1_ratio(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.exp_m1() / x
+    } else {
+        1.0 + x * 0.5 * (1.0 + x / 3.0 * (1.0 + 0.25 * x))
+    }
 }
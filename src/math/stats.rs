@@ -1,3 +1,6 @@
+use crate::math::matrix::Matrix;
+use serde::{Deserialize, Serialize};
+
 pub fn mean(values: &[f64]) -> f64 {
     if values.is_empty() {
         return 0.0;
@@ -13,20 +16,403 @@ pub fn variance(values: &[f64]) -> f64 {
     values.iter().map(|x| (x - mu).powi(2)).sum::<f64>() / values.len() as f64
 }
 
+pub fn sample_variance(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    variance(values) * values.len() as f64 / (values.len() - 1) as f64
+}
+
 pub fn std_dev(values: &[f64]) -> f64 {
     variance(values).sqrt()
 }
 
-pub fn min(values: &[f64]) -> f64 {
-    values
+pub fn min(values: &[f64]) -> Option<f64> {
+    values.iter().cloned().reduce(f64::min)
+}
+
+pub fn max(values: &[f64]) -> Option<f64> {
+    values.iter().cloned().reduce(f64::max)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum QuantileMethod {
+    #[default]
+    Linear,
+    Lower,
+    Higher,
+    Nearest,
+    Midpoint,
+}
+
+fn sorted_finite(values: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values
         .iter()
         .cloned()
-        .fold(f64::INFINITY, |acc, value| acc.min(value))
+        .filter(|value| !value.is_nan())
+        .collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+/// Quantile of already sorted, NaN-free values.
+pub fn quantile_sorted(sorted: &[f64], q: f64, method: QuantileMethod) -> Option<f64> {
+    if sorted.is_empty() || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = sorted[position.floor() as usize];
+    let upper = sorted[position.ceil() as usize];
+    let value = match method {
+        QuantileMethod::Linear => lower + (upper - lower) * position.fract(),
+        QuantileMethod::Lower => lower,
+        QuantileMethod::Higher => upper,
+        QuantileMethod::Nearest => sorted[position.round() as usize],
+        QuantileMethod::Midpoint => (lower + upper) / 2.0,
+    };
+    Some(value)
+}
+
+pub fn quantile(values: &[f64], q: f64, method: QuantileMethod) -> Option<f64> {
+    quantile_sorted(&sorted_finite(values), q, method)
+}
+
+pub fn quantiles(values: &[f64], qs: &[f64], method: QuantileMethod) -> Vec<Option<f64>> {
+    let sorted = sorted_finite(values);
+    qs.iter()
+        .map(|&q| quantile_sorted(&sorted, q, method))
+        .collect()
+}
+
+pub fn median(values: &[f64]) -> Option<f64> {
+    quantile(values, 0.5, QuantileMethod::Linear)
+}
+
+/// Most frequent value; ties resolve to the smallest value.
+pub fn mode(values: &[f64]) -> Option<f64> {
+    let sorted = sorted_finite(values);
+    let mut best: Option<(f64, usize)> = None;
+    let mut idx = 0;
+    while idx < sorted.len() {
+        let run_end = sorted[idx..]
+            .iter()
+            .position(|value| *value != sorted[idx])
+            .map(|offset| idx + offset)
+            .unwrap_or(sorted.len());
+        let count = run_end - idx;
+        if best
+            .map(|(_, best_count)| count > best_count)
+            .unwrap_or(true)
+        {
+            best = Some((sorted[idx], count));
+        }
+        idx = run_end;
+    }
+    best.map(|(value, _)| value)
+}
+
+fn central_moment(values: &[f64], order: i32) -> f64 {
+    let mu = mean(values);
+    values.iter().map(|x| (x - mu).powi(order)).sum::<f64>() / values.len() as f64
 }
 
-pub fn max(values: &[f64]) -> f64 {
+pub fn skewness(values: &[f64]) -> f64 {
+    let var = variance(values);
+    if values.is_empty() || var == 0.0 {
+        return 0.0;
+    }
+    central_moment(values, 3) / var.powf(1.5)
+}
+
+/// Excess kurtosis (0 for a normal distribution).
+pub fn kurtosis(values: &[f64]) -> f64 {
+    let var = variance(values);
+    if values.is_empty() || var == 0.0 {
+        return 0.0;
+    }
+    central_moment(values, 4) / (var * var) - 3.0
+}
+
+pub fn weighted_mean(values: &[f64], weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().take(values.len()).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    values.iter().zip(weights).map(|(x, w)| x * w).sum::<f64>() / total
+}
+
+pub fn weighted_variance(values: &[f64], weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().take(values.len()).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let mu = weighted_mean(values, weights);
     values
+        .iter()
+        .zip(weights)
+        .map(|(x, w)| w * (x - mu).powi(2))
+        .sum::<f64>()
+        / total
+}
+
+pub fn weighted_std_dev(values: &[f64], weights: &[f64]) -> f64 {
+    weighted_variance(values, weights).sqrt()
+}
+
+/// Smallest value whose cumulative weight reaches `q` of the total weight.
+pub fn weighted_quantile(values: &[f64], weights: &[f64], q: f64) -> Option<f64> {
+    if !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+    let mut pairs: Vec<(f64, f64)> = values
         .iter()
         .cloned()
-        .fold(f64::NEG_INFINITY, |acc, value| acc.max(value))
+        .zip(weights.iter().cloned())
+        .filter(|(value, weight)| !value.is_nan() && *weight > 0.0)
+        .collect();
+    if pairs.is_empty() {
+        return None;
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let total: f64 = pairs.iter().map(|(_, weight)| weight).sum();
+    let threshold = q * total;
+    let mut cumulative = 0.0;
+    for (value, weight) in &pairs {
+        cumulative += weight;
+        if cumulative >= threshold {
+            return Some(*value);
+        }
+    }
+    pairs.last().map(|(value, _)| *value)
+}
+
+pub fn weighted_median(values: &[f64], weights: &[f64]) -> Option<f64> {
+    weighted_quantile(values, weights, 0.5)
+}
+
+/// Ranks starting at 1, with ties assigned their average rank.
+pub fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut idx = 0;
+    while idx < order.len() {
+        let mut end = idx + 1;
+        while end < order.len() && values[order[end]] == values[order[idx]] {
+            end += 1;
+        }
+        let average = (idx + end + 1) as f64 / 2.0;
+        for &position in &order[idx..end] {
+            ranks[position] = average;
+        }
+        idx = end;
+    }
+    ranks
+}
+
+/// Number of pairs in two paired samples, which must have equal lengths.
+fn paired_len(a: &[f64], b: &[f64]) -> usize {
+    assert_eq!(a.len(), b.len(), "paired samples must have equal lengths");
+    a.len()
+}
+
+/// Population covariance. Panics if the samples differ in length.
+pub fn covariance(a: &[f64], b: &[f64]) -> f64 {
+    let n = paired_len(a, b);
+    if n == 0 {
+        return 0.0;
+    }
+    let mean_a = mean(a);
+    let mean_b = mean(b);
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - mean_a) * (y - mean_b))
+        .sum::<f64>()
+        / n as f64
+}
+
+/// Pearson correlation. Panics if the samples differ in length.
+pub fn pearson(a: &[f64], b: &[f64]) -> f64 {
+    paired_len(a, b);
+    let denominator = (variance(a) * variance(b)).sqrt();
+    if denominator == 0.0 {
+        return 0.0;
+    }
+    covariance(a, b) / denominator
+}
+
+/// Spearman rank correlation. Panics if the samples differ in length.
+pub fn spearman(a: &[f64], b: &[f64]) -> f64 {
+    paired_len(a, b);
+    pearson(&ranks(a), &ranks(b))
+}
+
+/// Kendall's tau-b, which corrects for ties in either variable. Panics if the
+/// samples differ in length.
+pub fn kendall(a: &[f64], b: &[f64]) -> f64 {
+    let n = paired_len(a, b);
+    let mut concordant = 0.0_f64;
+    let mut discordant = 0.0;
+    let mut ties_a = 0.0;
+    let mut ties_b = 0.0;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let da = if a[i] == a[j] {
+                0.0
+            } else {
+                (a[i] - a[j]).signum()
+            };
+            let db = if b[i] == b[j] {
+                0.0
+            } else {
+                (b[i] - b[j]).signum()
+            };
+            if da == 0.0 && db == 0.0 {
+                continue;
+            } else if da == 0.0 {
+                ties_a += 1.0;
+            } else if db == 0.0 {
+                ties_b += 1.0;
+            } else if da == db {
+                concordant += 1.0;
+            } else {
+                discordant += 1.0;
+            }
+        }
+    }
+    let denominator =
+        ((concordant + discordant + ties_a) * (concordant + discordant + ties_b)).sqrt();
+    if denominator == 0.0 {
+        return 0.0;
+    }
+    (concordant - discordant) / denominator
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum CorrelationMethod {
+    #[default]
+    Pearson,
+    Spearman,
+    Kendall,
+}
+
+pub fn correlation(a: &[f64], b: &[f64], method: CorrelationMethod) -> f64 {
+    match method {
+        CorrelationMethod::Pearson => pearson(a, b),
+        CorrelationMethod::Spearman => spearman(a, b),
+        CorrelationMethod::Kendall => kendall(a, b),
+    }
+}
+
+/// Population covariance between the columns of `data` (rows are observations).
+pub fn covariance_matrix(data: &Matrix) -> Matrix {
+    let columns: Vec<Vec<f64>> = (0..data.cols()).map(|col| data.column(col)).collect();
+    let mut result = Matrix::zeros(data.cols(), data.cols());
+    for i in 0..columns.len() {
+        for j in i..columns.len() {
+            let value = covariance(&columns[i], &columns[j]);
+            result[(i, j)] = value;
+            result[(j, i)] = value;
+        }
+    }
+    result
+}
+
+pub fn correlation_matrix(data: &Matrix, method: CorrelationMethod) -> Matrix {
+    let columns: Vec<Vec<f64>> = (0..data.cols()).map(|col| data.column(col)).collect();
+    let mut result = Matrix::identity(data.cols());
+    for i in 0..columns.len() {
+        for j in (i + 1)..columns.len() {
+            let value = correlation(&columns[i], &columns[j], method);
+            result[(i, j)] = value;
+            result[(j, i)] = value;
+        }
+    }
+    result
+}
+
+/// Welford's streaming accumulator for mean, variance and range.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        RunningStats::default()
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = Some(self.min.map_or(value, |current| current.min(value)));
+        self.max = Some(self.max.map_or(value, |current| current.max(value)));
+    }
+
+    /// Combines two accumulators (Chan et al.), e.g. from parallel workers.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 +=
+            other.m2 + delta * delta * (self.count as f64 * other.count as f64) / count as f64;
+        self.count = count;
+        self.min = self.min.zip(other.min).map(|(a, b)| a.min(b));
+        self.max = self.max.zip(other.max).map(|(a, b)| a.max(b));
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn variance(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.m2 / self.count as f64
+    }
+
+    pub fn sample_variance(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        self.m2 / (self.count - 1) as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+}
+
+impl Extend<f64> for RunningStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
 }
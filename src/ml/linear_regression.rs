@@ -1,6 +1,7 @@
 use crate::data::dataset::Dataset;
 use crate::math::vector;
-use crate::ml::{Model, TrainingReport};
+use crate::ml::parallel::{accumulate_gradients, predict_rows, Parallelism};
+use crate::ml::{LinearModel, Model, TrainingReport};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +10,9 @@ pub struct LinearRegression {
     pub bias: f64,
     pub learning_rate: f64,
     pub epochs: usize,
+    /// Runtime setting, not part of the fitted model.
+    #[serde(skip)]
+    pub parallelism: Parallelism,
 }
 
 impl LinearRegression {
@@ -18,6 +22,7 @@ impl LinearRegression {
             bias: 0.0,
             learning_rate: 0.001,
             epochs: 300,
+            parallelism: Parallelism::default(),
         }
     }
 
@@ -27,15 +32,21 @@ impl LinearRegression {
             bias: 0.0,
             learning_rate,
             epochs,
+            parallelism: Parallelism::default(),
         }
     }
 
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
     fn predict_row(&self, row: &[f64]) -> f64 {
         vector::dot(row, &self.weights) + self.bias
     }
 
     fn compute_loss(&self, dataset: &Dataset) -> f64 {
-        let predictions: Vec<f64> = dataset.data.iter().map(|row| self.predict_row(row)).collect();
+        let predictions = self.predict(dataset);
         let n = dataset.target.len().max(1) as f64;
         predictions
             .iter()
@@ -48,24 +59,16 @@ impl LinearRegression {
 
 impl Model for LinearRegression {
     fn train(&mut self, dataset: &Dataset) -> TrainingReport {
-        let n_samples = dataset.data.len() as f64;
+        let n_samples = dataset.num_rows() as f64;
         for _ in 0..self.epochs {
-            let mut weight_gradients = vec![0.0; self.weights.len()];
-            let mut bias_gradient = 0.0;
-            for (row, target) in dataset.iter_rows() {
-                let prediction = self.predict_row(row);
-                let error = prediction - target;
-                bias_gradient += error;
-                for (idx, value) in row.iter().enumerate() {
-                    weight_gradients[idx] += error * value;
-                }
-            }
+            let (weight_gradients, bias_gradient) =
+                accumulate_gradients(dataset, self.parallelism, |row| self.predict_row(row));
             for (idx, weight) in self.weights.iter_mut().enumerate() {
                 let gradient = weight_gradients[idx] / n_samples;
-                let clipped = gradient.max(-1_000.0).min(1_000.0);
+                let clipped = gradient.clamp(-1_000.0, 1_000.0);
                 *weight -= self.learning_rate * clipped;
             }
-            let bias_grad = (bias_gradient / n_samples).max(-1_000.0).min(1_000.0);
+            let bias_grad = (bias_gradient / n_samples).clamp(-1_000.0, 1_000.0);
             self.bias -= self.learning_rate * bias_grad;
         }
         TrainingReport {
@@ -75,6 +78,16 @@ impl Model for LinearRegression {
     }
 
     fn predict(&self, dataset: &Dataset) -> Vec<f64> {
-        dataset.data.iter().map(|row| self.predict_row(row)).collect()
+        predict_rows(dataset, self.parallelism, |row| self.predict_row(row))
+    }
+}
+
+impl LinearModel for LinearRegression {
+    fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    fn bias(&self) -> f64 {
+        self.bias
     }
 }
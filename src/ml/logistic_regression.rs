@@ -1,6 +1,7 @@
 use crate::data::dataset::Dataset;
 use crate::math::vector;
-use crate::ml::{Model, TrainingReport};
+use crate::ml::parallel::{accumulate_gradients, predict_rows, Parallelism};
+use crate::ml::{LinearModel, Model, TrainingReport};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +10,9 @@ pub struct LogisticRegression {
     pub bias: f64,
     pub learning_rate: f64,
     pub epochs: usize,
+    /// Runtime setting, not part of the fitted model.
+    #[serde(skip)]
+    pub parallelism: Parallelism,
 }
 
 impl LogisticRegression {
@@ -18,9 +22,15 @@ impl LogisticRegression {
             bias: 0.0,
             learning_rate: 0.05,
             epochs: 300,
+            parallelism: Parallelism::default(),
         }
     }
 
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
     fn sigmoid(value: f64) -> f64 {
         1.0 / (1.0 + (-value).exp())
     }
@@ -33,29 +43,20 @@ impl LogisticRegression {
     fn compute_loss(&self, dataset: &Dataset) -> f64 {
         let epsilon = 1e-12;
         let mut loss = 0.0;
-        for (row, target) in dataset.iter_rows() {
-            let prediction = self.predict_probability(row);
+        for (prediction, target) in self.predict(dataset).into_iter().zip(dataset.targets()) {
             loss += -target * (prediction + epsilon).ln()
                 - (1.0 - target) * (1.0 - prediction + epsilon).ln();
         }
-        loss / dataset.data.len().max(1) as f64
+        loss / dataset.num_rows().max(1) as f64
     }
 }
 
 impl Model for LogisticRegression {
     fn train(&mut self, dataset: &Dataset) -> TrainingReport {
-        let n_samples = dataset.data.len() as f64;
+        let n_samples = dataset.num_rows() as f64;
         for _ in 0..self.epochs {
-            let mut weight_gradients = vec![0.0; self.weights.len()];
-            let mut bias_gradient = 0.0;
-            for (row, target) in dataset.iter_rows() {
-                let prediction = self.predict_probability(row);
-                let error = prediction - target;
-                bias_gradient += error;
-                for (idx, value) in row.iter().enumerate() {
-                    weight_gradients[idx] += error * value;
-                }
-            }
+            let (weight_gradients, bias_gradient) =
+                accumulate_gradients(dataset, self.parallelism, |row| self.predict_probability(row));
             for (idx, weight) in self.weights.iter_mut().enumerate() {
                 *weight -= self.learning_rate * (weight_gradients[idx] / n_samples);
             }
@@ -68,10 +69,16 @@ impl Model for LogisticRegression {
     }
 
     fn predict(&self, dataset: &Dataset) -> Vec<f64> {
-        dataset
-            .data
-            .iter()
-            .map(|row| self.predict_probability(row))
-            .collect()
+        predict_rows(dataset, self.parallelism, |row| self.predict_probability(row))
+    }
+}
+
+impl LinearModel for LogisticRegression {
+    fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    fn bias(&self) -> f64 {
+        self.bias
     }
 }
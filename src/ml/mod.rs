@@ -1,5 +1,10 @@
 pub mod linear_regression;
 pub mod logistic_regression;
+pub mod parallel;
+
+use crate::ml::linear_regression::LinearRegression;
+use crate::ml::logistic_regression::LogisticRegression;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub struct TrainingReport {
@@ -11,3 +16,92 @@ pub trait Model {
     fn train(&mut self, dataset: &crate::data::dataset::Dataset) -> TrainingReport;
     fn predict(&self, dataset: &crate::data::dataset::Dataset) -> Vec<f64>;
 }
+
+/// Models whose predictions are a weighted sum of the input features.
+pub trait LinearModel {
+    fn weights(&self) -> &[f64];
+    fn bias(&self) -> f64;
+}
+
+/// Any trainable model, tagged by kind when serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Estimator {
+    LinearRegression(LinearRegression),
+    LogisticRegression(LogisticRegression),
+}
+
+impl Estimator {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Estimator::LinearRegression(_) => "linear_regression",
+            Estimator::LogisticRegression(_) => "logistic_regression",
+        }
+    }
+
+    /// Fresh model of the same kind and hyperparameters sized for `num_features`.
+    pub fn reset(&self, num_features: usize) -> Estimator {
+        match self {
+            Estimator::LinearRegression(model) => Estimator::LinearRegression(LinearRegression {
+                weights: vec![0.0; num_features],
+                bias: 0.0,
+                ..model.clone()
+            }),
+            Estimator::LogisticRegression(model) => {
+                Estimator::LogisticRegression(LogisticRegression {
+                    weights: vec![0.0; num_features],
+                    bias: 0.0,
+                    ..model.clone()
+                })
+            }
+        }
+    }
+
+    fn inner(&self) -> &dyn Model {
+        match self {
+            Estimator::LinearRegression(model) => model,
+            Estimator::LogisticRegression(model) => model,
+        }
+    }
+}
+
+impl Model for Estimator {
+    fn train(&mut self, dataset: &crate::data::dataset::Dataset) -> TrainingReport {
+        match self {
+            Estimator::LinearRegression(model) => model.train(dataset),
+            Estimator::LogisticRegression(model) => model.train(dataset),
+        }
+    }
+
+    fn predict(&self, dataset: &crate::data::dataset::Dataset) -> Vec<f64> {
+        self.inner().predict(dataset)
+    }
+}
+
+impl LinearModel for Estimator {
+    fn weights(&self) -> &[f64] {
+        match self {
+            Estimator::LinearRegression(model) => &model.weights,
+            Estimator::LogisticRegression(model) => &model.weights,
+        }
+    }
+
+    fn bias(&self) -> f64 {
+        match self {
+            Estimator::LinearRegression(model) => model.bias,
+            Estimator::LogisticRegression(model) => model.bias,
+        }
+    }
+}
+
+impl From<LinearRegression> for Estimator {
+    fn from(model: LinearRegression) -> Self {
+        Estimator::LinearRegression(model)
+    }
+}
+
+impl From<LogisticRegression> for Estimator {
+    fn from(model: LogisticRegression) -> Self {
+        Estimator::LogisticRegression(model)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::data::dataset::Dataset;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Controls how row-wise work is split across threads.
+///
+/// Rows are processed in `chunk_size` blocks, but only per-row work runs in
+/// parallel: results are combined in row order, so the output is bit-identical
+/// to a plain sequential loop for any thread count or chunk size. A `threads`
+/// value of 0 uses every available core.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Parallelism {
+    pub threads: usize,
+    pub chunk_size: usize,
+}
+
+impl Default for Parallelism {
+    fn default() -> Self {
+        Parallelism {
+            threads: 1,
+            chunk_size: 1024,
+        }
+    }
+}
+
+impl Parallelism {
+    pub fn with_threads(threads: usize) -> Self {
+        Parallelism {
+            threads,
+            ..Parallelism::default()
+        }
+    }
+
+    pub fn effective_threads(&self) -> usize {
+        if self.threads == 0 {
+            std::thread::available_parallelism()
+                .map(|count| count.get())
+                .unwrap_or(1)
+        } else {
+            self.threads
+        }
+    }
+}
+
+pub fn map_chunks<T, F>(len: usize, parallelism: Parallelism, func: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(Range<usize>) -> T + Sync,
+{
+    let chunk_size = parallelism.chunk_size.max(1);
+    let ranges: Vec<Range<usize>> = (0..len)
+        .step_by(chunk_size)
+        .map(|start| start..(start + chunk_size).min(len))
+        .collect();
+    let threads = parallelism.effective_threads().min(ranges.len());
+    if threads <= 1 {
+        return ranges.into_iter().map(func).collect();
+    }
+    let per_thread = ranges.len().div_ceil(threads);
+    let func = &func;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = ranges
+            .chunks(per_thread)
+            .map(|group| scope.spawn(move || group.iter().cloned().map(func).collect::<Vec<T>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("worker thread panicked"))
+            .collect()
+    })
+}
+
+pub(crate) fn predict_rows<P>(dataset: &Dataset, parallelism: Parallelism, predict: P) -> Vec<f64>
+where
+    P: Fn(&[f64]) -> f64 + Sync,
+{
+    map_chunks(dataset.num_rows(), parallelism, |rows| {
+        rows.map(|index| predict(dataset.row(index)))
+            .collect::<Vec<f64>>()
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Sums `error * x` and `error` over all rows, where `error` is `predict(row) - target`.
+/// Errors are computed in parallel and summed sequentially in row order.
+pub(crate) fn accumulate_gradients<P>(
+    dataset: &Dataset,
+    parallelism: Parallelism,
+    predict: P,
+) -> (Vec<f64>, f64)
+where
+    P: Fn(&[f64]) -> f64 + Sync,
+{
+    let errors = map_chunks(dataset.num_rows(), parallelism, |rows| {
+        rows.map(|index| predict(dataset.row(index)) - dataset.target[index])
+            .collect::<Vec<f64>>()
+    });
+    let mut weight_gradients = vec![0.0; dataset.num_features()];
+    let mut bias_gradient = 0.0;
+    for (index, error) in errors.into_iter().flatten().enumerate() {
+        bias_gradient += error;
+        for (gradient, value) in weight_gradients.iter_mut().zip(dataset.row(index)) {
+            *gradient += error * value;
+        }
+    }
+    (weight_gradients, bias_gradient)
+}
@@ -1,9 +1,15 @@
-use crate::data::cleaning::{clean_dataset, CleaningConfig};
-use crate::data::dataset::Dataset;
+use crate::data::cleaning::{remove_rows_with_missing, CleaningConfig, OutlierClipper};
+use crate::data::dataset::{Dataset, DatasetError};
+use crate::data::feature_engineering::{
+    DerivedFeatures, Discretizer, LogTransform, PolynomialFeatures, PowerTransform, SplineBasis,
+};
+use crate::data::selection::FeatureSelector;
+use crate::data::{TransformError, Transformer};
 use crate::ml::linear_regression::LinearRegression;
-use crate::ml::Model;
+use crate::ml::parallel::Parallelism;
+use crate::ml::{Estimator, LinearModel, Model, TrainingReport};
 use crate::utils::evaluation::{mean_squared_error, mean_absolute_error, root_mean_squared_error};
-use crate::utils::normalization::{normalize_z_score, NormalizationStats};
+use crate::utils::normalization::{NormalizationStats, Scaler, StandardScaler};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,19 +21,208 @@ pub struct TrainingSummary {
     pub loss: f64,
 }
 
+/// Any preprocessing step a `Pipeline` can hold, tagged by type when serialized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransformStep {
+    ClipOutliers(OutlierClipper),
+    Scale(Scaler),
+    Log(LogTransform),
+    Power(PowerTransform),
+    Discretize(Discretizer),
+    Spline(SplineBasis),
+    Derived(DerivedFeatures),
+    Polynomial(PolynomialFeatures),
+    Select(FeatureSelector),
+}
+
+impl TransformStep {
+    fn inner(&self) -> &dyn Transformer {
+        match self {
+            TransformStep::ClipOutliers(step) => step,
+            TransformStep::Scale(step) => step,
+            TransformStep::Log(step) => step,
+            TransformStep::Power(step) => step,
+            TransformStep::Discretize(step) => step,
+            TransformStep::Spline(step) => step,
+            TransformStep::Derived(step) => step,
+            TransformStep::Polynomial(step) => step,
+            TransformStep::Select(step) => step,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn Transformer {
+        match self {
+            TransformStep::ClipOutliers(step) => step,
+            TransformStep::Scale(step) => step,
+            TransformStep::Log(step) => step,
+            TransformStep::Power(step) => step,
+            TransformStep::Discretize(step) => step,
+            TransformStep::Spline(step) => step,
+            TransformStep::Derived(step) => step,
+            TransformStep::Polynomial(step) => step,
+            TransformStep::Select(step) => step,
+        }
+    }
+}
+
+impl Transformer for TransformStep {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.inner_mut().fit(dataset)
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.inner().transform(dataset)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.inner().inverse_transform(dataset)
+    }
+}
+
+macro_rules! impl_from_step {
+    ($($variant:ident($ty:ty)),*) => {
+        $(impl From<$ty> for TransformStep {
+            fn from(step: $ty) -> Self {
+                TransformStep::$variant(step)
+            }
+        })*
+    };
+}
+
+impl_from_step!(
+    ClipOutliers(OutlierClipper),
+    Scale(Scaler),
+    Log(LogTransform),
+    Power(PowerTransform),
+    Discretize(Discretizer),
+    Spline(SplineBasis),
+    Derived(DerivedFeatures),
+    Polynomial(PolynomialFeatures),
+    Select(FeatureSelector)
+);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineStep {
+    pub name: String,
+    pub step: TransformStep,
+}
+
+/// Named preprocessing steps followed by a model. Fitting learns every step's
+/// state in order on the training data; prediction replays it unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pipeline {
+    pub steps: Vec<PipelineStep>,
+    pub model: Estimator,
+    /// Drop rows with non-finite values before fitting. Prediction never
+    /// drops rows, so outputs stay aligned with the input.
+    pub drop_incomplete_rows: bool,
+    fitted: bool,
+}
+
+impl Pipeline {
+    pub fn new(model: impl Into<Estimator>) -> Self {
+        Pipeline {
+            steps: Vec::new(),
+            model: model.into(),
+            drop_incomplete_rows: true,
+            fitted: false,
+        }
+    }
+
+    pub fn with_step(mut self, name: impl Into<String>, step: impl Into<TransformStep>) -> Self {
+        self.steps.push(PipelineStep {
+            name: name.into(),
+            step: step.into(),
+        });
+        self
+    }
+
+    pub fn step(&self, name: &str) -> Option<&TransformStep> {
+        self.steps
+            .iter()
+            .find(|step| step.name == name)
+            .map(|step| &step.step)
+    }
+
+    /// Whether `fit` has completed; a failed refit leaves the pipeline unfitted.
+    pub fn is_fitted(&self) -> bool {
+        self.fitted
+    }
+
+    pub fn fit(&mut self, dataset: &Dataset) -> Result<TrainingReport, TransformError> {
+        self.fitted = false;
+        let mut current = if self.drop_incomplete_rows {
+            remove_rows_with_missing(dataset)?
+        } else {
+            dataset.clone()
+        };
+        for step in &mut self.steps {
+            current = step.step.fit_transform(&current)?;
+        }
+        self.model = self.model.reset(current.num_features());
+        let report = self.model.train(&current);
+        self.fitted = true;
+        Ok(report)
+    }
+
+    /// Runs every preprocessing step, yielding the model's input features.
+    pub fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        if !self.fitted {
+            return Err(TransformError::NotFitted);
+        }
+        let mut current = dataset.clone();
+        for step in &self.steps {
+            current = step.step.transform(&current)?;
+        }
+        Ok(current)
+    }
+
+    pub fn predict(&self, dataset: &Dataset) -> Result<Vec<f64>, TransformError> {
+        let features = self.transform(dataset)?;
+        let expected = self.model.weights().len();
+        if features.num_features() != expected {
+            return Err(DatasetError::InvalidColumnCount {
+                expected,
+                found: features.num_features(),
+            }
+            .into());
+        }
+        Ok(self.model.predict(&features))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PipelineResult {
     pub model: LinearRegression,
     pub normalized: Dataset,
     pub stats: NormalizationStats,
+    pub pipeline: Pipeline,
     pub summary: TrainingSummary,
 }
 
-pub fn train_linear_pipeline(dataset: &Dataset) -> Result<PipelineResult, crate::data::dataset::DatasetError> {
-    let cleaned = clean_dataset(dataset, CleaningConfig::default())?;
-    let (normalized, stats) = normalize_z_score(&cleaned)?;
-    let mut model = LinearRegression::new(normalized.num_features());
-    let report = model.train(&normalized);
+pub fn train_linear_pipeline(dataset: &Dataset) -> Result<PipelineResult, TransformError> {
+    train_linear_pipeline_with_parallelism(dataset, Parallelism::default())
+}
+
+pub fn train_linear_pipeline_with_parallelism(
+    dataset: &Dataset,
+    parallelism: Parallelism,
+) -> Result<PipelineResult, TransformError> {
+    let complete = remove_rows_with_missing(dataset)?;
+    let template = LinearRegression::new(0).with_parallelism(parallelism);
+    let mut pipeline = Pipeline::new(template.clone())
+        .with_step("clip", OutlierClipper::new(CleaningConfig::default()))
+        .with_step("scale", Scaler::Standard(StandardScaler::default()));
+    let report = pipeline.fit(&complete)?;
+    let clipped = pipeline.steps[0].step.transform(&complete)?;
+    let stats = NormalizationStats::z_score(&clipped);
+    let normalized = pipeline.transform(&complete)?;
+    let model = LinearRegression {
+        weights: pipeline.model.weights().to_vec(),
+        bias: pipeline.model.bias(),
+        ..template
+    };
     let predictions = model.predict(&normalized);
     let mse = mean_squared_error(&predictions, &normalized.target);
     let rmse = root_mean_squared_error(&predictions, &normalized.target);
@@ -43,6 +238,7 @@ pub fn train_linear_pipeline(dataset: &Dataset) -> Result<PipelineResult, crate:
         model,
         normalized,
         stats,
+        pipeline,
         summary,
     })
 }
@@ -0,0 +1,492 @@
+//! File-system model registry. Every registered training run gets a numbered
+//! version directory, `<root>/<name>/v<version>/`, holding the artifact
+//! (`model.json`) and a `run.json` record of how it was produced.
+
+use crate::config::AppConfig;
+use crate::data::dataset::Dataset;
+use crate::io::{load_model, save_model, ModelArtifact, TrainingConfig};
+use crate::pipeline::TrainingSummary;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub const MODEL_FILE: &str = "model.json";
+pub const RUN_FILE: &str = "run.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    None,
+    Staging,
+    Production,
+    Archived,
+}
+
+impl Stage {
+    pub fn from_name(name: &str) -> Option<Stage> {
+        match name {
+            "none" => Some(Stage::None),
+            "staging" => Some(Stage::Staging),
+            "production" => Some(Stage::Production),
+            "archived" => Some(Stage::Archived),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stage::None => "none",
+            Stage::Staging => "staging",
+            Stage::Production => "production",
+            Stage::Archived => "archived",
+        }
+    }
+
+    /// Whether at most one version of a model may hold this stage.
+    fn is_exclusive(&self) -> bool {
+        matches!(self, Stage::Staging | Stage::Production)
+    }
+}
+
+/// The data a run was trained on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetRecord {
+    pub fingerprint: String,
+    pub source: Option<String>,
+    pub rows: usize,
+    pub features: Vec<String>,
+    pub target: String,
+}
+
+impl DatasetRecord {
+    pub fn new(dataset: &Dataset, source: Option<&str>) -> Self {
+        DatasetRecord {
+            fingerprint: dataset.fingerprint(),
+            source: source.map(str::to_string),
+            rows: dataset.num_rows(),
+            features: dataset.feature_names.clone(),
+            target: dataset.target_name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub name: String,
+    pub version: u32,
+    pub stage: Stage,
+    pub model_kind: String,
+    pub parameters: TrainingConfig,
+    pub dataset: DatasetRecord,
+    pub metrics: TrainingSummary,
+    pub artifact_hash: String,
+    /// Unix seconds, copied from the artifact.
+    pub created_at: u64,
+}
+
+impl RunRecord {
+    /// Training metrics by name, in the order `compare` reports them.
+    pub fn metric_values(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("mse", self.metrics.mse),
+            ("rmse", self.metrics.rmse),
+            ("mae", self.metrics.mae),
+            ("loss", self.metrics.loss),
+            ("epochs", self.metrics.epochs as f64),
+        ]
+    }
+}
+
+/// Which version of a model to resolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelRef {
+    Version(u32),
+    Latest,
+    Stage(Stage),
+}
+
+impl ModelRef {
+    /// Parses `3`, `v3`, `latest`, `staging` or `production`.
+    pub fn parse(value: &str) -> Option<ModelRef> {
+        if value == "latest" {
+            return Some(ModelRef::Latest);
+        }
+        if let Some(stage) = Stage::from_name(value) {
+            return Some(ModelRef::Stage(stage));
+        }
+        value
+            .trim_start_matches('v')
+            .parse()
+            .ok()
+            .map(ModelRef::Version)
+    }
+}
+
+#[derive(Debug)]
+pub enum RegistryError {
+    Io {
+        path: String,
+        message: String,
+    },
+    /// A record or artifact that exists but cannot be read back.
+    Corrupt {
+        path: String,
+        message: String,
+    },
+    InvalidName(String),
+    UnknownModel(String),
+    UnknownVersion {
+        name: String,
+        version: u32,
+    },
+    NoModelInStage {
+        name: String,
+        stage: Stage,
+    },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::Io { path, message } => write!(f, "registry I/O on {path}: {message}"),
+            RegistryError::Corrupt { path, message } => {
+                write!(f, "unreadable registry entry {path}: {message}")
+            }
+            RegistryError::InvalidName(name) => write!(
+                f,
+                "invalid model name '{name}': use letters, digits, '-', '_' or '.'"
+            ),
+            RegistryError::UnknownModel(name) => write!(f, "no registered model named '{name}'"),
+            RegistryError::UnknownVersion { name, version } => {
+                write!(f, "model '{name}' has no version {version}")
+            }
+            RegistryError::NoModelInStage { name, stage } => {
+                write!(f, "model '{name}' has no version in {}", stage.name())
+            }
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+fn io_error(path: &Path, err: impl fmt::Display) -> RegistryError {
+    RegistryError::Io {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    }
+}
+
+fn corrupt(path: &Path, err: impl fmt::Display) -> RegistryError {
+    RegistryError::Corrupt {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricChange {
+    pub metric: String,
+    pub base: f64,
+    pub candidate: f64,
+    pub delta: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterChange {
+    pub parameter: String,
+    pub base: String,
+    pub candidate: String,
+}
+
+/// Differences between two versions of the same model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunComparison {
+    pub name: String,
+    pub base: u32,
+    pub candidate: u32,
+    pub metrics: Vec<MetricChange>,
+    /// Only the parameters that differ.
+    pub parameters: Vec<ParameterChange>,
+    pub same_dataset: bool,
+    pub same_features: bool,
+}
+
+impl RunComparison {
+    pub fn new(base: &RunRecord, candidate: &RunRecord) -> Self {
+        let metrics = base
+            .metric_values()
+            .into_iter()
+            .zip(candidate.metric_values())
+            .map(|((metric, base), (_, candidate))| MetricChange {
+                metric: metric.to_string(),
+                base,
+                candidate,
+                delta: candidate - base,
+            })
+            .collect();
+        let parameters = parameter_map(base)
+            .into_iter()
+            .zip(parameter_map(candidate))
+            .filter(|((_, base), (_, candidate))| base != candidate)
+            .map(|((parameter, base), (_, candidate))| ParameterChange {
+                parameter,
+                base,
+                candidate,
+            })
+            .collect();
+        RunComparison {
+            name: base.name.clone(),
+            base: base.version,
+            candidate: candidate.version,
+            metrics,
+            parameters,
+            same_dataset: base.dataset.fingerprint == candidate.dataset.fingerprint,
+            same_features: base.dataset.features == candidate.dataset.features,
+        }
+    }
+}
+
+fn parameter_map(record: &RunRecord) -> BTreeMap<String, String> {
+    let p = &record.parameters;
+    let mut map = BTreeMap::new();
+    map.insert("model_kind".to_string(), record.model_kind.clone());
+    map.insert("learning_rate".to_string(), p.learning_rate.to_string());
+    map.insert("epochs".to_string(), p.epochs.to_string());
+    map.insert(
+        "drop_incomplete_rows".to_string(),
+        p.drop_incomplete_rows.to_string(),
+    );
+    map
+}
+
+#[derive(Debug, Clone)]
+pub struct ModelRegistry {
+    root: PathBuf,
+}
+
+impl ModelRegistry {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        ModelRegistry { root: root.into() }
+    }
+
+    /// The registry under `<default_output_dir>/registry`.
+    pub fn from_config(config: &AppConfig) -> Self {
+        ModelRegistry::new(Path::new(&config.default_output_dir).join("registry"))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn model_dir(&self, name: &str) -> Result<PathBuf, RegistryError> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(RegistryError::InvalidName(name.to_string()));
+        }
+        Ok(self.root.join(name))
+    }
+
+    fn version_dir(&self, name: &str, version: u32) -> Result<PathBuf, RegistryError> {
+        Ok(self.model_dir(name)?.join(format!("v{version}")))
+    }
+
+    /// Stores `artifact` as the next version of `name`. Version directories
+    /// are claimed with `create_dir`, so concurrent registrations never share
+    /// one, and a directory whose writes fail is removed again.
+    pub fn register(
+        &self,
+        name: &str,
+        artifact: &ModelArtifact,
+        dataset: &Dataset,
+        source: Option<&str>,
+    ) -> Result<RunRecord, RegistryError> {
+        let model_dir = self.model_dir(name)?;
+        std::fs::create_dir_all(&model_dir).map_err(|err| io_error(&model_dir, err))?;
+        let mut version = self.versions(name)?.last().map_or(1, |last| last + 1);
+        let dir = loop {
+            let dir = self.version_dir(name, version)?;
+            match std::fs::create_dir(&dir) {
+                Ok(()) => break dir,
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => version += 1,
+                Err(err) => return Err(io_error(&dir, err)),
+            }
+        };
+        let record = RunRecord {
+            name: name.to_string(),
+            version,
+            stage: Stage::None,
+            model_kind: artifact.model.kind().to_string(),
+            parameters: artifact.training.clone(),
+            dataset: DatasetRecord::new(dataset, source),
+            metrics: artifact.summary.clone(),
+            artifact_hash: artifact.compute_hash(),
+            created_at: artifact.created_at,
+        };
+        let model_path = dir.join(MODEL_FILE);
+        let written = save_model(&model_path.to_string_lossy(), artifact)
+            .map_err(|err| io_error(&model_path, err))
+            .and_then(|_| self.write_record(&record));
+        if let Err(err) = written {
+            let _ = std::fs::remove_dir_all(&dir);
+            return Err(err);
+        }
+        Ok(record)
+    }
+
+    fn write_record(&self, record: &RunRecord) -> Result<(), RegistryError> {
+        let path = self
+            .version_dir(&record.name, record.version)?
+            .join(RUN_FILE);
+        let content = serde_json::to_string_pretty(record).map_err(|err| corrupt(&path, err))?;
+        std::fs::write(&path, content).map_err(|err| io_error(&path, err))
+    }
+
+    /// Registered model names, sorted.
+    pub fn models(&self) -> Result<Vec<String>, RegistryError> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = std::fs::read_dir(&self.root).map_err(|err| io_error(&self.root, err))?;
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|err| io_error(&self.root, err))?;
+            if entry.path().is_dir() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Version numbers of `name` that have a run record, ascending; empty for
+    /// an unknown model.
+    pub fn versions(&self, name: &str) -> Result<Vec<u32>, RegistryError> {
+        let dir = self.model_dir(name)?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = std::fs::read_dir(&dir).map_err(|err| io_error(&dir, err))?;
+        let mut versions = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|err| io_error(&dir, err))?;
+            let file_name = entry.file_name();
+            let version = file_name
+                .to_str()
+                .and_then(|file_name| file_name.strip_prefix('v'))
+                .and_then(|version| version.parse().ok());
+            if let Some(version) = version {
+                if entry.path().join(RUN_FILE).exists() {
+                    versions.push(version);
+                }
+            }
+        }
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    pub fn run(&self, name: &str, version: u32) -> Result<RunRecord, RegistryError> {
+        let path = self.version_dir(name, version)?.join(RUN_FILE);
+        if !path.exists() {
+            return Err(RegistryError::UnknownVersion {
+                name: name.to_string(),
+                version,
+            });
+        }
+        let content = std::fs::read_to_string(&path).map_err(|err| io_error(&path, err))?;
+        serde_json::from_str(&content).map_err(|err| corrupt(&path, err))
+    }
+
+    /// Every run of `name`, oldest first.
+    pub fn runs(&self, name: &str) -> Result<Vec<RunRecord>, RegistryError> {
+        self.versions(name)?
+            .into_iter()
+            .map(|version| self.run(name, version))
+            .collect()
+    }
+
+    /// Every run of every model.
+    pub fn list(&self) -> Result<Vec<RunRecord>, RegistryError> {
+        let mut runs = Vec::new();
+        for name in self.models()? {
+            runs.extend(self.runs(&name)?);
+        }
+        Ok(runs)
+    }
+
+    pub fn resolve(&self, name: &str, reference: ModelRef) -> Result<RunRecord, RegistryError> {
+        match reference {
+            ModelRef::Version(version) => self.run(name, version),
+            ModelRef::Latest => {
+                let latest = self.versions(name)?.last().copied();
+                let version =
+                    latest.ok_or_else(|| RegistryError::UnknownModel(name.to_string()))?;
+                self.run(name, version)
+            }
+            ModelRef::Stage(stage) => self
+                .runs(name)?
+                .into_iter()
+                .rev()
+                .find(|run| run.stage == stage)
+                .ok_or_else(|| RegistryError::NoModelInStage {
+                    name: name.to_string(),
+                    stage,
+                }),
+        }
+    }
+
+    pub fn model_path(&self, name: &str, version: u32) -> Result<PathBuf, RegistryError> {
+        Ok(self.version_dir(name, version)?.join(MODEL_FILE))
+    }
+
+    /// Loads the artifact `reference` resolves to, with its record.
+    pub fn load(
+        &self,
+        name: &str,
+        reference: ModelRef,
+    ) -> Result<(RunRecord, ModelArtifact), RegistryError> {
+        let record = self.resolve(name, reference)?;
+        let path = self.model_path(name, record.version)?;
+        let artifact = load_model(&path.to_string_lossy()).map_err(|err| corrupt(&path, err))?;
+        Ok((record, artifact))
+    }
+
+    /// Moves a version to `stage`. Staging and production hold one version at
+    /// a time, so the previous holder is archived.
+    pub fn set_stage(
+        &self,
+        name: &str,
+        version: u32,
+        stage: Stage,
+    ) -> Result<RunRecord, RegistryError> {
+        let mut record = self.run(name, version)?;
+        if stage.is_exclusive() {
+            for mut other in self.runs(name)? {
+                if other.version != version && other.stage == stage {
+                    other.stage = Stage::Archived;
+                    self.write_record(&other)?;
+                }
+            }
+        }
+        record.stage = stage;
+        self.write_record(&record)?;
+        Ok(record)
+    }
+
+    pub fn compare(
+        &self,
+        name: &str,
+        base: u32,
+        candidate: u32,
+    ) -> Result<RunComparison, RegistryError> {
+        Ok(RunComparison::new(
+            &self.run(name, base)?,
+            &self.run(name, candidate)?,
+        ))
+    }
+}
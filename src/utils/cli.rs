@@ -39,3 +39,9 @@ impl ArgParser {
             .unwrap_or_else(|| default.to_string())
     }
 }
+
+impl Default for ArgParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Clone)]
 pub struct ConfusionMatrix {
     pub true_positive: usize,
@@ -88,3 +90,42 @@ pub fn confusion_matrix(predictions: &[f64], targets: &[f64], threshold: f64) ->
     }
     matrix
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    Mse,
+    Rmse,
+    Mae,
+    /// Classification metrics threshold predictions and targets at `threshold`.
+    Accuracy,
+    Precision,
+    Recall,
+    F1,
+}
+
+impl Metric {
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Mse => "mse",
+            Metric::Rmse => "rmse",
+            Metric::Mae => "mae",
+            Metric::Accuracy => "accuracy",
+            Metric::Precision => "precision",
+            Metric::Recall => "recall",
+            Metric::F1 => "f1",
+        }
+    }
+
+    pub fn compute(self, predictions: &[f64], targets: &[f64], threshold: f64) -> f64 {
+        match self {
+            Metric::Mse => mean_squared_error(predictions, targets),
+            Metric::Rmse => root_mean_squared_error(predictions, targets),
+            Metric::Mae => mean_absolute_error(predictions, targets),
+            Metric::Accuracy => confusion_matrix(predictions, targets, threshold).accuracy(),
+            Metric::Precision => confusion_matrix(predictions, targets, threshold).precision(),
+            Metric::Recall => confusion_matrix(predictions, targets, threshold).recall(),
+            Metric::F1 => confusion_matrix(predictions, targets, threshold).f1(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Stable, dependency-free content hashing (64-bit FNV-1a). Suitable for
+//! detecting corruption and accidental changes, not for adversarial use.
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ContentHasher {
+    state: u64,
+}
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        ContentHasher { state: FNV_OFFSET }
+    }
+}
+
+impl ContentHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Writes a length prefix first so adjacent strings cannot run together.
+    pub fn write_str(&mut self, value: &str) {
+        self.write(&(value.len() as u64).to_le_bytes());
+        self.write(value.as_bytes());
+    }
+
+    /// Hashes the raw bits, so `-0.0` and each NaN payload hash distinctly.
+    pub fn write_f64(&mut self, value: f64) {
+        self.write(&value.to_bits().to_le_bytes());
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+
+    /// The hash as 16 lowercase hex digits.
+    pub fn finish_hex(&self) -> String {
+        format!("{:016x}", self.state)
+    }
+}
+
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    let mut hasher = ContentHasher::new();
+    hasher.write(bytes);
+    hasher.finish_hex()
+}
@@ -2,3 +2,4 @@ pub mod normalization;
 pub mod evaluation;
 pub mod cli;
 pub mod report;
+pub mod hashing;
@@ -1,4 +1,9 @@
 use crate::data::dataset::{Dataset, DatasetError};
+use crate::data::{TransformError, Transformer};
+use crate::math::stats::{quantiles, QuantileMethod, RunningStats};
+use serde::{Deserialize, Serialize};
+
+const MIN_SCALE: f64 = 1e-12;
 
 #[derive(Debug, Clone)]
 pub struct NormalizationStats {
@@ -10,70 +15,319 @@ pub struct NormalizationStats {
 
 impl NormalizationStats {
     pub fn z_score(dataset: &Dataset) -> Self {
-        let cols = dataset.num_features();
-        let mut mean = vec![0.0; cols];
-        let mut std_dev = vec![0.0; cols];
-        let mut min = vec![f64::MAX; cols];
-        let mut max = vec![f64::MIN; cols];
-        for row in &dataset.data {
-            for (idx, value) in row.iter().enumerate() {
-                mean[idx] += value;
-                min[idx] = min[idx].min(*value);
-                max[idx] = max[idx].max(*value);
+        let mut columns = vec![RunningStats::new(); dataset.num_features()];
+        for row in dataset.data.iter_rows() {
+            for (accumulator, value) in columns.iter_mut().zip(row) {
+                accumulator.push(*value);
             }
         }
-        for value in &mut mean {
-            *value /= dataset.data.len() as f64;
+        NormalizationStats {
+            mean: columns.iter().map(|column| column.mean()).collect(),
+            std_dev: columns
+                .iter()
+                .map(|column| column.std_dev().max(MIN_SCALE))
+                .collect(),
+            min: columns.iter().map(|column| column.min().unwrap_or(0.0)).collect(),
+            max: columns.iter().map(|column| column.max().unwrap_or(0.0)).collect(),
         }
-        for row in &dataset.data {
-            for (idx, value) in row.iter().enumerate() {
-                std_dev[idx] += (*value - mean[idx]).powi(2);
-            }
+    }
+}
+
+/// Applies `(value - offset[col]) / scale[col]` column-wise, or its inverse.
+fn affine(
+    dataset: &Dataset,
+    offset: &[f64],
+    scale: &[f64],
+    inverse: bool,
+) -> Result<Dataset, TransformError> {
+    if offset.is_empty() && dataset.num_features() > 0 {
+        return Err(TransformError::NotFitted);
+    }
+    if offset.len() != dataset.num_features() {
+        return Err(DatasetError::InvalidColumnCount {
+            expected: offset.len(),
+            found: dataset.num_features(),
         }
-        for value in &mut std_dev {
-            *value = (*value / dataset.data.len() as f64).sqrt().max(1e-12);
+        .into());
+    }
+    let mut scaled = dataset.clone();
+    for row in scaled.data.iter_rows_mut() {
+        for (idx, value) in row.iter_mut().enumerate() {
+            *value = if inverse {
+                *value * scale[idx] + offset[idx]
+            } else {
+                (*value - offset[idx]) / scale[idx]
+            };
         }
-        NormalizationStats {
-            mean,
-            std_dev,
-            min,
-            max,
+    }
+    Ok(scaled)
+}
+
+/// Z-score scaling with the population standard deviation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardScaler {
+    pub mean: Vec<f64>,
+    pub std_dev: Vec<f64>,
+}
+
+impl From<&NormalizationStats> for StandardScaler {
+    fn from(stats: &NormalizationStats) -> Self {
+        StandardScaler {
+            mean: stats.mean.clone(),
+            std_dev: stats.std_dev.clone(),
         }
     }
 }
 
-pub fn normalize_z_score(dataset: &Dataset) -> Result<(Dataset, NormalizationStats), DatasetError> {
-    let stats = NormalizationStats::z_score(dataset);
-    let mut data = dataset.data.clone();
-    for row in &mut data {
-        for (idx, value) in row.iter_mut().enumerate() {
-            *value = (*value - stats.mean[idx]) / stats.std_dev[idx];
+impl Transformer for StandardScaler {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        *self = StandardScaler::from(&NormalizationStats::z_score(dataset));
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        affine(dataset, &self.mean, &self.std_dev, false)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        affine(dataset, &self.mean, &self.std_dev, true)
+    }
+}
+
+/// Maps each column's training range onto `feature_range`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    pub feature_range: (f64, f64),
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+}
+
+impl Default for MinMaxScaler {
+    fn default() -> Self {
+        MinMaxScaler::new(0.0, 1.0)
+    }
+}
+
+impl MinMaxScaler {
+    pub fn new(lower: f64, upper: f64) -> Self {
+        MinMaxScaler {
+            feature_range: (lower, upper),
+            min: Vec::new(),
+            max: Vec::new(),
+        }
+    }
+
+    fn check_range(&self) -> Result<(), TransformError> {
+        let (lower, upper) = self.feature_range;
+        if upper <= lower || !(upper - lower).is_finite() {
+            return Err(TransformError::InvalidRange { lower, upper });
         }
+        Ok(())
+    }
+
+    fn offsets_and_scales(&self) -> Result<(Vec<f64>, Vec<f64>), TransformError> {
+        self.check_range()?;
+        let (lower, upper) = self.feature_range;
+        let scales: Vec<f64> = self
+            .min
+            .iter()
+            .zip(&self.max)
+            .map(|(min, max)| (max - min).max(MIN_SCALE) / (upper - lower))
+            .collect();
+        let offsets = self
+            .min
+            .iter()
+            .zip(&scales)
+            .map(|(min, scale)| min - lower * scale)
+            .collect();
+        Ok((offsets, scales))
     }
-    Ok((
-        Dataset::from_records(
-            dataset.feature_names.clone(),
-            dataset.target_name.clone(),
-            data,
-            dataset.target.clone(),
-        )?,
-        stats,
-    ))
 }
 
-pub fn normalize_min_max(dataset: &Dataset) -> Result<Dataset, DatasetError> {
-    let stats = NormalizationStats::z_score(dataset);
-    let mut data = dataset.data.clone();
-    for row in &mut data {
-        for (idx, value) in row.iter_mut().enumerate() {
-            let range = (stats.max[idx] - stats.min[idx]).max(1e-12);
-            *value = (*value - stats.min[idx]) / range;
+impl Transformer for MinMaxScaler {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.check_range()?;
+        let stats = NormalizationStats::z_score(dataset);
+        self.min = stats.min;
+        self.max = stats.max;
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let (offsets, scales) = self.offsets_and_scales()?;
+        affine(dataset, &offsets, &scales, false)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        let (offsets, scales) = self.offsets_and_scales()?;
+        affine(dataset, &offsets, &scales, true)
+    }
+}
+
+/// Centres on the median and scales by the inter-quantile range, so a few
+/// extreme flows do not dominate the scale.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RobustScaler {
+    pub quantile_range: (f64, f64),
+    pub median: Vec<f64>,
+    pub scale: Vec<f64>,
+}
+
+impl Default for RobustScaler {
+    fn default() -> Self {
+        RobustScaler::new(0.25, 0.75)
+    }
+}
+
+impl RobustScaler {
+    pub fn new(lower: f64, upper: f64) -> Self {
+        RobustScaler {
+            quantile_range: (lower, upper),
+            median: Vec::new(),
+            scale: Vec::new(),
         }
     }
-    Dataset::from_records(
-        dataset.feature_names.clone(),
-        dataset.target_name.clone(),
-        data,
-        dataset.target.clone(),
-    )
+}
+
+impl Transformer for RobustScaler {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        let (lower, upper) = self.quantile_range;
+        self.median.clear();
+        self.scale.clear();
+        for col in 0..dataset.num_features() {
+            let levels = quantiles(
+                &dataset.column(col).to_vec(),
+                &[lower, 0.5, upper],
+                QuantileMethod::Linear,
+            );
+            let value = |idx: usize| levels[idx].unwrap_or(0.0);
+            let range = value(2) - value(0);
+            self.median.push(value(1));
+            self.scale.push(if range > MIN_SCALE { range } else { 1.0 });
+        }
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        affine(dataset, &self.median, &self.scale, false)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        affine(dataset, &self.median, &self.scale, true)
+    }
+}
+
+/// Divides each column by its largest absolute value, preserving sparsity.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MaxAbsScaler {
+    pub max_abs: Vec<f64>,
+}
+
+impl MaxAbsScaler {
+    fn offsets(&self) -> Vec<f64> {
+        vec![0.0; self.max_abs.len()]
+    }
+}
+
+impl Transformer for MaxAbsScaler {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.max_abs = (0..dataset.num_features())
+            .map(|col| {
+                let largest = dataset
+                    .column(col)
+                    .iter()
+                    .filter(|value| value.is_finite())
+                    .fold(0.0_f64, |acc, value| acc.max(value.abs()));
+                if largest > 0.0 {
+                    largest
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+        Ok(())
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        affine(dataset, &self.offsets(), &self.max_abs, false)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        affine(dataset, &self.offsets(), &self.max_abs, true)
+    }
+}
+
+/// Any of the fitted scalers, tagged by kind when serialized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Scaler {
+    Standard(StandardScaler),
+    MinMax(MinMaxScaler),
+    Robust(RobustScaler),
+    MaxAbs(MaxAbsScaler),
+}
+
+impl Scaler {
+    /// Unfitted scaler from a name such as `zscore`, `minmax`, `robust` or `maxabs`.
+    pub fn from_name(name: &str) -> Option<Scaler> {
+        match name {
+            "zscore" | "standard" => Some(Scaler::Standard(StandardScaler::default())),
+            "minmax" => Some(Scaler::MinMax(MinMaxScaler::default())),
+            "robust" => Some(Scaler::Robust(RobustScaler::default())),
+            "maxabs" => Some(Scaler::MaxAbs(MaxAbsScaler::default())),
+            _ => None,
+        }
+    }
+
+    /// The serialized `kind` tag.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Scaler::Standard(_) => "standard",
+            Scaler::MinMax(_) => "min_max",
+            Scaler::Robust(_) => "robust",
+            Scaler::MaxAbs(_) => "max_abs",
+        }
+    }
+
+    fn inner(&self) -> &dyn Transformer {
+        match self {
+            Scaler::Standard(scaler) => scaler,
+            Scaler::MinMax(scaler) => scaler,
+            Scaler::Robust(scaler) => scaler,
+            Scaler::MaxAbs(scaler) => scaler,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn Transformer {
+        match self {
+            Scaler::Standard(scaler) => scaler,
+            Scaler::MinMax(scaler) => scaler,
+            Scaler::Robust(scaler) => scaler,
+            Scaler::MaxAbs(scaler) => scaler,
+        }
+    }
+}
+
+impl Transformer for Scaler {
+    fn fit(&mut self, dataset: &Dataset) -> Result<(), TransformError> {
+        self.inner_mut().fit(dataset)
+    }
+
+    fn transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.inner().transform(dataset)
+    }
+
+    fn inverse_transform(&self, dataset: &Dataset) -> Result<Dataset, TransformError> {
+        self.inner().inverse_transform(dataset)
+    }
+}
+
+pub fn normalize_z_score(dataset: &Dataset) -> Result<(Dataset, NormalizationStats), TransformError> {
+    let stats = NormalizationStats::z_score(dataset);
+    let normalized = StandardScaler::from(&stats).transform(dataset)?;
+    Ok((normalized, stats))
+}
+
+pub fn normalize_min_max(dataset: &Dataset) -> Result<Dataset, TransformError> {
+    MinMaxScaler::default().fit_transform(dataset)
 }
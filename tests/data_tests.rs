@@ -1,35 +1,222 @@
-use rustml::data::cleaning::{clean_dataset, CleaningConfig};
-use rustml::data::dataset::Dataset;
-use rustml::data::feature_engineering::{add_polynomial_features, FeatureEngineeringConfig};
+use rustml::data::cleaning::{
+    clean_dataset, remove_rows_with_missing, CleaningConfig, OutlierAction, OutlierClipper,
+    OutlierConfig, OutlierDetection, OutlierHandler, OutlierMethod,
+};
+use rustml::data::dataset::{Dataset, DatasetError};
+use rustml::data::feature_engineering::{
+    add_polynomial_features, polynomial_term_count, polynomial_terms, BinningStrategy,
+    DerivedFeature, DerivedFeatures, Discretizer, FeatureEngineeringConfig, LogTransform,
+    PowerMethod, PowerTransform, SplineBasis,
+};
 use rustml::data::fixtures::fixture_dataset;
-use rustml::data::synthetic::{generate_network_dataset, SyntheticConfig};
-use rustml::utils::normalization::{normalize_min_max, normalize_z_score};
+use rustml::data::synthetic::{
+    generate_link_time_series, generate_network_dataset, generate_network_traffic,
+    generate_traffic_classes, one_vs_rest, ClassificationConfig, SyntheticConfig, SyntheticError,
+    TimeSeriesConfig, TrafficClass, TrafficLabel,
+};
+use rustml::data::{TransformError, Transformer};
+use rustml::data::profiling::{
+    validate, ColumnType, DataProfile, Direction, Histogram, ProfileConfig, RuleSet,
+    ValidationRule,
+};
+use rustml::data::versioning::{diff_csv, manifest_path, DatasetManifest, Provenance};
+use rustml::data::temporal::{add_temporal_features, TemporalConfig, WarmUpPolicy};
+use rustml::math::random::DeterministicRng;
+use rustml::math::stats::{mean, pearson, skewness};
+use rustml::utils::normalization::{
+    normalize_min_max, normalize_z_score, MinMaxScaler, RobustScaler, Scaler, StandardScaler,
+};
 
 #[test]
 fn test_from_csv() {
     let dataset = Dataset::from_csv("data/iris.csv").unwrap();
-    assert_eq!(dataset.data.len(), 150);
+    assert_eq!(dataset.data.rows(), 150);
     assert_eq!(dataset.target.len(), 150);
     assert_eq!(dataset.num_features(), 4);
 }
 
 #[test]
 fn test_synthetic_dataset_generation() {
-    let dataset = generate_network_dataset(SyntheticConfig { rows: 10, seed: 1 });
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 10,
+        seed: 1,
+        ..SyntheticConfig::default()
+    });
     assert_eq!(dataset.num_rows(), 10);
     assert_eq!(dataset.num_features(), 6);
 }
 
 #[test]
 fn test_cleaning_pipeline() {
-    let dataset = generate_network_dataset(SyntheticConfig { rows: 32, seed: 2 });
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 32,
+        seed: 2,
+        ..SyntheticConfig::default()
+    });
     let cleaned = clean_dataset(&dataset, CleaningConfig::default()).unwrap();
     assert_eq!(cleaned.num_rows(), 32);
 }
 
+#[test]
+fn test_outlier_handling_methods_and_actions() {
+    let rows: Vec<Vec<f64>> = (1..=10)
+        .map(|value| vec![value as f64, 5.0])
+        .chain(std::iter::once(vec![100.0, 5.0]))
+        .collect();
+    let mut rows_with_nan = rows.clone();
+    rows_with_nan[3][1] = f64::NAN;
+    let names = vec!["a".to_string(), "b".to_string()];
+    let train = Dataset::from_records(names.clone(), "t", rows_with_nan, vec![0.0; 11]).unwrap();
+
+    let iqr = |action| {
+        let mut handler = OutlierHandler::new(OutlierConfig {
+            detection: OutlierDetection {
+                method: OutlierMethod::Iqr { k: 1.5 },
+                ..OutlierDetection::default()
+            },
+            action,
+            ..OutlierConfig::default()
+        });
+        handler.fit(&train).unwrap();
+        handler
+    };
+    // Q1 = 3.5 and Q3 = 8.5 give fences at -4 and 16; the NaN-free constant
+    // column has no spread and stays unbounded.
+    let clip = iqr(OutlierAction::Clip);
+    assert_eq!((clip.clipper.lower[0], clip.clipper.upper[0]), (-4.0, 16.0));
+    assert_eq!(
+        (clip.clipper.lower[1], clip.clipper.upper[1]),
+        (f64::MIN, f64::MAX)
+    );
+    let (clipped, report) = clip.apply(&train).unwrap();
+    assert_eq!((report.rows, report.affected_rows), (11, 1));
+    assert_eq!((report.columns[0].below, report.columns[0].above), (0, 1));
+    assert_eq!(clipped.row(10)[0], 16.0);
+    assert!(clipped.row(3)[1].is_nan());
+
+    // New data is judged by the training bounds, not its own percentiles.
+    let test = Dataset::from_records(
+        names.clone(),
+        "t",
+        vec![vec![50.0, 5.0], vec![2.0, 5.0]],
+        vec![0.0; 2],
+    )
+    .unwrap();
+    let (clipped_test, test_report) = clip.apply(&test).unwrap();
+    assert_eq!(clipped_test.row(0)[0], 16.0);
+    assert_eq!(clipped_test.row(1)[0], 2.0);
+    assert_eq!(test_report.affected_rows, 1);
+
+    let (dropped, report) = iqr(OutlierAction::Drop).apply(&train).unwrap();
+    assert_eq!((dropped.num_rows(), report.affected_rows), (10, 1));
+    let (flagged, _) = iqr(OutlierAction::Flag).apply(&train).unwrap();
+    assert_eq!(flagged.num_rows(), 11);
+    assert_eq!(flagged.feature_names.last().unwrap(), "is_outlier");
+    let flags = flagged.column(2).to_vec();
+    assert_eq!(flags.iter().sum::<f64>(), 1.0);
+    assert_eq!(flags[10], 1.0);
+
+    // Median 6 and MAD 3 put the modified z-score bounds at 6 +/- 15.57;
+    // the fixed override flags every finite value of `b`.
+    let mut columns = std::collections::BTreeMap::new();
+    columns.insert(
+        "b".to_string(),
+        OutlierMethod::Fixed {
+            min: Some(6.0),
+            max: None,
+        },
+    );
+    let mut handler = OutlierHandler::new(OutlierConfig {
+        detection: OutlierDetection {
+            method: OutlierMethod::ModifiedZScore { threshold: 3.5 },
+            columns: columns.clone(),
+        },
+        ..OutlierConfig::default()
+    });
+    handler.fit(&train).unwrap();
+    assert!((handler.clipper.upper[0] - (6.0 + 3.5 * 3.0 / 0.6745)).abs() < 1e-9);
+    let (_, report) = handler.apply(&train).unwrap();
+    assert_eq!((report.columns[0].above, report.columns[1].below), (1, 10));
+    assert_eq!(report.affected_rows, 10);
+    let restored: OutlierHandler =
+        serde_json::from_str(&serde_json::to_string(&handler).unwrap()).unwrap();
+    assert_eq!(restored, handler);
+    // Bounds are tied to the fitted column names, so a reordered file is
+    // rejected instead of being clipped with another column's bounds.
+    assert!(matches!(
+        restored.apply(&train.select_features(&[1, 0])),
+        Err(TransformError::MismatchedFeature { expected, found }) if expected == "a" && found == "b"
+    ));
+    // Bounds of different lengths are an error on load and on use, not a
+    // silently shortened clip.
+    let mut truncated = restored.clone();
+    truncated.clipper.upper.pop();
+    assert!(matches!(
+        truncated.apply(&train),
+        Err(TransformError::Dataset(DatasetError::InvalidColumnCount {
+            expected: 2,
+            found: 1
+        }))
+    ));
+    let path = std::env::temp_dir().join("rustml_truncated_outliers.json");
+    let path = path.to_str().unwrap();
+    truncated.save(path).unwrap();
+    let loaded = OutlierHandler::load(path);
+    std::fs::remove_file(path).unwrap();
+    assert!(loaded.is_err());
+
+    columns.insert("missing".to_string(), OutlierMethod::Ignore);
+    let mut unknown = OutlierHandler::new(OutlierConfig {
+        detection: OutlierDetection {
+            columns,
+            ..OutlierDetection::default()
+        },
+        ..OutlierConfig::default()
+    });
+    assert!(matches!(
+        unknown.fit(&train),
+        Err(TransformError::UnknownFeature { name }) if name == "missing"
+    ));
+    assert!(OutlierMethod::Percentile {
+        lower: 0.9,
+        upper: 0.1
+    }
+    .validate()
+    .is_err());
+
+    // Percentile fitting skips NaN instead of panicking; an all-NaN column
+    // is left unbounded.
+    let nan_column: Vec<Vec<f64>> = (0..4).map(|value| vec![value as f64, f64::NAN]).collect();
+    let nan_dataset = Dataset::from_records(names.clone(), "t", nan_column, vec![0.0; 4]).unwrap();
+    let mut clipper = OutlierClipper::new(CleaningConfig::default());
+    clipper.fit(&nan_dataset).unwrap();
+    assert_eq!((clipper.lower[0], clipper.upper[0]), (0.0, 3.0));
+    assert_eq!((clipper.lower[1], clipper.upper[1]), (f64::MIN, f64::MAX));
+
+    // The default handler reproduces `clean_dataset`, and clipper state saved
+    // before detection existed still loads.
+    let clean = Dataset::from_records(names, "t", rows, vec![0.0; 11]).unwrap();
+    let mut default = OutlierHandler::new(OutlierConfig::default());
+    let complete = remove_rows_with_missing(&clean).unwrap();
+    default.fit(&complete).unwrap();
+    let expected = clean_dataset(&clean, CleaningConfig::default()).unwrap();
+    assert_eq!(default.apply(&complete).unwrap().0.data, expected.data);
+    let legacy = r#"{"lower_percentile":0.01,"upper_percentile":0.99,"lower":[1.0],"upper":[9.0]}"#;
+    let legacy: OutlierClipper = serde_json::from_str(legacy).unwrap();
+    assert!(legacy.detection.is_none());
+    assert!(legacy.input_features.is_empty());
+    assert!(!serde_json::to_string(&legacy)
+        .unwrap()
+        .contains("detection"));
+}
+
 #[test]
 fn test_normalization_z_score() {
-    let dataset = generate_network_dataset(SyntheticConfig { rows: 20, seed: 3 });
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 20,
+        seed: 3,
+        ..SyntheticConfig::default()
+    });
     let (normalized, stats) = normalize_z_score(&dataset).unwrap();
     assert_eq!(normalized.num_features(), dataset.num_features());
     assert_eq!(stats.mean.len(), dataset.num_features());
@@ -37,23 +224,608 @@ fn test_normalization_z_score() {
 
 #[test]
 fn test_normalization_min_max() {
-    let dataset = generate_network_dataset(SyntheticConfig { rows: 20, seed: 4 });
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 20,
+        seed: 4,
+        ..SyntheticConfig::default()
+    });
     let normalized = normalize_min_max(&dataset).unwrap();
     assert_eq!(normalized.num_features(), dataset.num_features());
 }
 
 #[test]
 fn test_feature_engineering() {
-    let dataset = generate_network_dataset(SyntheticConfig { rows: 8, seed: 5 });
-    let engineered =
-        add_polynomial_features(&dataset, FeatureEngineeringConfig { polynomial_degree: 3 })
-            .unwrap();
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 8,
+        seed: 5,
+        ..SyntheticConfig::default()
+    });
+    let engineered = add_polynomial_features(
+        &dataset,
+        FeatureEngineeringConfig {
+            polynomial_degree: 3,
+            ..FeatureEngineeringConfig::default()
+        },
+    )
+    .unwrap();
     assert!(engineered.num_features() > dataset.num_features());
 }
 
+#[test]
+fn test_polynomial_interactions() {
+    let dataset = Dataset::from_records(
+        vec!["packet_rate".to_string(), "jitter".to_string(), "loss_rate".to_string()],
+        "latency".to_string(),
+        vec![vec![2.0, 3.0, 5.0]],
+        vec![1.0],
+    )
+    .unwrap();
+    let full = add_polynomial_features(
+        &dataset,
+        FeatureEngineeringConfig {
+            include_bias: true,
+            ..FeatureEngineeringConfig::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(full.num_features(), 10);
+    assert_eq!(full.feature_names[0], "bias");
+    assert_eq!(full.feature_names[4], "packet_rate^2");
+    assert_eq!(full.feature_names[5], "packet_rate*jitter");
+    assert_eq!(full.row(0), &[1.0, 2.0, 3.0, 5.0, 4.0, 6.0, 10.0, 9.0, 15.0, 25.0][..]);
+
+    let interactions = add_polynomial_features(
+        &dataset,
+        FeatureEngineeringConfig {
+            polynomial_degree: 3,
+            interaction_only: true,
+            ..FeatureEngineeringConfig::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(interactions.num_features(), 7);
+    assert_eq!(interactions.feature_names[6], "packet_rate*jitter*loss_rate");
+    assert_eq!(interactions.row(0)[6], 30.0);
+
+    let capped = add_polynomial_features(
+        &dataset,
+        FeatureEngineeringConfig {
+            max_features: Some(8),
+            ..FeatureEngineeringConfig::default()
+        },
+    );
+    assert!(matches!(
+        capped,
+        Err(TransformError::TooManyFeatures { generated: 9, limit: 8 })
+    ));
+
+    for degree in 1..=4 {
+        for interaction_only in [false, true] {
+            let config = FeatureEngineeringConfig {
+                polynomial_degree: degree,
+                interaction_only,
+                include_bias: true,
+                ..FeatureEngineeringConfig::default()
+            };
+            assert_eq!(
+                polynomial_term_count(3, config),
+                Some(polynomial_terms(3, config).len())
+            );
+        }
+    }
+    // The cap is checked by counting, before any of the ~96M terms exist.
+    let names = (0..100).map(|idx| format!("x{idx}")).collect();
+    let wide = Dataset::from_records(names, "t", vec![vec![1.0; 100]], vec![0.0]).unwrap();
+    let degree_five = FeatureEngineeringConfig {
+        polynomial_degree: 5,
+        max_features: Some(1_000),
+        ..FeatureEngineeringConfig::default()
+    };
+    assert!(matches!(
+        add_polynomial_features(&wide, degree_five),
+        Err(TransformError::TooManyFeatures {
+            generated: 96_560_645,
+            limit: 1_000
+        })
+    ));
+    assert_eq!(polynomial_term_count(usize::MAX / 2, degree_five), None);
+}
+
 #[test]
 fn test_fixture_dataset() {
     let dataset = fixture_dataset();
     assert_eq!(dataset.num_rows(), 5000);
     assert_eq!(dataset.num_features(), 6);
 }
+
+#[test]
+fn test_dataset_views() {
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 12,
+        seed: 6,
+        ..SyntheticConfig::default()
+    });
+    assert_eq!(dataset.data.as_slice().len(), 12 * 6);
+    let column = dataset.column(3);
+    assert_eq!(column.len(), 12);
+    assert_eq!(column.get(4), Some(dataset.row(4)[3]));
+
+    let slice = dataset.slice_rows(2..5).unwrap();
+    assert_eq!(slice.num_rows(), 3);
+    assert_eq!(slice.row(0), dataset.row(2));
+    assert_eq!(slice.target(2), dataset.target[4]);
+
+    let selected = dataset.select_rows(&[7, 1]).unwrap();
+    let materialized = selected.to_dataset();
+    assert_eq!(materialized.row(0), dataset.row(7));
+    assert_eq!(materialized.target, vec![dataset.target[7], dataset.target[1]]);
+    assert!(dataset.select_rows(&[12]).is_err());
+
+    let doubled = dataset.map_features(|row| row.iter().map(|value| value * 2.0).collect());
+    assert_eq!(doubled.row(5)[2], dataset.row(5)[2] * 2.0);
+    let mut width = 0;
+    let ragged = dataset.try_map_features(|row| {
+        width += 1;
+        row[..width.min(row.len())].to_vec()
+    });
+    assert!(matches!(
+        ragged,
+        Err(DatasetError::MismatchedRowLengths { .. })
+    ));
+}
+
+#[test]
+fn test_synthetic_traffic_scenarios() {
+    let config = SyntheticConfig {
+        rows: 2000,
+        seed: 21,
+        anomaly_rate: 0.0,
+        ..SyntheticConfig::default()
+    };
+    let traffic = generate_network_traffic(config);
+    assert!(traffic.labels.iter().all(|label| !label.is_anomaly()));
+    let packet_rate = traffic.dataset.column(3).to_vec();
+    let jitter = traffic.dataset.column(4).to_vec();
+    let loss_rate = traffic.dataset.column(5).to_vec();
+    assert!(pearson(&packet_rate, &jitter) > 0.5);
+    assert!(pearson(&packet_rate, &loss_rate) > 0.0);
+
+    let day = config.samples_per_day;
+    let midnight = mean(&packet_rate[..day / 8]);
+    let midday = mean(&packet_rate[day / 2 - day / 16..day / 2 + day / 16]);
+    assert!(midday > midnight);
+
+    let attacked = generate_network_traffic(SyntheticConfig {
+        anomaly_rate: 1.0,
+        ..config
+    });
+    assert!(attacked.labels.iter().all(|label| label.is_anomaly()));
+    assert!(attacked.labels.contains(&TrafficLabel::Ddos));
+    assert!(attacked.labels.contains(&TrafficLabel::LinkFailure));
+    assert!(attacked.labels.contains(&TrafficLabel::Scan));
+    assert_eq!(generate_network_traffic(config).dataset, traffic.dataset);
+}
+
+#[test]
+fn test_traffic_class_generation() {
+    let config = ClassificationConfig {
+        rows: 3000,
+        seed: 8,
+        class_weights: [0.5, 0.2, 0.1, 0.1, 0.1],
+        ..ClassificationConfig::default()
+    };
+    let dataset = generate_traffic_classes(config).unwrap();
+    assert_eq!(dataset.num_features(), 6);
+    let web = dataset.target.iter().filter(|label| **label == 0.0).count() as f64;
+    assert!((web / 3000.0 - 0.5).abs() < 0.05);
+    let binary = one_vs_rest(&dataset, TrafficClass::Attack);
+    assert_eq!(binary.target_name, "is_attack");
+    assert!(binary.target.iter().all(|label| *label == 0.0 || *label == 1.0));
+
+    for class_weights in [
+        [0.0; 5],
+        [-1.0, 0.0, 0.0, 0.0, 0.0],
+        [f64::NAN, 1.0, 1.0, 1.0, 1.0],
+    ] {
+        let invalid = ClassificationConfig {
+            class_weights,
+            ..config
+        };
+        assert!(matches!(
+            generate_traffic_classes(invalid),
+            Err(SyntheticError::InvalidClassWeights(_))
+        ));
+    }
+    let empty = ClassificationConfig { rows: 0, ..config };
+    assert_eq!(
+        generate_traffic_classes(empty).unwrap_err(),
+        SyntheticError::ZeroCount("rows")
+    );
+}
+
+#[test]
+fn test_link_time_series_generation() {
+    let config = TimeSeriesConfig {
+        links: 3,
+        steps: 100,
+        trend: 0.2,
+        seasonal_amplitude: 0.0,
+        ..TimeSeriesConfig::default()
+    };
+    let series = generate_link_time_series(config).unwrap();
+    assert_eq!(series.num_rows(), 300);
+    let timestamps = series.column(0).to_vec();
+    assert!(timestamps.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(series.row(4)[1], 1.0);
+    let first_link: Vec<f64> = series
+        .iter_rows()
+        .filter(|(row, _)| row[1] == 0.0)
+        .map(|(_, utilization)| utilization)
+        .collect();
+    assert!(mean(&first_link[80..]) > mean(&first_link[..20]));
+
+    let no_links = TimeSeriesConfig { links: 0, ..config };
+    assert_eq!(
+        generate_link_time_series(no_links).unwrap_err(),
+        SyntheticError::ZeroCount("links")
+    );
+    let no_steps = TimeSeriesConfig { steps: 0, ..config };
+    assert_eq!(
+        generate_link_time_series(no_steps).unwrap_err(),
+        SyntheticError::ZeroCount("steps")
+    );
+}
+
+#[test]
+fn test_feature_transformers() {
+    let mut rng = DeterministicRng::new(21);
+    let rows: Vec<Vec<f64>> = (0..400)
+        .map(|_| {
+            let packets = rng.gen_range(1.0, 50.0);
+            vec![rng.log_normal(3.0, 0.8), rng.normal(0.0, 2.0), packets, packets * 800.0]
+        })
+        .collect();
+    let names = ["flow_duration", "jitter_delta", "packet_rate", "src_bytes"];
+    let dataset = Dataset::from_records(
+        names.iter().map(|name| name.to_string()).collect(),
+        "latency",
+        rows,
+        vec![0.0; 400],
+    )
+    .unwrap();
+
+    let mut log = LogTransform::new(&["flow_duration"]);
+    let logged = log.fit_transform(&dataset).unwrap();
+    assert!(skewness(&logged.column(0).to_vec()).abs() < 0.3);
+    assert!(LogTransform::new(&["jitter_delta"]).fit(&dataset).is_err());
+
+    let mut box_cox = PowerTransform::new(&["flow_duration"], PowerMethod::BoxCox);
+    assert!(matches!(box_cox.transform(&dataset), Err(TransformError::NotFitted)));
+    box_cox.fit(&dataset).unwrap();
+    assert!(box_cox.lambdas[0].abs() < 0.15);
+    let mut yeo_johnson = PowerTransform::new(&["jitter_delta"], PowerMethod::YeoJohnson);
+    let transformed = yeo_johnson.fit_transform(&dataset).unwrap();
+    let restored = yeo_johnson.inverse_transform(&transformed).unwrap();
+    for (original, roundtrip) in dataset.column(1).iter().zip(restored.column(1).iter()) {
+        assert!((original - roundtrip).abs() < 1e-9);
+    }
+
+    let mut discretizer = Discretizer::new(&["packet_rate"], 4, BinningStrategy::Quantile);
+    let quartiles = discretizer.fit_transform(&dataset).unwrap();
+    let top_bin = quartiles.column(2).iter().filter(|bin| *bin == 3.0).count();
+    assert!((95..=105).contains(&top_bin));
+    let midpoints = discretizer.inverse_transform(&quartiles).unwrap();
+    let edges = &discretizer.edges[0];
+    assert_eq!(midpoints.row(0)[2], {
+        let bin = quartiles.row(0)[2] as usize;
+        (edges[bin] + edges[bin + 1]) / 2.0
+    });
+
+    let mut splines = SplineBasis::new(&["packet_rate"], 5, 3);
+    let expanded = splines.fit_transform(&dataset).unwrap();
+    assert_eq!(expanded.num_features(), 4 + 7);
+    assert_eq!(expanded.feature_names[4], "packet_rate_bs0");
+    for row in expanded.data.iter_rows() {
+        let total: f64 = row[4..].iter().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+    assert_eq!(splines.inverse_transform(&expanded).unwrap(), dataset);
+
+    let mut derived = DerivedFeatures::new(vec![
+        DerivedFeature::ratio("src_bytes", "packet_rate").named("bytes_per_packet")
+    ]);
+    derived.fit(&dataset).unwrap();
+    let json = serde_json::to_string(&derived).unwrap();
+    let restored: DerivedFeatures = serde_json::from_str(&json).unwrap();
+    let with_ratio = restored.transform(&dataset).unwrap();
+    assert_eq!(with_ratio.feature_index("bytes_per_packet"), Some(4));
+    assert!((with_ratio.row(0)[4] - 800.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_temporal_features() {
+    let series = generate_link_time_series(TimeSeriesConfig {
+        links: 2,
+        steps: 30,
+        ..TimeSeriesConfig::default()
+    })
+    .unwrap();
+    let config = TemporalConfig {
+        time_column: Some("timestamp".to_string()),
+        group_column: Some("link_id".to_string()),
+        columns: vec!["packet_rate".to_string()],
+        lags: vec![1, 2],
+        target_lags: vec![1],
+        windows: vec![4],
+        ewma_alphas: vec![0.5],
+        deltas: true,
+        rates: true,
+        calendar: true,
+        ..TemporalConfig::default()
+    };
+    assert_eq!(config.warm_up_rows(), 3);
+    let features = add_temporal_features(&series, &config).unwrap();
+    assert_eq!(features.num_rows(), 2 * (30 - 3));
+    let lag = features.feature_index("packet_rate_lag2").unwrap();
+    let mean4 = features.feature_index("packet_rate_mean4").unwrap();
+    let rate = features.feature_index("packet_rate_rate").unwrap();
+    let target_lag = features.feature_index("utilization_lag1").unwrap();
+    // Row 0 of the output is link 0 at step 3; link 1 rows interleave.
+    let original: Vec<f64> = series.column(2).iter().step_by(2).collect();
+    let row = features.row(0);
+    assert_eq!(row[1], 0.0);
+    assert_eq!(row[lag], original[1]);
+    assert!((row[mean4] - mean(&original[..4])).abs() < 1e-9);
+    assert!((row[rate] - (original[3] - original[2]) / 300.0).abs() < 1e-9);
+    assert_eq!(row[target_lag], series.target[4]);
+    assert!(features.feature_index("day_of_week").is_some());
+
+    let partial = add_temporal_features(
+        &series,
+        &TemporalConfig {
+            warm_up: WarmUpPolicy::Partial,
+            ..config.clone()
+        },
+    )
+    .unwrap();
+    assert_eq!(partial.num_rows(), 60);
+    assert!(partial.row(0)[lag].is_nan());
+    assert_eq!(partial.row(0)[mean4], series.row(0)[2]);
+
+    let untimed = TemporalConfig {
+        time_column: None,
+        ..config
+    };
+    assert!(matches!(
+        add_temporal_features(&series, &untimed),
+        Err(TransformError::MissingTimestamp)
+    ));
+}
+
+#[test]
+fn test_scalers_fit_transform_and_invert() {
+    let train = generate_network_dataset(SyntheticConfig {
+        rows: 200,
+        seed: 9,
+        ..SyntheticConfig::default()
+    });
+    let test = generate_network_dataset(SyntheticConfig {
+        rows: 50,
+        seed: 10,
+        ..SyntheticConfig::default()
+    });
+    for name in ["zscore", "minmax", "robust", "maxabs"].iter() {
+        let mut scaler = Scaler::from_name(name).unwrap();
+        assert!(matches!(scaler.transform(&test), Err(TransformError::NotFitted)));
+        let scaled_train = scaler.fit_transform(&train).unwrap();
+        let json = serde_json::to_string(&scaler).unwrap();
+        let restored: Scaler = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, scaler);
+        let scaled_test = restored.transform(&test).unwrap();
+        let roundtrip = restored.inverse_transform(&scaled_test).unwrap();
+        for (original, value) in test.data.as_slice().iter().zip(roundtrip.data.as_slice()) {
+            assert!((original - value).abs() < 1e-6 * original.abs().max(1.0));
+        }
+        if *name == "minmax" {
+            let column = scaled_train.column(0).to_vec();
+            assert_eq!(rustml::math::stats::min(&column), Some(0.0));
+            assert!((rustml::math::stats::max(&column).unwrap() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    let (_, stats) = normalize_z_score(&train).unwrap();
+    let replayed = StandardScaler::from(&stats).transform(&test).unwrap();
+    let mut fitted = StandardScaler::default();
+    fitted.fit(&train).unwrap();
+    assert_eq!(fitted.transform(&test).unwrap(), replayed);
+
+    let mut robust = RobustScaler::default();
+    let scaled = robust.fit_transform(&train).unwrap();
+    let median = rustml::math::stats::median(&scaled.column(2).to_vec()).unwrap();
+    assert!(median.abs() < 1e-9);
+    let narrow =
+        Dataset::from_records(vec!["a".to_string()], "t", vec![vec![1.0]], vec![0.0]).unwrap();
+    assert!(matches!(
+        robust.transform(&narrow),
+        Err(TransformError::Dataset(DatasetError::InvalidColumnCount {
+            expected: 6,
+            found: 1
+        }))
+    ));
+
+    let invalid_ranges = [
+        (1.0, 1.0),
+        (1.0, 0.0),
+        (0.0, f64::INFINITY),
+        (f64::NAN, 1.0),
+    ];
+    for &(lower, upper) in invalid_ranges.iter() {
+        let mut minmax = MinMaxScaler::new(lower, upper);
+        assert!(matches!(
+            minmax.fit(&train),
+            Err(TransformError::InvalidRange { .. })
+        ));
+        let mut fitted = MinMaxScaler::default();
+        fitted.fit(&train).unwrap();
+        fitted.feature_range = (lower, upper);
+        assert!(matches!(
+            fitted.transform(&test),
+            Err(TransformError::InvalidRange { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_dataset_fingerprints_manifests_and_diffs() {
+    let iris = Dataset::from_csv("data/iris.csv").unwrap();
+    assert_eq!(iris.fingerprint(), iris.clone().fingerprint());
+    let mut renamed = iris.clone();
+    renamed.feature_names[0] = "sepal".to_string();
+    assert_eq!(renamed.content_hash(), iris.content_hash());
+    assert_ne!(renamed.schema_hash(), iris.schema_hash());
+    let mut edited = iris.clone();
+    edited.target[10] += 1.0;
+    assert_eq!(edited.schema_hash(), iris.schema_hash());
+    assert_ne!(edited.fingerprint(), iris.fingerprint());
+
+    let dir = std::env::temp_dir().join("rustml_versioning_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let raw = dir.join("raw.csv").to_string_lossy().into_owned();
+    iris.to_csv(&raw).unwrap();
+    let provenance = Provenance {
+        source: Some("data/iris.csv".to_string()),
+        ..Provenance::default()
+    };
+    let mut first = DatasetManifest::new(&iris, provenance);
+    first.provenance.transformations.push("copy".to_string());
+    first.save_beside(&raw).unwrap();
+    let (scaled, _) = normalize_z_score(&iris).unwrap();
+    let manifest = DatasetManifest::derived(&scaled, &raw, &iris, "scale:standard");
+    assert_eq!(manifest.provenance.transformations, vec!["copy", "scale:standard"]);
+    assert_eq!(manifest.provenance.parent, Some(iris.fingerprint()));
+    assert!(manifest.matches(&scaled));
+    assert!(!manifest.matches(&iris));
+    assert_eq!(manifest.rows, 150);
+    assert_eq!(manifest.columns.len(), 5);
+    assert!(manifest.columns[0].mean.unwrap().abs() < 1e-9);
+    let reloaded = DatasetManifest::load(&manifest_path(&raw)).unwrap();
+    assert_eq!(reloaded, first);
+    assert_eq!(manifest.provenance.history_error, None);
+    let stale = DatasetManifest::derived(&scaled, &raw, &edited, "scale:standard");
+    assert_eq!(stale.provenance.transformations, vec!["scale:standard"]);
+    assert!(stale.provenance.history_error.is_some());
+    std::fs::write(manifest_path(&raw), "{").unwrap();
+    let unreadable = DatasetManifest::derived(&scaled, &raw, &iris, "scale:standard");
+    assert_eq!(
+        unreadable.provenance.transformations,
+        vec!["scale:standard"]
+    );
+    assert!(unreadable
+        .provenance
+        .history_error
+        .unwrap()
+        .starts_with("failed to load"));
+    let untracked = dir.join("untracked.csv").to_string_lossy().into_owned();
+    let fresh = DatasetManifest::derived(&scaled, &untracked, &iris, "scale:standard");
+    assert_eq!(fresh.provenance.history_error, None);
+    assert!(!serde_json::to_string(&fresh)
+        .unwrap()
+        .contains("history_error"));
+
+    let old = dir.join("old.csv");
+    let new = dir.join("new.csv");
+    std::fs::write(&old, "id,a,b\n1,1.0,x\n2,2,y\n3,3,z\n").unwrap();
+    std::fs::write(&new, "id,a,c\n1,1,p\n3,4,q\n4,5,r\n").unwrap();
+    let (old, new) = (old.to_str().unwrap(), new.to_str().unwrap());
+    let diff = diff_csv(old, new, Some("id")).unwrap();
+    assert_eq!(diff.added_columns, vec!["c"]);
+    assert_eq!(diff.removed_columns, vec!["b"]);
+    assert_eq!(diff.removed_rows, vec!["2"]);
+    assert_eq!(diff.added_rows, vec!["4"]);
+    assert_eq!(diff.unchanged_rows, 1);
+    assert_eq!(diff.changed_rows.len(), 1);
+    assert_eq!(diff.changed_rows[0].key, "3");
+    assert_eq!(diff.changed_rows[0].cells[0].after, "4");
+    assert!(diff_csv(old, old, None).unwrap().is_empty());
+    assert!(diff_csv(old, new, Some("missing")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_profiling_and_validation_rules() {
+    let dataset = Dataset::from_records(
+        vec!["flag".to_string(), "count".to_string(), "load".to_string()],
+        "latency",
+        vec![
+            vec![0.0, 3.0, 0.5],
+            vec![1.0, 0.0, f64::NAN],
+            vec![1.0, -2.0, 2.5],
+            vec![0.0, 5.0, 3.5],
+        ],
+        vec![1.0, 2.0, 3.0, 4.0],
+    )
+    .unwrap();
+    let profile = DataProfile::new(&dataset, &ProfileConfig::default());
+    assert_eq!(profile.rows, 4);
+    let flag = profile.column("flag").unwrap();
+    assert_eq!(flag.column_type, ColumnType::Binary);
+    assert_eq!(flag.distinct, 2);
+    assert_eq!(flag.zeros, 2);
+    let count = profile.column("count").unwrap();
+    assert_eq!(count.column_type, ColumnType::Integer);
+    assert_eq!(count.negatives, 1);
+    assert_eq!(count.min, Some(-2.0));
+    let load = profile.column("load").unwrap();
+    assert_eq!(load.column_type, ColumnType::Continuous);
+    assert_eq!(load.missing, 1);
+    assert_eq!(load.max, Some(3.5));
+    assert!((load.target_correlation.unwrap() - 1.0).abs() < 1e-12);
+    assert_eq!(load.quantiles[2].value, 2.5);
+    let target = profile.column("latency").unwrap();
+    assert!(target.is_target);
+    assert_eq!(target.target_correlation, None);
+    assert_eq!(target.histogram.as_ref().unwrap().counts.iter().sum::<usize>(), 4);
+
+    let histogram = Histogram::new(&[0.0, 1.0, 2.0, 3.0, 4.0], 4).unwrap();
+    assert_eq!(histogram.edges, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(histogram.counts, vec![1, 1, 1, 2]);
+    assert!(Histogram::new(&[f64::NAN], 4).is_none());
+
+    let rules: RuleSet = serde_json::from_str(
+        r#"{"rules": [
+            {"rule": "non_null", "column": "load"},
+            {"rule": "range", "column": "count", "min": 0},
+            {"rule": "monotonic", "column": "latency", "direction": "increasing", "strict": true},
+            {"rule": "monotonic", "column": "load", "direction": "decreasing"},
+            {"rule": "non_null", "column": "missing"}
+        ]}"#,
+    )
+    .unwrap();
+    let report = rules.validate(&dataset);
+    assert_eq!(report.rules_checked, 5);
+    let columns: Vec<&str> = report
+        .violations
+        .iter()
+        .map(|violation| violation.column.as_str())
+        .collect();
+    assert_eq!(columns, vec!["load", "count", "load", "missing"]);
+    assert_eq!(report.violations[0].rows, vec![1]);
+    assert_eq!(report.violations[1].rows, vec![2]);
+    assert_eq!(report.violations[2].count, 2);
+    assert!(!report.passed());
+    let monotonic = ValidationRule::Monotonic {
+        column: "flag".to_string(),
+        direction: Direction::Increasing,
+        strict: false,
+    };
+    assert_eq!(validate(&dataset, &[monotonic]).violations[0].rows, vec![3]);
+    let unknown = r#"{"rules": [{"rule": "unique", "column": "x"}]}"#;
+    assert!(serde_json::from_str::<RuleSet>(unknown).is_err());
+
+    let iris = Dataset::from_csv("data/iris.csv").unwrap();
+    assert!(RuleSet::load("configs/iris_rules.json")
+        .unwrap()
+        .validate(&iris)
+        .passed());
+}
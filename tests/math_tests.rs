@@ -0,0 +1,329 @@
+use rustml::math::hypothesis::{
+    bootstrap_confidence_interval, chi_square_test, corrected_resampled_t_test, ks_two_sample,
+    mann_whitney_u, mcnemar_test, paired_t_test, welch_t_test, HypothesisError,
+};
+use rustml::math::linalg::{cholesky, inverse, qr, solve, svd, symmetric_eigen};
+use rustml::math::matrix::{Matrix, MatrixError};
+use rustml::math::random::DeterministicRng;
+use rustml::math::special::{chi_square_sf, ln_gamma, normal_cdf, student_t_cdf};
+use rustml::math::stats::{
+    correlation, correlation_matrix, covariance, covariance_matrix, kendall, kurtosis, max, mean,
+    median, min, mode, pearson, quantile, ranks, skewness, spearman, variance, weighted_mean,
+    weighted_median, CorrelationMethod, QuantileMethod, RunningStats,
+};
+use rustml::utils::evaluation::mean_absolute_error;
+
+fn assert_close(a: &Matrix, b: &Matrix, tolerance: f64) {
+    assert_eq!(a.shape(), b.shape());
+    for (x, y) in a.as_slice().iter().zip(b.as_slice()) {
+        assert!((x - y).abs() < tolerance, "{} != {}", x, y);
+    }
+}
+
+fn spd_matrix() -> Matrix {
+    Matrix::from_rows(&[
+        vec![4.0, 12.0, -16.0],
+        vec![12.0, 37.0, -43.0],
+        vec![-16.0, -43.0, 98.0],
+    ])
+    .unwrap()
+}
+
+#[test]
+fn test_matrix_products_and_transpose() {
+    let a = Matrix::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+    let product = a.matmul(&a.transpose()).unwrap();
+    assert_eq!(product.as_slice(), &[14.0, 32.0, 32.0, 77.0]);
+    assert_eq!(a.mul_vector(&[1.0, 0.0, -1.0]).unwrap(), vec![-2.0, -2.0]);
+    assert!(matches!(
+        a.matmul(&a),
+        Err(MatrixError::DimensionMismatch { .. })
+    ));
+
+    let json = serde_json::to_string(&a).unwrap();
+    assert_eq!(serde_json::from_str::<Matrix>(&json).unwrap(), a);
+    let short = r#"{"rows": 2, "cols": 3, "data": [1.0, 2.0]}"#;
+    assert!(serde_json::from_str::<Matrix>(short).is_err());
+    let overflow = format!(r#"{{"rows": {}, "cols": 2, "data": []}}"#, usize::MAX);
+    assert!(serde_json::from_str::<Matrix>(&overflow).is_err());
+}
+
+#[test]
+fn test_lu_solve_and_inverse() {
+    let a = Matrix::from_rows(&[
+        vec![0.0, 2.0, 1.0],
+        vec![1.0, 1.0, 0.0],
+        vec![3.0, 0.0, 1.0],
+    ])
+    .unwrap();
+    let x = solve(&a, &[5.0, 3.0, 4.0]).unwrap();
+    let expected = [1.0, 2.0, 1.0];
+    for (value, target) in x.iter().zip(expected.iter()) {
+        assert!((value - target).abs() < 1e-10);
+    }
+    let inv = inverse(&a).unwrap();
+    assert_close(&a.matmul(&inv).unwrap(), &Matrix::identity(3), 1e-10);
+    let singular = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+    assert_eq!(inverse(&singular).unwrap_err(), MatrixError::Singular);
+}
+
+#[test]
+fn test_cholesky_and_qr_reconstruct() {
+    let a = spd_matrix();
+    let lower = cholesky(&a).unwrap();
+    assert_close(&lower.matmul(&lower.transpose()).unwrap(), &a, 1e-9);
+
+    let tall = Matrix::from_rows(&[
+        vec![1.0, 2.0],
+        vec![3.0, 4.0],
+        vec![5.0, 7.0],
+        vec![-1.0, 0.5],
+    ])
+    .unwrap();
+    let decomposition = qr(&tall);
+    assert_close(
+        &decomposition.q.matmul(&decomposition.r).unwrap(),
+        &tall,
+        1e-10,
+    );
+    assert_close(
+        &decomposition
+            .q
+            .transpose()
+            .matmul(&decomposition.q)
+            .unwrap(),
+        &Matrix::identity(2),
+        1e-10,
+    );
+}
+
+#[test]
+fn test_symmetric_eigen_decomposition() {
+    let a = spd_matrix();
+    let eigen = symmetric_eigen(&a).unwrap();
+    assert!(eigen.values.windows(2).all(|pair| pair[0] >= pair[1]));
+    let reconstructed = eigen
+        .vectors
+        .matmul(&Matrix::from_diagonal(&eigen.values))
+        .unwrap()
+        .matmul(&eigen.vectors.transpose())
+        .unwrap();
+    assert_close(&reconstructed, &a, 1e-8);
+}
+
+#[test]
+fn test_svd_reconstruct_and_pseudo_inverse() {
+    let wide = Matrix::from_rows(&[vec![3.0, 2.0, 2.0], vec![2.0, 3.0, -2.0]]).unwrap();
+    let decomposition = svd(&wide).unwrap();
+    assert!((decomposition.singular_values[0] - 5.0).abs() < 1e-10);
+    assert!((decomposition.singular_values[1] - 3.0).abs() < 1e-10);
+    let reconstructed = decomposition
+        .u
+        .matmul(&Matrix::from_diagonal(&decomposition.singular_values))
+        .unwrap()
+        .matmul(&decomposition.v.transpose())
+        .unwrap();
+    assert_close(&reconstructed, &wide, 1e-10);
+    let pinv = decomposition.pseudo_inverse(1e-12);
+    assert_close(&wide.matmul(&pinv).unwrap(), &Matrix::identity(2), 1e-10);
+}
+
+#[test]
+fn test_order_statistics() {
+    let values = [7.0, 1.0, 3.0, 3.0, 10.0, f64::NAN];
+    assert_eq!(median(&values), Some(3.0));
+    assert_eq!(
+        quantile(&[1.0, 2.0, 3.0, 4.0], 0.5, QuantileMethod::Linear),
+        Some(2.5)
+    );
+    assert_eq!(
+        quantile(&[1.0, 2.0, 3.0, 4.0], 0.5, QuantileMethod::Lower),
+        Some(2.0)
+    );
+    assert_eq!(
+        quantile(&[1.0, 2.0, 3.0, 4.0], 0.5, QuantileMethod::Higher),
+        Some(3.0)
+    );
+    assert_eq!(
+        quantile(&[1.0, 2.0, 3.0, 4.0], 0.5, QuantileMethod::Midpoint),
+        Some(2.5)
+    );
+    assert_eq!(mode(&values), Some(3.0));
+    assert_eq!(min(&[]), None);
+    assert_eq!(max(&[2.0, -1.0]), Some(2.0));
+    assert_eq!(
+        weighted_median(&[1.0, 2.0, 3.0], &[1.0, 1.0, 5.0]),
+        Some(3.0)
+    );
+    assert!((weighted_mean(&[1.0, 3.0], &[3.0, 1.0]) - 1.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_shape_and_correlation_statistics() {
+    let symmetric = [1.0, 2.0, 3.0, 4.0, 5.0];
+    assert!(skewness(&symmetric).abs() < 1e-12);
+    assert!((kurtosis(&symmetric) + 1.3).abs() < 1e-12);
+    assert!(skewness(&[1.0, 1.0, 1.0, 10.0]) > 0.0);
+
+    let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [1.0, 4.0, 9.0, 16.0, 25.0];
+    assert!((spearman(&x, &y) - 1.0).abs() < 1e-12);
+    assert!((kendall(&x, &y) - 1.0).abs() < 1e-12);
+    assert!(pearson(&x, &y) < 1.0);
+    assert_eq!(ranks(&[10.0, 20.0, 10.0]), vec![1.5, 3.0, 1.5]);
+    assert!(std::panic::catch_unwind(|| covariance(&x, &y[..4])).is_err());
+    for method in [
+        CorrelationMethod::Pearson,
+        CorrelationMethod::Spearman,
+        CorrelationMethod::Kendall,
+    ] {
+        assert!(std::panic::catch_unwind(|| correlation(&x, &y[..4], method)).is_err());
+    }
+
+    let data = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.5]]).unwrap();
+    let covariance = covariance_matrix(&data);
+    assert!((covariance[(0, 0)] - 2.0 / 3.0).abs() < 1e-12);
+    let correlation = correlation_matrix(&data, CorrelationMethod::Spearman);
+    assert_eq!(correlation[(0, 1)], 1.0);
+    assert_eq!(correlation[(1, 0)], 1.0);
+}
+
+#[test]
+fn test_running_stats_matches_batch() {
+    let values: Vec<f64> = (0..100).map(|i| 1e9 + (i as f64) * 0.5).collect();
+    let mut left = RunningStats::new();
+    left.extend(values[..40].iter().cloned());
+    let mut right = RunningStats::new();
+    right.extend(values[40..].iter().cloned());
+    left.merge(&right);
+    assert_eq!(left.count(), 100);
+    assert!((left.mean() - mean(&values)).abs() < 1e-6);
+    assert!((left.variance() - variance(&values)).abs() < 1e-6);
+    assert_eq!(left.min(), Some(1e9));
+    assert_eq!(RunningStats::new().max(), None);
+}
+
+#[test]
+fn test_distribution_functions() {
+    assert!((normal_cdf(1.959963985) - 0.975).abs() < 1e-7);
+    assert!((2.0 * student_t_cdf(-2.228138852, 10.0) - 0.05).abs() < 1e-7);
+    assert!((chi_square_sf(3.841458821, 1.0) - 0.05).abs() < 1e-7);
+    assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-10);
+}
+
+#[test]
+fn test_parametric_hypothesis_tests() {
+    let welch = welch_t_test(&[1.0, 2.0, 3.0, 4.0, 5.0], &[2.0, 4.0, 6.0, 8.0, 10.0]).unwrap();
+    assert!((welch.statistic + 1.897366596).abs() < 1e-8);
+    assert!((welch.degrees_of_freedom.unwrap() - 5.882352941).abs() < 1e-8);
+    assert!(welch.p_value > 0.05 && welch.p_value < 0.2);
+
+    let chi = chi_square_test(
+        &[16.0, 18.0, 16.0, 14.0, 12.0, 12.0],
+        &[16.0, 16.0, 16.0, 16.0, 16.0, 8.0],
+    )
+    .unwrap();
+    assert!((chi.statistic - 3.5).abs() < 1e-12);
+    assert!((chi.p_value - 0.6233876277).abs() < 1e-8);
+
+    assert_eq!(
+        paired_t_test(&[1.0, 2.0], &[1.0, 2.0]).unwrap_err(),
+        HypothesisError::ZeroVariance
+    );
+    let corrected =
+        corrected_resampled_t_test(&[0.9, 0.8, 0.85, 0.95], &[0.7, 0.75, 0.8, 0.7], 0.25).unwrap();
+    let naive = paired_t_test(&[0.9, 0.8, 0.85, 0.95], &[0.7, 0.75, 0.8, 0.7]).unwrap();
+    assert!(corrected.p_value > naive.p_value);
+}
+
+#[test]
+fn test_nonparametric_and_model_comparison_tests() {
+    let low = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let high = [6.0, 7.0, 8.0, 9.0, 10.0, 11.0];
+    let ks = ks_two_sample(&low, &high).unwrap();
+    assert_eq!(ks.statistic, 1.0);
+    assert!(ks.p_value < 0.05);
+    let mw = mann_whitney_u(&low, &high).unwrap();
+    assert_eq!(mw.statistic, 0.0);
+    assert!(mw.p_value < 0.05);
+    assert!(mann_whitney_u(&low, &low).unwrap().p_value > 0.9);
+
+    let targets = [1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0];
+    let perfect = targets;
+    let flipped = [0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+    let mcnemar = mcnemar_test(&perfect, &flipped, &targets, 0.5).unwrap();
+    assert!((mcnemar.statistic - 25.0 / 6.0).abs() < 1e-12);
+    assert!(mcnemar.p_value < 0.05);
+
+    let predictions: Vec<f64> = (0..50)
+        .map(|i| i as f64 + if i % 2 == 0 { 0.5 } else { -0.5 })
+        .collect();
+    let actual: Vec<f64> = (0..50).map(|i| i as f64).collect();
+    let interval =
+        bootstrap_confidence_interval(&predictions, &actual, mean_absolute_error, 200, 0.9, 7)
+            .unwrap();
+    assert_eq!(interval.estimate, 0.5);
+    assert!(interval.lower <= 0.5 && interval.upper >= 0.5);
+}
+
+#[test]
+fn test_rng_reference_output_and_streams() {
+    let mut reference = DeterministicRng::from_state([1, 2, 3, 4]);
+    let outputs: Vec<u64> = (0..4).map(|_| reference.next_u64()).collect();
+    assert_eq!(outputs, vec![11520, 0, 1509978240, 1215971899390074240]);
+
+    let mut a = DeterministicRng::new(42);
+    let mut b = DeterministicRng::new(42);
+    assert_eq!(a.next_u64(), b.next_u64());
+    let streams = a.streams(3);
+    assert_eq!(streams.len(), 3);
+    assert_ne!(streams[0], streams[1]);
+    assert_eq!(streams, a.streams(3));
+
+    let mut values: Vec<usize> = (0..20).collect();
+    a.shuffle(&mut values);
+    let mut sorted = values.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..20).collect::<Vec<usize>>());
+    let sample = a.sample_without_replacement(10, 4);
+    assert_eq!(sample.len(), 4);
+    assert!(sample.iter().all(|index| *index < 10));
+    assert!(sample
+        .iter()
+        .enumerate()
+        .all(|(i, x)| !sample[i + 1..].contains(x)));
+}
+
+#[test]
+fn test_rng_distribution_moments() {
+    let mut rng = DeterministicRng::new(7);
+    let n = 20_000;
+    let normals: Vec<f64> = (0..n).map(|_| rng.normal(3.0, 2.0)).collect();
+    assert!((mean(&normals) - 3.0).abs() < 0.05);
+    assert!((variance(&normals).sqrt() - 2.0).abs() < 0.05);
+    let exponentials: Vec<f64> = (0..n).map(|_| rng.exponential(4.0)).collect();
+    assert!((mean(&exponentials) - 0.25).abs() < 0.01);
+    for lambda in [3.5, 80.0] {
+        let counts: Vec<f64> = (0..n).map(|_| rng.poisson(lambda) as f64).collect();
+        assert!((mean(&counts) - lambda).abs() < 0.03 * lambda);
+        assert!((variance(&counts) - lambda).abs() < 0.1 * lambda);
+    }
+    let hits = (0..n).filter(|_| rng.bernoulli(0.3)).count() as f64 / n as f64;
+    assert!((hits - 0.3).abs() < 0.02);
+    assert!(rng.log_normal(0.0, 0.5) > 0.0);
+
+    let mut category_counts = [0usize; 3];
+    for _ in 0..n {
+        category_counts[rng.categorical(&[1.0, 0.0, 3.0]).unwrap()] += 1;
+    }
+    assert_eq!(category_counts[1], 0);
+    assert!((category_counts[2] as f64 / n as f64 - 0.75).abs() < 0.02);
+    assert_eq!(rng.categorical(&[0.0, -1.0]), None);
+
+    let ranks: Vec<usize> = (0..n).map(|_| rng.zipf(50, 1.2)).collect();
+    assert!(ranks.iter().all(|rank| (1..=50).contains(rank)));
+    let ones = ranks.iter().filter(|rank| **rank == 1).count();
+    let twos = ranks.iter().filter(|rank| **rank == 2).count();
+    let expected_ratio = 2f64.powf(1.2);
+    assert!((ones as f64 / twos as f64 - expected_ratio).abs() < 0.2);
+}
@@ -1,13 +1,46 @@
+use rustml::config::AppConfig;
+use rustml::data::cleaning::{CleaningConfig, OutlierClipper};
 use rustml::data::dataset::Dataset;
-use rustml::data::synthetic::{generate_network_dataset, SyntheticConfig};
+use rustml::data::feature_engineering::{
+    DerivedFeature, DerivedFeatures, FeatureEngineeringConfig, LogTransform, PolynomialFeatures,
+};
+use rustml::data::selection::{
+    correlation_filter, l1_selection, recursive_feature_elimination, select_k_best,
+    univariate_scores, variance_threshold, FeatureMask, FeatureSelector, SelectionMethod,
+    UnivariateScore,
+};
+use rustml::data::synthetic::{
+    generate_network_dataset, generate_traffic_classes, one_vs_rest, ClassificationConfig,
+    SyntheticConfig, TrafficClass,
+};
+use rustml::data::TransformError;
+use rustml::drift::{DistributionSketch, DriftConfig, DriftMetric, DriftThresholds, PREDICTION};
+use rustml::io::binary::{self, BinaryError};
+use rustml::io::export::{ExportError, LinearExport};
+use rustml::io::codegen::{self, Language};
+use rustml::io::{onnx, pmml};
+use rustml::io::{
+    load_model, save_model, save_model_as, ArtifactError, ArtifactFormat, FeatureMismatch,
+    ModelArtifact, ARTIFACT_SCHEMA_VERSION,
+};
+use rustml::math::random::DeterministicRng;
+use rustml::math::stats::CorrelationMethod;
 use rustml::ml::linear_regression::LinearRegression;
 use rustml::ml::logistic_regression::LogisticRegression;
-use rustml::ml::Model;
-use rustml::pipeline::train_linear_pipeline;
+use rustml::ml::parallel::Parallelism;
+use rustml::ml::{LinearModel, Model};
+use rustml::pipeline::{train_linear_pipeline, Pipeline, TrainingSummary, TransformStep};
+use rustml::registry::{ModelRef, ModelRegistry, RegistryError, Stage};
+use rustml::utils::evaluation::confusion_matrix;
+use rustml::utils::normalization::{normalize_z_score, Scaler};
 
 #[test]
 fn test_linear_regression_training() {
-    let dataset = generate_network_dataset(SyntheticConfig { rows: 64, seed: 10 });
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 64,
+        seed: 10,
+        ..SyntheticConfig::default()
+    });
     let mut model = LinearRegression::new(dataset.num_features());
     let report = model.train(&dataset);
     assert!(report.final_loss.is_finite());
@@ -17,7 +50,11 @@ fn test_linear_regression_training() {
 
 #[test]
 fn test_logistic_regression_training() {
-    let mut dataset = generate_network_dataset(SyntheticConfig { rows: 40, seed: 11 });
+    let mut dataset = generate_network_dataset(SyntheticConfig {
+        rows: 40,
+        seed: 11,
+        ..SyntheticConfig::default()
+    });
     dataset.target = dataset
         .target
         .iter()
@@ -35,4 +72,859 @@ fn test_pipeline_training() {
     let dataset = Dataset::from_csv("data/iris.csv").unwrap();
     let result = train_linear_pipeline(&dataset).unwrap();
     assert!(result.summary.mse.is_finite());
+    match result.pipeline.step("scale") {
+        Some(TransformStep::Scale(Scaler::Standard(scaler))) => {
+            assert_eq!(scaler.mean, result.stats.mean);
+            assert_eq!(scaler.std_dev, result.stats.std_dev);
+        }
+        other => panic!("unexpected scale step {:?}", other),
+    }
+    assert_eq!(
+        result.pipeline.transform(&dataset).unwrap(),
+        result.normalized
+    );
+    assert_eq!(result.pipeline.model.weights(), result.model.weights());
+    assert_eq!(
+        result.pipeline.predict(&dataset).unwrap(),
+        result.model.predict(&result.normalized)
+    );
+}
+
+#[test]
+fn test_parallel_training_is_bit_identical() {
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 257,
+        seed: 12,
+        ..SyntheticConfig::default()
+    });
+    let sequential = Parallelism {
+        threads: 1,
+        chunk_size: 16,
+    };
+    let parallel = Parallelism {
+        threads: 4,
+        chunk_size: 16,
+    };
+    let mut single = LinearRegression::with_params(dataset.num_features(), 1e-6, 25)
+        .with_parallelism(sequential);
+    let mut multi = LinearRegression::with_params(dataset.num_features(), 1e-6, 25)
+        .with_parallelism(parallel);
+    single.train(&dataset);
+    multi.train(&dataset);
+    let single_bits: Vec<u64> = single.weights.iter().map(|w| w.to_bits()).collect();
+    let multi_bits: Vec<u64> = multi.weights.iter().map(|w| w.to_bits()).collect();
+    assert_eq!(single_bits, multi_bits);
+    assert_eq!(single.bias.to_bits(), multi.bias.to_bits());
+    assert_eq!(single.predict(&dataset), multi.predict(&dataset));
+
+    let mut logistic_single = LogisticRegression::new(dataset.num_features()).with_parallelism(sequential);
+    let mut logistic_multi = LogisticRegression::new(dataset.num_features()).with_parallelism(parallel);
+    let mut labels = dataset.clone();
+    labels.target = labels.target.iter().map(|v| if *v > 20.0 { 1.0 } else { 0.0 }).collect();
+    logistic_single.train(&labels);
+    logistic_multi.train(&labels);
+    assert_eq!(logistic_single.weights, logistic_multi.weights);
+}
+
+#[test]
+fn test_parallel_gradients_match_sequential_loop() {
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 2500,
+        seed: 13,
+        ..SyntheticConfig::default()
+    });
+    let (learning_rate, epochs) = (1e-6, 10);
+    let mut weights = vec![0.0; dataset.num_features()];
+    let mut bias = 0.0;
+    let n_samples = dataset.num_rows() as f64;
+    for _ in 0..epochs {
+        let mut weight_gradients = vec![0.0; weights.len()];
+        let mut bias_gradient = 0.0;
+        for (row, target) in dataset.iter_rows() {
+            let error = row.iter().zip(&weights).map(|(x, w)| x * w).sum::<f64>() + bias - target;
+            bias_gradient += error;
+            for (idx, value) in row.iter().enumerate() {
+                weight_gradients[idx] += error * value;
+            }
+        }
+        for (idx, weight) in weights.iter_mut().enumerate() {
+            let gradient = (weight_gradients[idx] / n_samples).clamp(-1_000.0, 1_000.0);
+            *weight -= learning_rate * gradient;
+        }
+        bias -= learning_rate * (bias_gradient / n_samples).clamp(-1_000.0, 1_000.0);
+    }
+
+    let expected: Vec<u64> = weights.iter().map(|w| w.to_bits()).collect();
+    for parallelism in [
+        Parallelism::default(),
+        Parallelism::with_threads(4),
+        Parallelism {
+            threads: 3,
+            chunk_size: 100,
+        },
+    ] {
+        let mut model =
+            LinearRegression::with_params(dataset.num_features(), learning_rate, epochs)
+                .with_parallelism(parallelism);
+        model.train(&dataset);
+        let bits: Vec<u64> = model.weights.iter().map(|w| w.to_bits()).collect();
+        assert_eq!(bits, expected, "{:?}", parallelism);
+        assert_eq!(model.bias.to_bits(), bias.to_bits(), "{:?}", parallelism);
+    }
+}
+
+#[test]
+fn test_logistic_regression_on_traffic_classes() {
+    let classes = generate_traffic_classes(ClassificationConfig {
+        rows: 400,
+        seed: 13,
+        ..ClassificationConfig::default()
+    })
+    .unwrap();
+    let binary = one_vs_rest(&classes, TrafficClass::Video);
+    let (normalized, _) = normalize_z_score(&binary).unwrap();
+    let mut model = LogisticRegression::new(normalized.num_features());
+    model.train(&normalized);
+    let accuracy = confusion_matrix(&model.predict(&normalized), &normalized.target, 0.5).accuracy();
+    assert!(accuracy > 0.9);
+}
+
+#[test]
+fn test_feature_selection() {
+    let mut rng = DeterministicRng::new(17);
+    let rows: Vec<Vec<f64>> = (0..300)
+        .map(|_| {
+            let signal = rng.normal(0.0, 1.0);
+            let weak = rng.normal(0.0, 1.0);
+            vec![signal, signal * 2.0 + rng.normal(0.0, 0.01), weak, rng.normal(0.0, 1.0), 3.0]
+        })
+        .collect();
+    let target: Vec<f64> = rows.iter().map(|row| 3.0 * row[0] + 0.5 * row[2]).collect();
+    let names = ["signal", "signal_copy", "weak", "noise", "constant"];
+    let dataset = Dataset::from_records(
+        names.iter().map(|name| name.to_string()).collect(),
+        "latency",
+        rows,
+        target,
+    )
+    .unwrap();
+
+    let (reduced, mask) = variance_threshold(&dataset, 0.0).unwrap();
+    assert_eq!(reduced.num_features(), 4);
+    assert!(!mask.selected[4]);
+
+    let (_, mask) = correlation_filter(&dataset, 0.95, CorrelationMethod::Pearson).unwrap();
+    assert_eq!(mask.selected_names(), vec!["signal", "weak", "noise", "constant"]);
+
+    let f_scores = univariate_scores(&dataset, UnivariateScore::FTest);
+    assert!(f_scores[0] > f_scores[2] && f_scores[2] > f_scores[3]);
+    let (_, mask) = select_k_best(&dataset, 2, UnivariateScore::MutualInformation).unwrap();
+    assert_eq!(mask.selected_names(), vec!["signal", "signal_copy"]);
+
+    let (filtered, _) = correlation_filter(&reduced, 0.95, CorrelationMethod::Pearson).unwrap();
+    let (standardized, _) = normalize_z_score(&filtered).unwrap();
+    let (_, mask) = recursive_feature_elimination(&standardized, 2, 1, |features| {
+        LinearRegression::with_params(features, 0.05, 400)
+    })
+    .unwrap();
+    assert_eq!(mask.selected_names(), vec!["signal", "weak"]);
+
+    let (lasso, mask) = l1_selection(&dataset, 0.3).unwrap();
+    assert!(mask.selected[0] || mask.selected[1]);
+    assert!(!mask.selected[3] && !mask.selected[4]);
+    let json = serde_json::to_string(&mask).unwrap();
+    let replayed: FeatureMask = serde_json::from_str(&json).unwrap();
+    assert_eq!(replayed.apply(&dataset).unwrap(), lasso);
+}
+
+#[test]
+fn test_pipeline_persists_every_step() {
+    let train = generate_network_dataset(SyntheticConfig {
+        rows: 300,
+        seed: 31,
+        ..SyntheticConfig::default()
+    });
+    let mut pipeline = Pipeline::new(LinearRegression::with_params(0, 0.05, 200))
+        .with_step(
+            "ratios",
+            DerivedFeatures::new(vec![DerivedFeature::ratio("src_bytes", "packet_rate")]),
+        )
+        .with_step("log", LogTransform::log1p(&["flow_duration", "src_bytes"]))
+        .with_step("clip", OutlierClipper::new(CleaningConfig::default()))
+        .with_step("scale", Scaler::from_name("robust").unwrap())
+        .with_step(
+            "select",
+            FeatureSelector::new(SelectionMethod::KBest {
+                k: 5,
+                score: UnivariateScore::FTest,
+            }),
+        );
+    let new_flows = generate_network_dataset(SyntheticConfig {
+        rows: 40,
+        seed: 32,
+        ..SyntheticConfig::default()
+    });
+    assert!(matches!(
+        pipeline.predict(&new_flows),
+        Err(TransformError::NotFitted)
+    ));
+    let report = pipeline.fit(&train).unwrap();
+    assert_eq!(pipeline.model.weights().len(), 5);
+    assert!(matches!(pipeline.step("scale"), Some(TransformStep::Scale(_))));
+
+    let summary = TrainingSummary {
+        mse: 0.0,
+        rmse: 0.0,
+        mae: 0.0,
+        epochs: report.epochs,
+        loss: report.final_loss,
+    };
+    let path = std::env::temp_dir().join("rustml_pipeline_test.json");
+    let path = path.to_str().unwrap();
+    let artifact = ModelArtifact::from_pipeline(&pipeline, &train, summary).unwrap();
+    save_model(path, &artifact).unwrap();
+    let restored = load_model(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(restored.preprocessing, pipeline.steps);
+    let expected = pipeline.predict(&new_flows).unwrap();
+    assert_eq!(restored.predict(&new_flows).unwrap(), expected);
+    assert_eq!(expected.len(), 40);
+
+    let narrow = new_flows.select_features(&[0, 1, 2]);
+    assert!(restored.predict(&narrow).is_err());
+    assert!(pipeline.is_fitted());
+    assert!(pipeline.fit(&narrow).is_err());
+    assert!(!pipeline.is_fitted());
+    assert!(matches!(
+        pipeline.predict(&new_flows),
+        Err(TransformError::NotFitted)
+    ));
+}
+
+#[test]
+fn test_pipeline_config_runs_and_reports_errors() {
+    let dir = std::env::temp_dir().join("rustml_config_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let write = |name: &str, content: &str| {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    };
+    let data = dir.join("flows.csv");
+    generate_network_dataset(SyntheticConfig {
+        rows: 120,
+        seed: 5,
+        ..SyntheticConfig::default()
+    })
+    .to_csv(data.to_str().unwrap())
+    .unwrap();
+    let valid = format!(
+        r#"{{"seed": 3, "pipeline": {{
+            "input": {:?},
+            "columns": {{"ignore": ["jitter"]}},
+            "transformers": [{{"type": "scale", "method": "minmax"}}],
+            "model": {{"kind": "linear_regression", "learning_rate": 0.05, "epochs": 100}},
+            "evaluation": {{"test_ratio": 0.25, "metrics": ["rmse", "mae"]}},
+            "outputs": {{"directory": {:?}, "report": null, "predictions": "predictions.csv"}}
+        }}}}"#,
+        data.to_str().unwrap(),
+        dir.to_str().unwrap()
+    );
+    let config = AppConfig::from_file(&write("valid.json", &valid)).unwrap();
+    let (pipeline, report) = config.run_pipeline().unwrap();
+    assert_eq!(report.test_rows, 30);
+    assert!(!report.features.contains(&"jitter".to_string()));
+    assert_eq!(report.metrics.keys().collect::<Vec<_>>(), ["mae", "rmse"]);
+    assert_eq!(report.outputs.len(), 2);
+    let restored = load_model(&report.outputs[0]).unwrap();
+    assert_eq!(restored.preprocessing, pipeline.steps);
+    assert_eq!(restored.summary.rmse, report.metrics["rmse"]);
+
+    let bad_rate = write(
+        "bad_rate.json",
+        r#"{"pipeline": {"input": "x.csv", "model": {"kind": "logistic_regression", "learning_rate": 0}}}"#,
+    );
+    let err = AppConfig::from_file(&bad_rate).unwrap_err().to_string();
+    assert!(err.contains("pipeline.model.learning_rate"), "{}", err);
+    let typo = write(
+        "typo.json",
+        r#"{"pipeline": {"input": "x.csv", "modle": {"kind": "linear_regression"}}}"#,
+    );
+    let err = AppConfig::from_file(&typo).unwrap_err().to_string();
+    assert!(err.contains("unknown field `modle`"), "{}", err);
+    let bad_outliers = write(
+        "bad_outliers.json",
+        r#"{"pipeline": {"input": "x.csv", "model": {"kind": "linear_regression"},
+            "cleaning": {"outlier_detection": {"columns": {"jitter": {"method": "iqr", "k": -1}}}}}}"#,
+    );
+    let err = AppConfig::from_file(&bad_outliers).unwrap_err().to_string();
+    assert!(err.contains("pipeline.cleaning.outlier_detection"), "{}", err);
+    assert!(err.contains("column 'jitter'"), "{}", err);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_model_artifacts_are_versioned_and_polymorphic() {
+    let classes = generate_traffic_classes(ClassificationConfig {
+        rows: 200,
+        seed: 17,
+        ..ClassificationConfig::default()
+    })
+    .unwrap();
+    let binary = one_vs_rest(&classes, TrafficClass::Voip);
+    let mut pipeline = Pipeline::new(LogisticRegression::new(0))
+        .with_step("scale", Scaler::from_name("zscore").unwrap());
+    let report = pipeline.fit(&binary).unwrap();
+    let summary = TrainingSummary {
+        mse: 0.0,
+        rmse: 0.0,
+        mae: 0.0,
+        epochs: report.epochs,
+        loss: report.final_loss,
+    };
+    let artifact = ModelArtifact::from_pipeline(&pipeline, &binary, summary).unwrap();
+    assert_eq!(artifact.schema_version, ARTIFACT_SCHEMA_VERSION);
+    assert_eq!(artifact.feature_names, binary.feature_names);
+    assert_eq!(artifact.crate_version, env!("CARGO_PKG_VERSION"));
+    let encoded = serde_json::to_string(&artifact).unwrap();
+    assert!(!encoded.contains("parallelism") && !encoded.contains("threads"));
+
+    let path = std::env::temp_dir().join("rustml_artifact_test.json");
+    let path = path.to_str().unwrap();
+    save_model(path, &artifact).unwrap();
+    let loaded = load_model(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(loaded.model.kind(), "logistic_regression");
+    assert_eq!(loaded.target_name, binary.target_name);
+    assert_eq!(
+        loaded.predict(&binary).unwrap(),
+        pipeline.predict(&binary).unwrap()
+    );
+
+    let legacy = serde_json::json!({
+        "model": {"weights": [0.5, -1.0], "bias": 2.0, "learning_rate": 0.001, "epochs": 300},
+        "summary": {"mse": 1.0, "rmse": 1.0, "mae": 1.0, "epochs": 300, "loss": 0.5},
+    });
+    let migrated = ModelArtifact::migrate(legacy).unwrap();
+    assert_eq!(migrated.schema_version, ARTIFACT_SCHEMA_VERSION);
+    assert_eq!(migrated.model.kind(), "linear_regression");
+    assert_eq!(migrated.model.weights(), &[0.5, -1.0]);
+    assert!(migrated.preprocessing.is_empty());
+
+    let future = serde_json::json!({"schema_version": ARTIFACT_SCHEMA_VERSION + 1});
+    assert!(matches!(
+        ModelArtifact::migrate(future),
+        Err(ArtifactError::UnsupportedVersion { .. })
+    ));
+    let wrapped = (1u64 << 32) + u64::from(ARTIFACT_SCHEMA_VERSION);
+    for version in [serde_json::json!(0), serde_json::json!(wrapped)].iter() {
+        let mut value = serde_json::to_value(&artifact).unwrap();
+        value["schema_version"] = version.clone();
+        assert!(matches!(
+            ModelArtifact::migrate(value),
+            Err(ArtifactError::Malformed { version: 0, .. })
+        ));
+    }
+    let mut zeroed = artifact.clone();
+    zeroed.schema_version = 0;
+    let path = std::env::temp_dir().join("rustml_zero_version.bin");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, binary::to_bytes(&zeroed).unwrap()).unwrap();
+    let err = load_model(path).unwrap_err();
+    std::fs::remove_file(path).unwrap();
+    assert!(matches!(
+        err.downcast_ref::<ArtifactError>(),
+        Some(ArtifactError::Malformed { version: 0, .. })
+    ));
+}
+
+#[test]
+fn test_binary_artifacts_round_trip_exactly() {
+    let train = generate_network_dataset(SyntheticConfig {
+        rows: 200,
+        seed: 23,
+        ..SyntheticConfig::default()
+    });
+    let mut pipeline = Pipeline::new(LinearRegression::with_params(0, 0.05, 150))
+        .with_step("log", LogTransform::log1p(&["flow_duration"]))
+        .with_step("scale", Scaler::from_name("minmax").unwrap())
+        .with_step(
+            "select",
+            FeatureSelector::new(SelectionMethod::KBest {
+                k: 4,
+                score: UnivariateScore::MutualInformation,
+            }),
+        );
+    let report = pipeline.fit(&train).unwrap();
+    let summary = TrainingSummary {
+        mse: f64::NAN,
+        rmse: -0.0,
+        mae: f64::MIN_POSITIVE / 2.0,
+        epochs: report.epochs,
+        loss: report.final_loss,
+    };
+    let artifact = ModelArtifact::from_pipeline(&pipeline, &train, summary).unwrap();
+
+    let bytes = binary::to_bytes(&artifact).unwrap();
+    assert!(binary::is_binary(&bytes));
+    assert!(bytes.len() < serde_json::to_vec_pretty(&artifact).unwrap().len());
+    let decoded: ModelArtifact = binary::from_bytes(&bytes).unwrap();
+    assert_eq!(binary::to_bytes(&decoded).unwrap(), bytes);
+    assert_eq!(decoded.summary.rmse.to_bits(), (-0.0_f64).to_bits());
+    assert!(decoded.summary.mse.is_nan());
+    assert_eq!(decoded.preprocessing, artifact.preprocessing);
+
+    let path = std::env::temp_dir().join("rustml_artifact_test.bin");
+    let path = path.to_str().unwrap();
+    save_model_as(path, &artifact, ArtifactFormat::Binary).unwrap();
+    let loaded = load_model(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(
+        loaded.predict(&train).unwrap(),
+        pipeline.predict(&train).unwrap()
+    );
+
+    let mut corrupted = bytes.clone();
+    corrupted[40] ^= 0x01;
+    assert!(matches!(
+        binary::from_bytes::<ModelArtifact>(&corrupted),
+        Err(BinaryError::ChecksumMismatch { .. })
+    ));
+    assert_eq!(
+        binary::from_bytes::<ModelArtifact>(&bytes[..bytes.len() - 1]).unwrap_err(),
+        BinaryError::Truncated
+    );
+
+    // `depth` single-element sequences (tag 8) wrapped around a null (tag 0).
+    let nested = |depth: usize| {
+        let mut payload = Vec::new();
+        for _ in 0..depth {
+            payload.push(8);
+            payload.extend_from_slice(&1u64.to_le_bytes());
+        }
+        payload.push(0);
+        let mut bytes = binary::MAGIC.to_vec();
+        bytes.extend_from_slice(&binary::FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        bytes.extend_from_slice(&binary::crc32(&payload).to_le_bytes());
+        bytes
+    };
+    assert!(binary::from_bytes::<serde_json::Value>(&nested(128)).is_ok());
+    assert_eq!(
+        binary::from_bytes::<serde_json::Value>(&nested(200_000)).unwrap_err(),
+        BinaryError::TooDeep(128)
+    );
+}
+
+#[test]
+fn test_artifacts_verify_hash_and_feature_schema() {
+    let iris = Dataset::from_csv("data/iris.csv").unwrap();
+    let result = train_linear_pipeline(&iris).unwrap();
+    let artifact = ModelArtifact::from_pipeline(&result.pipeline, &iris, result.summary).unwrap();
+    artifact.verify_hash().unwrap();
+    let unfitted = Pipeline::new(LinearRegression::new(0));
+    assert!(matches!(
+        ModelArtifact::from_pipeline(&unfitted, &iris, artifact.summary.clone()),
+        Err(TransformError::NotFitted)
+    ));
+
+    let network = generate_network_dataset(SyntheticConfig {
+        rows: 10,
+        seed: 3,
+        ..SyntheticConfig::default()
+    });
+    match artifact.predict(&network) {
+        Err(ArtifactError::SchemaMismatch(mismatches)) => {
+            assert_eq!(
+                mismatches[0],
+                FeatureMismatch::Count {
+                    expected: 4,
+                    found: network.num_features()
+                }
+            );
+            assert!(mismatches.contains(&FeatureMismatch::Missing {
+                name: "sepal_length".to_string()
+            }));
+            assert!(mismatches.contains(&FeatureMismatch::Unexpected {
+                name: "jitter".to_string()
+            }));
+        }
+        other => panic!("expected a schema mismatch, got {:?}", other),
+    }
+    let swapped = iris.select_features(&[1, 0, 2, 3]);
+    match artifact.predict(&swapped) {
+        Err(ArtifactError::SchemaMismatch(mismatches)) => assert_eq!(
+            mismatches,
+            vec![
+                FeatureMismatch::OutOfOrder {
+                    name: "sepal_length".to_string(),
+                    expected: 0,
+                    found: 1
+                },
+                FeatureMismatch::OutOfOrder {
+                    name: "sepal_width".to_string(),
+                    expected: 1,
+                    found: 0
+                },
+            ]
+        ),
+        other => panic!("expected a schema mismatch, got {:?}", other),
+    }
+
+    let path = std::env::temp_dir().join("rustml_tampered_artifact.json");
+    let path = path.to_str().unwrap();
+    let mut value = serde_json::to_value(&artifact).unwrap();
+    value["model"]["bias"] = serde_json::json!(123.0);
+    std::fs::write(path, value.to_string()).unwrap();
+    let err = load_model(path).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ArtifactError>(),
+        Some(ArtifactError::HashMismatch { .. })
+    ));
+
+    let mut unhashed = serde_json::to_value(&artifact).unwrap();
+    unhashed.as_object_mut().unwrap().remove("content_hash");
+    std::fs::write(path, unhashed.to_string()).unwrap();
+    let err = load_model(path).unwrap_err();
+    std::fs::remove_file(path).unwrap();
+    assert!(matches!(
+        err.downcast_ref::<ArtifactError>(),
+        Some(ArtifactError::Malformed { .. })
+    ));
+}
+
+#[test]
+fn test_onnx_and_pmml_exports_match_predictions() {
+    let classes = generate_traffic_classes(ClassificationConfig {
+        rows: 150,
+        seed: 29,
+        ..ClassificationConfig::default()
+    })
+    .unwrap();
+    let binary_classes = one_vs_rest(&classes, TrafficClass::Attack);
+    let (normalized, stats) = normalize_z_score(&binary_classes).unwrap();
+    let mut logistic = LogisticRegression::new(normalized.num_features());
+    logistic.train(&normalized);
+    let export = LinearExport::new(
+        &logistic.clone().into(),
+        &binary_classes.feature_names,
+        &binary_classes.target_name,
+    )
+    .unwrap()
+    .with_normalization(&stats)
+    .unwrap();
+    let rows: Vec<Vec<f64>> = (0..binary_classes.num_rows())
+        .map(|idx| binary_classes.row(idx).to_vec())
+        .collect();
+    let expected = logistic.predict(&normalized);
+    let close = |actual: Vec<f64>| {
+        assert_eq!(actual.len(), expected.len());
+        for (a, b) in actual.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-12, "{} vs {}", a, b);
+        }
+    };
+    close(onnx::evaluate(&onnx::to_onnx(&export), &rows).unwrap());
+    close(pmml::evaluate(&pmml::to_pmml(&export), &rows).unwrap());
+
+    let iris = Dataset::from_csv("data/iris.csv").unwrap();
+    let result = train_linear_pipeline(&iris).unwrap();
+    let artifact = ModelArtifact::from_pipeline(&result.pipeline, &iris, result.summary).unwrap();
+    let export = LinearExport::from_artifact(&artifact).unwrap();
+    assert!(export.clip.is_some() && export.scaling.is_some());
+    let rows: Vec<Vec<f64>> = (0..iris.num_rows())
+        .map(|idx| iris.row(idx).to_vec())
+        .collect();
+    let expected = artifact.predict(&iris).unwrap();
+    for actual in &[
+        onnx::evaluate(&onnx::to_onnx(&export), &rows).unwrap(),
+        pmml::evaluate(&pmml::to_pmml(&export), &rows).unwrap(),
+    ] {
+        for (a, b) in actual.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-9, "{} vs {}", a, b);
+        }
+    }
+
+    let mut expanded = Pipeline::new(LinearRegression::new(0)).with_step(
+        "poly",
+        PolynomialFeatures::new(FeatureEngineeringConfig::default()),
+    );
+    expanded.fit(&iris).unwrap();
+    let artifact = ModelArtifact::from_pipeline(&expanded, &iris, artifact.summary).unwrap();
+    assert!(matches!(
+        LinearExport::from_artifact(&artifact),
+        Err(ExportError::UnsupportedStep { .. })
+    ));
+}
+
+/// Compiles `source` plus a `main` that prints one prediction per row, runs
+/// it and parses the output. Returns `None` when the compiler is missing.
+fn run_generated(export: &LinearExport, language: Language, rows: &[Vec<f64>]) -> Option<Vec<f64>> {
+    let dir = std::env::temp_dir().join(format!("rustml_codegen_{:?}", language));
+    std::fs::create_dir_all(&dir).unwrap();
+    let literal_rows: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let values: Vec<String> = row.iter().map(|value| format!("{:?}", value)).collect();
+            values.join(", ")
+        })
+        .collect();
+    let binary = dir.join("predictor");
+    let mut source = codegen::generate(export, language);
+    let mut command = match language {
+        Language::Rust => {
+            source.push_str(&format!(
+                "fn main() {{\n    let rows: [[f64; NUM_FEATURES]; {}] = [[{}]];\n    for row in rows.iter() {{\n        println!(\"{{:?}}\", predict(row));\n    }}\n}}\n",
+                rows.len(),
+                literal_rows.join("], [")
+            ));
+            let path = dir.join("predictor.rs");
+            std::fs::write(&path, &source).unwrap();
+            let mut command = std::process::Command::new("rustc");
+            command.arg("--edition=2018").arg("-O").arg(&path);
+            command
+        }
+        Language::C => {
+            source.push_str(&format!(
+                "#include <stdio.h>\nint main(void) {{\n    static const double rows[{}][NUM_FEATURES] = {{{{{}}}}};\n    for (int i = 0; i < {}; i++) printf(\"%.17g\\n\", predict(rows[i]));\n    return 0;\n}}\n",
+                rows.len(),
+                literal_rows.join("}, {"),
+                rows.len()
+            ));
+            let path = dir.join("predictor.c");
+            std::fs::write(&path, &source).unwrap();
+            let mut command = std::process::Command::new("cc");
+            command
+                .arg("-std=c99")
+                .arg("-O2")
+                .arg("-ffp-contract=off")
+                .arg(&path)
+                .arg("-lm");
+            command
+        }
+    };
+    let compiled = match command.arg("-o").arg(&binary).output() {
+        Ok(output) => output,
+        Err(_) => return None,
+    };
+    assert!(
+        compiled.status.success(),
+        "{}",
+        String::from_utf8_lossy(&compiled.stderr)
+    );
+    assert!(compiled.stderr.is_empty(), "{}", String::from_utf8_lossy(&compiled.stderr));
+    let output = std::process::Command::new(&binary).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    Some(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect(),
+    )
+}
+
+#[test]
+fn test_generated_predictors_match_model() {
+    let iris = Dataset::from_csv("data/iris.csv").unwrap();
+    let result = train_linear_pipeline(&iris).unwrap();
+    let artifact = ModelArtifact::from_pipeline(&result.pipeline, &iris, result.summary).unwrap();
+    let linear = LinearExport::from_artifact(&artifact).unwrap();
+    let rows: Vec<Vec<f64>> = (0..iris.num_rows())
+        .step_by(7)
+        .map(|idx| iris.row(idx).to_vec())
+        .collect();
+    let expected: Vec<f64> = artifact
+        .predict(&iris)
+        .unwrap()
+        .into_iter()
+        .step_by(7)
+        .collect();
+
+    let classes = one_vs_rest(
+        &generate_traffic_classes(ClassificationConfig {
+            rows: 120,
+            seed: 41,
+            ..ClassificationConfig::default()
+        })
+        .unwrap(),
+        TrafficClass::Web,
+    );
+    let (normalized, stats) = normalize_z_score(&classes).unwrap();
+    let mut model = LogisticRegression::new(normalized.num_features());
+    model.train(&normalized);
+    let logistic = LinearExport::new(&model.clone().into(), &classes.feature_names, "is_web")
+        .unwrap()
+        .with_normalization(&stats)
+        .unwrap();
+    let class_rows: Vec<Vec<f64>> = (0..10).map(|idx| classes.row(idx).to_vec()).collect();
+    let class_expected = model.predict(&normalized)[..10].to_vec();
+
+    for language in [Language::Rust, Language::C].iter().cloned() {
+        for (export, rows, expected) in [
+            (&linear, &rows, &expected),
+            (&logistic, &class_rows, &class_expected),
+        ]
+        .iter()
+        {
+            let actual = match run_generated(export, language, rows) {
+                Some(actual) => actual,
+                None => {
+                    eprintln!("skipping {:?}: compiler not found", language);
+                    continue;
+                }
+            };
+            assert_eq!(actual.len(), expected.len());
+            for (a, b) in actual.iter().zip(expected.iter()) {
+                assert!((a - b).abs() < 1e-9, "{:?}: {} vs {}", language, a, b);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_model_registry_tracks_runs_and_stages() {
+    let dir = std::env::temp_dir().join("rustml_registry_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    let registry = ModelRegistry::new(&dir);
+    assert!(registry.list().unwrap().is_empty());
+
+    let mut versions = Vec::new();
+    for seed in [3, 4].iter() {
+        let dataset = generate_network_dataset(SyntheticConfig {
+            rows: 80,
+            seed: *seed,
+            ..SyntheticConfig::default()
+        });
+        let result = train_linear_pipeline(&dataset).unwrap();
+        let artifact =
+            ModelArtifact::from_pipeline(&result.pipeline, &dataset, result.summary).unwrap();
+        let run = registry
+            .register("traffic", &artifact, &dataset, Some("synthetic.csv"))
+            .unwrap();
+        assert_eq!(run.dataset.fingerprint, dataset.fingerprint());
+        assert_eq!(run.artifact_hash, artifact.content_hash);
+        versions.push((run.version, artifact));
+    }
+    assert_eq!(versions[0].0, 1);
+    assert_eq!(versions[1].0, 2);
+    assert_eq!(registry.models().unwrap(), vec!["traffic".to_string()]);
+    assert_eq!(registry.runs("traffic").unwrap().len(), 2);
+
+    let comparison = registry.compare("traffic", 1, 2).unwrap();
+    assert!(!comparison.same_dataset);
+    assert!(comparison.same_features);
+    assert!(comparison.parameters.is_empty());
+    assert_eq!(comparison.metrics[0].metric, "mse");
+
+    registry.set_stage("traffic", 1, Stage::Production).unwrap();
+    registry.set_stage("traffic", 2, Stage::Production).unwrap();
+    assert_eq!(registry.run("traffic", 1).unwrap().stage, Stage::Archived);
+    let (run, loaded) = registry
+        .load("traffic", ModelRef::Stage(Stage::Production))
+        .unwrap();
+    assert_eq!(run.version, 2);
+    assert_eq!(loaded.content_hash, versions[1].1.content_hash);
+    assert_eq!(
+        registry.resolve("traffic", ModelRef::Latest).unwrap().version,
+        2
+    );
+    assert!(matches!(
+        registry.resolve("traffic", ModelRef::Stage(Stage::Staging)),
+        Err(RegistryError::NoModelInStage { .. })
+    ));
+    assert!(matches!(
+        registry.run("traffic", 9),
+        Err(RegistryError::UnknownVersion { .. })
+    ));
+    assert!(matches!(
+        registry.versions("../escape"),
+        Err(RegistryError::InvalidName(_))
+    ));
+    assert_eq!(ModelRef::parse("v3"), Some(ModelRef::Version(3)));
+
+    let dataset = generate_network_dataset(SyntheticConfig {
+        rows: 40,
+        seed: 5,
+        ..SyntheticConfig::default()
+    });
+    std::fs::create_dir(dir.join("traffic").join("v3")).unwrap();
+    assert_eq!(registry.versions("traffic").unwrap(), vec![1, 2]);
+    let run = registry
+        .register("traffic", &versions[1].1, &dataset, None)
+        .unwrap();
+    assert_eq!(run.version, 4);
+    assert_eq!(registry.versions("traffic").unwrap(), vec![1, 2, 4]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_drift_detection_against_reference_profile() {
+    let train = generate_network_dataset(SyntheticConfig {
+        rows: 400,
+        seed: 21,
+        ..SyntheticConfig::default()
+    });
+    let result = train_linear_pipeline(&train).unwrap();
+    let artifact = ModelArtifact::from_pipeline(&result.pipeline, &train, result.summary).unwrap();
+    let reference = artifact.reference.as_ref().unwrap();
+    assert_eq!(reference.features.len(), train.num_features());
+    assert!(reference.predictions.is_some());
+
+    let values: Vec<f64> = (0..500).map(|idx| idx as f64).collect();
+    let sketch = DistributionSketch::new(&values, 10).unwrap();
+    assert_eq!(sketch.proportions.len(), 10);
+    assert!(sketch.psi(&values).abs() < 1e-12);
+    assert!(sketch.jensen_shannon(&values).abs() < 1e-12);
+    assert!(sketch.ks(&values) < 0.01);
+    let shifted: Vec<f64> = values.iter().map(|value| value + 250.0).collect();
+    assert!((sketch.ks(&shifted) - 0.5).abs() < 0.01);
+    assert!(sketch.psi(&shifted) > 1.0);
+    assert!(sketch.jensen_shannon(&shifted) > 0.3);
+    assert!(DistributionSketch::new(&[f64::NAN], 10).is_none());
+
+    let config = DriftConfig::default();
+    let same = reference.compare(&train, None, &config).unwrap();
+    assert!(same.evaluated);
+    assert!(!same.has_alerts());
+    assert!(same.prediction.is_none());
+
+    let mut live = generate_network_dataset(SyntheticConfig {
+        rows: 200,
+        seed: 22,
+        ..SyntheticConfig::default()
+    });
+    for row in live.data.iter_rows_mut() {
+        row[0] = row[0] * 3.0 + 10.0;
+    }
+    let predictions = artifact.predict(&live).unwrap();
+    let report = reference.compare(&live, Some(&predictions), &config).unwrap();
+    let drifted: Vec<&str> = report
+        .features
+        .iter()
+        .filter(|feature| feature.drifted())
+        .map(|feature| feature.name.as_str())
+        .collect();
+    assert_eq!(drifted, vec![live.feature_names[0].as_str()]);
+    assert!(report
+        .alerts
+        .iter()
+        .any(|alert| alert.metric == DriftMetric::Psi));
+    assert_eq!(report.prediction.as_ref().unwrap().name, PREDICTION);
+
+    let mut lenient = DriftConfig::default();
+    lenient.overrides.insert(
+        live.feature_names[0].clone(),
+        DriftThresholds {
+            psi: f64::INFINITY,
+            ks: 1.0,
+            jensen_shannon: 1.0,
+        },
+    );
+    let report = reference.compare(&live, None, &lenient).unwrap();
+    assert!(!report.has_alerts());
+    let small = live.subset(&[0, 1, 2]).unwrap();
+    let report = reference.compare(&small, None, &config).unwrap();
+    assert!(!report.evaluated && !report.has_alerts());
+
 }